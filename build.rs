@@ -0,0 +1,21 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+/// Generates the C header for the `ffi` module in `OUT_DIR`, copied to include/ by
+/// scripts/update_header.sh.
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=src/checkers/ffi.rs");
+    let out_dir = std::env::var("OUT_DIR").expect("cargo should set OUT_DIR");
+    cbindgen::Builder::new()
+        .with_src("src/checkers/ffi.rs")
+        .with_language(cbindgen::Language::C)
+        .with_include_guard("BIT_CHECKERS_H")
+        .with_header("/* Generated by cbindgen from src/checkers/ffi.rs, do not edit. */")
+        .with_cpp_compat(true)
+        .generate()
+        .expect("C header should be generated")
+        .write_to_file(std::path::Path::new(&out_dir).join("bit_checkers.h"));
+}
@@ -0,0 +1,187 @@
+/* Generated by cbindgen from src/checkers/ffi.rs, do not edit. */
+
+#ifndef BIT_CHECKERS_H
+#define BIT_CHECKERS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Maximum number of squares in the path of a move.
+ */
+#define BC_MAX_PATH 13
+
+/**
+ * Value for no player, returned by `bc_board_winner` while the game is in progress.
+ */
+#define BC_NONE 0
+
+/**
+ * Value for Player1 (Black), who moves first.
+ */
+#define BC_PLAYER1 1
+
+/**
+ * Value for Player2 (White).
+ */
+#define BC_PLAYER2 2
+
+/**
+ * Status returned when a call succeeds.
+ */
+#define BC_OK 0
+
+/**
+ * Status returned when a required pointer argument is null.
+ */
+#define BC_ERROR_NULL -1
+
+/**
+ * Status returned when a move is not legal for the player to move.
+ */
+#define BC_ERROR_ILLEGAL_MOVE -2
+
+/**
+ * Opaque handle to a board and the player to move.
+ */
+typedef struct BcBoard BcBoard;
+
+/**
+ * A move as the path of squares visited by the moving piece, in the standard 1 to 32
+ * numbering. A jump over several pieces visits more than two squares.
+ */
+typedef struct BcMove {
+  /**
+   * Number of squares in the path, at least 2.
+   */
+  uint8_t len;
+  uint8_t squares[BC_MAX_PATH];
+} BcMove;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns a new board with pieces in the initial positions and Player1 to move.
+ *
+ * The board must be freed with `bc_board_free`.
+ */
+struct BcBoard *bc_board_new(void);
+
+/**
+ * Returns a new board from a FEN string such as `B:W21-32:B1-12`, or null if the string is
+ * null or not a valid position.
+ *
+ * The board must be freed with `bc_board_free`.
+ *
+ * # Safety
+ *
+ * `fen` must be null or point to a null terminated string.
+ */
+struct BcBoard *bc_board_from_fen(const char *fen);
+
+/**
+ * Returns a copy of a board, or null if the board is null.
+ *
+ * The copy must be freed with `bc_board_free`.
+ *
+ * # Safety
+ *
+ * `board` must be null or a board returned by this library that hasn't been freed.
+ */
+struct BcBoard *bc_board_clone(const struct BcBoard *board);
+
+/**
+ * Frees a board. Does nothing if the board is null.
+ *
+ * # Safety
+ *
+ * `board` must be null or a board returned by this library that hasn't been freed.
+ */
+void bc_board_free(struct BcBoard *board);
+
+/**
+ * Returns `BC_PLAYER1` or `BC_PLAYER2` for the player to move, or `BC_ERROR_NULL` if the board
+ * is null.
+ *
+ * # Safety
+ *
+ * `board` must be null or a board returned by this library that hasn't been freed.
+ */
+int32_t bc_board_to_move(const struct BcBoard *board);
+
+/**
+ * Writes the legal moves for the player to move into a buffer, returning the number of legal
+ * moves.
+ *
+ * At most `capacity` moves are written, so if the result is larger than `capacity` the call can
+ * be repeated with a larger buffer. `moves` may be null if `capacity` is 0. Returns 0 if the
+ * board is null.
+ *
+ * # Safety
+ *
+ * `board` must be null or a board returned by this library that hasn't been freed, and `moves`
+ * must point to a buffer of at least `capacity` moves.
+ */
+uintptr_t bc_board_legal_moves(const struct BcBoard *board,
+                               struct BcMove *moves,
+                               uintptr_t capacity);
+
+/**
+ * Plays a move for the player to move, returning `BC_OK`, `BC_ERROR_ILLEGAL_MOVE` if the move
+ * is not legal (including after the game has been won) or `BC_ERROR_NULL`.
+ *
+ * # Safety
+ *
+ * `board` must be null or a board returned by this library that hasn't been freed, and
+ * `bc_move` must be null or point to a move.
+ */
+int32_t bc_board_apply_move(struct BcBoard *board, const struct BcMove *bc_move);
+
+/**
+ * Returns the player who has won because their opponent can't move, `BC_NONE` if the game is
+ * in progress, or `BC_ERROR_NULL` if the board is null.
+ *
+ * # Safety
+ *
+ * `board` must be null or a board returned by this library that hasn't been freed.
+ */
+int32_t bc_board_winner(const struct BcBoard *board);
+
+/**
+ * Writes the board as a null terminated FEN string, returning the length of the string without
+ * the terminator.
+ *
+ * Like `snprintf`, the string is truncated if the buffer is too small, so the result can be
+ * used to size the buffer. `buffer` may be null if `capacity` is 0. Returns 0 if the board is
+ * null.
+ *
+ * # Safety
+ *
+ * `board` must be null or a board returned by this library that hasn't been freed, and
+ * `buffer` must point to at least `capacity` bytes.
+ */
+uintptr_t bc_board_fen(const struct BcBoard *board, char *buffer, uintptr_t capacity);
+
+/**
+ * Writes a move in standard notation such as `11-15` or `9x18x27` as a null terminated string,
+ * returning the length of the string without the terminator.
+ *
+ * The string is truncated in the same way as `bc_board_fen`. Returns 0 if the move is null or
+ * invalid.
+ *
+ * # Safety
+ *
+ * `bc_move` must be null or point to a move, and `buffer` must point to at least `capacity`
+ * bytes.
+ */
+uintptr_t bc_move_notation(const struct BcMove *bc_move, char *buffer, uintptr_t capacity);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BIT_CHECKERS_H */
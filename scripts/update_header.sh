@@ -0,0 +1,19 @@
+#!/bin/sh
+# Copies the C header generated by build.rs into include/bit_checkers.h, or with --check fails if
+# the committed header is out of date.
+set -eu
+
+cd "$(dirname "$0")/.."
+out_dir=$(cargo build --lib --features ffi --message-format=json |
+    jq -r 'select(.reason == "build-script-executed" and (.package_id | contains("bit_checkers")))
+        | .out_dir')
+header="$out_dir/bit_checkers.h"
+
+if [ "${1:-}" = "--check" ]; then
+    if ! diff -u include/bit_checkers.h "$header"; then
+        echo "include/bit_checkers.h is out of date, run scripts/update_header.sh" >&2
+        exit 1
+    fi
+else
+    cp "$header" include/bit_checkers.h
+fi
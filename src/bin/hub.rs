@@ -0,0 +1,6 @@
+use std::io;
+
+/// Runs the engine using the Hub-like text protocol on stdin and stdout.
+fn main() -> io::Result<()> {
+    bit_checkers::hub::run(io::stdin().lock(), io::stdout())
+}
@@ -0,0 +1,22 @@
+use bit_checkers::arena::{self, MatchOptions};
+use std::env;
+use std::io;
+use std::process;
+
+/// Plays a match between two engines to measure the difference in their strength. See
+/// `arena::USAGE` for the options.
+fn main() -> io::Result<()> {
+    if env::args().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", arena::USAGE);
+        return Ok(());
+    }
+    let options = match MatchOptions::from_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, arena::USAGE);
+            process::exit(2);
+        }
+    };
+    arena::run(&options, io::stdout().lock())?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use bit_checkers::arena::{self, TournamentConfig, TOURNAMENT_USAGE};
+use std::env;
+use std::fs;
+use std::io;
+use std::process;
+
+/// Plays a tournament between the players in a config file. See `arena::TOURNAMENT_USAGE` for
+/// the format of the file.
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = match args.as_slice() {
+        [arg] if arg != "--help" && arg != "-h" => arg,
+        [_] => {
+            println!("{}", TOURNAMENT_USAGE);
+            return Ok(());
+        }
+        _ => {
+            eprintln!("{}", TOURNAMENT_USAGE);
+            process::exit(2);
+        }
+    };
+    let config: TournamentConfig = match fs::read_to_string(path)?.parse() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}: {}", path, error);
+            process::exit(2);
+        }
+    };
+    arena::run_tournament(&config, io::stdout().lock())?;
+    Ok(())
+}
@@ -0,0 +1,456 @@
+use crate::checkers::engine::{Engine, SearchLimits};
+use crate::checkers::fen::Fen;
+use crate::checkers::game::Game;
+use crate::checkers::hub::Message;
+use crate::checkers::turn::Turn;
+use crate::checkers::util::Rng;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Arguments of an engine spec for any kind of engine.
+const ARGUMENTS: [&str; 6] = ["name", "depth", "nodes", "time", "threads", "hash"];
+
+/// Type representing a player in matches and tournaments, which chooses turns in games.
+pub trait Contestant {
+    /// Prepares for a new game, forgetting anything learnt from earlier games.
+    fn new_game(&mut self) -> io::Result<()>;
+
+    /// Returns the turn chosen for the player to move in a game in progress.
+    fn choose_turn(&mut self, game: &Game) -> io::Result<Turn>;
+}
+
+/// Type of engine described by an `EngineSpec`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum EngineKind {
+    /// The engine built into this crate.
+    Builtin,
+    /// An external engine run as a child process, using the protocol of `hub::run`.
+    Hub { command: PathBuf, args: Vec<String> },
+    /// A player choosing legal turns at random, seeded from the process' source of randomness if
+    /// no seed is given. It ignores the search limits.
+    Random { seed: Option<u64> },
+}
+
+/// Description of an engine and how long it searches for, from which any number of copies can
+/// be started to play games in parallel.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::arena::{EngineKind, EngineSpec};
+/// let spec: EngineSpec = "hub command=./old_engine depth=8 hash=64".parse().unwrap();
+///
+/// assert_eq!(spec.name, "old_engine");
+/// assert_eq!(spec.limits.depth, Some(8));
+/// assert_eq!(spec.hash_size, Some(64));
+/// assert!(matches!(spec.kind, EngineKind::Hub { .. }));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct EngineSpec {
+    /// Name shown in results.
+    pub name: String,
+    pub kind: EngineKind,
+    /// Limits for the search of each turn.
+    pub limits: SearchLimits,
+    /// Number of search threads, or None for the engine's default.
+    pub threads: Option<usize>,
+    /// Size of the transposition table in megabytes, or None for the engine's default.
+    pub hash_size: Option<usize>,
+}
+
+/// Error returned when parsing an engine spec fails.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseSpecError(String);
+
+/// The built-in engine searching each turn with fixed limits.
+struct BuiltinEngine {
+    engine: Engine,
+    limits: SearchLimits,
+}
+
+/// A player choosing each turn at random from the legal turns.
+struct RandomMover {
+    rng: Rng,
+}
+
+/// Type representing an external engine running as a child process, which is sent commands of
+/// the protocol of `hub::run` on its standard input and replies on its standard output.
+pub struct HubEngine {
+    child: Child,
+    input: BufReader<ChildStdout>,
+    output: ChildStdin,
+    limits: SearchLimits,
+}
+
+impl EngineSpec {
+    /// Returns a spec for the built-in engine with the engine's default threads and table size.
+    pub fn builtin(name: &str, limits: SearchLimits) -> EngineSpec {
+        EngineSpec {
+            name: name.to_string(),
+            kind: EngineKind::Builtin,
+            limits,
+            threads: None,
+            hash_size: None,
+        }
+    }
+
+    /// Starts a copy of the engine.
+    pub fn create(&self) -> io::Result<Box<dyn Contestant>> {
+        Ok(match &self.kind {
+            EngineKind::Builtin => {
+                let mut engine = Engine::new().with_threads(self.threads.unwrap_or(1));
+                if let Some(hash_size) = self.hash_size {
+                    engine = engine.with_hash_size(hash_size);
+                }
+                Box::new(BuiltinEngine {
+                    engine,
+                    limits: self.limits,
+                })
+            }
+            EngineKind::Hub { command, args } => Box::new(HubEngine::start(
+                command,
+                args,
+                self.threads,
+                self.hash_size,
+                self.limits,
+            )?),
+            EngineKind::Random { seed } => Box::new(RandomMover {
+                rng: seed.map_or_else(Rng::from_entropy, Rng::new),
+            }),
+        })
+    }
+}
+
+impl FromStr for EngineSpec {
+    type Err = ParseSpecError;
+
+    /// Parses a spec written as a kind followed by `name=value` arguments in the format of a
+    /// `hub::Message`:
+    ///
+    /// - `builtin`, `hub command=<path> [args="<arguments>"]` or `random [seed=<n>]`
+    /// - `name=<name>`, which defaults to the kind or the file name of the command.
+    /// - `depth=<n>`, `nodes=<n>` or `time=<seconds>` to limit the search of each turn, of which
+    ///   at least one must be given for an engine.
+    /// - `threads=<n>` and `hash=<megabytes>`.
+    fn from_str(s: &str) -> Result<EngineSpec, ParseSpecError> {
+        let message: Message = s
+            .parse()
+            .map_err(|_| ParseSpecError(format!("invalid engine \"{}\"", s)))?;
+        let kind = match message.command.as_str() {
+            "builtin" => EngineKind::Builtin,
+            "hub" => EngineKind::Hub {
+                command: message
+                    .get("command")
+                    .filter(|command| !command.is_empty())
+                    .map(PathBuf::from)
+                    .ok_or_else(|| ParseSpecError(format!("{} needs a command", s)))?,
+                args: message
+                    .get("args")
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+            },
+            "random" => EngineKind::Random {
+                seed: number(&message, "seed")?,
+            },
+            kind => {
+                return Err(ParseSpecError(format!(
+                    "unknown engine kind \"{}\", expected builtin, hub or random",
+                    kind
+                )))
+            }
+        };
+        for (name, _) in &message.args {
+            let kind_argument = match kind {
+                EngineKind::Builtin => false,
+                EngineKind::Hub { .. } => name == "command" || name == "args",
+                EngineKind::Random { .. } => name == "seed",
+            };
+            if !ARGUMENTS.contains(&name.as_str()) && !kind_argument {
+                return Err(ParseSpecError(format!("unknown argument \"{}\"", name)));
+            }
+        }
+
+        let limits = SearchLimits {
+            depth: number(&message, "depth")?,
+            nodes: number(&message, "nodes")?,
+            time: number::<f64>(&message, "time")?
+                .map(|time| {
+                    Duration::try_from_secs_f64(time)
+                        .map_err(|_| ParseSpecError(format!("invalid time {}", time)))
+                })
+                .transpose()?,
+        };
+        if limits == SearchLimits::default() && !matches!(kind, EngineKind::Random { .. }) {
+            return Err(ParseSpecError(format!(
+                "{} needs a depth, nodes or time limit",
+                s
+            )));
+        }
+        let name = match (message.get("name"), &kind) {
+            (Some(name), _) => name.to_string(),
+            (None, EngineKind::Builtin) => "builtin".to_string(),
+            (None, EngineKind::Random { .. }) => "random".to_string(),
+            (None, EngineKind::Hub { command, .. }) => command
+                .file_stem()
+                .map_or_else(|| "hub".to_string(), |stem| stem.to_string_lossy().into()),
+        };
+        Ok(EngineSpec {
+            name,
+            kind,
+            limits,
+            threads: number(&message, "threads")?,
+            hash_size: number(&message, "hash")?,
+        })
+    }
+}
+
+impl Contestant for BuiltinEngine {
+    fn new_game(&mut self) -> io::Result<()> {
+        self.engine.clear_hash();
+        Ok(())
+    }
+
+    fn choose_turn(&mut self, game: &Game) -> io::Result<Turn> {
+        self.engine
+            .search(game.board(), game.to_move(), self.limits)
+            .turn
+            .ok_or_else(|| invalid_data("the game has finished".to_string()))
+    }
+}
+
+impl Contestant for RandomMover {
+    fn new_game(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn choose_turn(&mut self, game: &Game) -> io::Result<Turn> {
+        let turns: Vec<Turn> = game.legal_turns().collect();
+        if turns.is_empty() {
+            return Err(invalid_data("the game has finished".to_string()));
+        }
+        Ok(turns[self.rng.below(turns.len() as u64) as usize])
+    }
+}
+
+impl HubEngine {
+    /// Starts an engine process and initialises it, setting the `threads` and `hash` parameters
+    /// if they are given.
+    pub fn start(
+        command: &Path,
+        args: &[String],
+        threads: Option<usize>,
+        hash_size: Option<usize>,
+        limits: SearchLimits,
+    ) -> io::Result<HubEngine> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let input = BufReader::new(child.stdout.take().expect("stdout should be piped"));
+        let output = child.stdin.take().expect("stdin should be piped");
+        let mut engine = HubEngine {
+            child,
+            input,
+            output,
+            limits,
+        };
+
+        engine.send(&Message::new("hub"))?;
+        engine.receive("wait")?;
+        let parameters = [("threads", threads), ("hash", hash_size)];
+        for (name, value) in parameters.iter() {
+            if let Some(value) = value {
+                engine.send(
+                    &Message::new("set-param")
+                        .arg("name", name)
+                        .arg("value", value),
+                )?;
+            }
+        }
+        engine.send(&Message::new("init"))?;
+        engine.receive("ready")?;
+        Ok(engine)
+    }
+
+    fn send(&mut self, message: &Message) -> io::Result<()> {
+        writeln!(self.output, "{}", message)?;
+        self.output.flush()
+    }
+
+    /// Reads messages until one with the given command is received, returning it. Fails if the
+    /// engine sends an `error` message or exits.
+    fn receive(&mut self, command: &str) -> io::Result<Message> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("engine exited while waiting for {}", command),
+                ));
+            }
+            let message = match line.parse::<Message>() {
+                Ok(message) => message,
+                // Blank or unparseable lines are ignored, as other engines may log to stdout
+                Err(_) => continue,
+            };
+            if message.command == command {
+                return Ok(message);
+            } else if message.command == "error" {
+                return Err(invalid_data(format!(
+                    "engine error: {}",
+                    message.get("message").unwrap_or_default()
+                )));
+            }
+        }
+    }
+}
+
+impl Contestant for HubEngine {
+    fn new_game(&mut self) -> io::Result<()> {
+        self.send(&Message::new("new-game"))
+    }
+
+    fn choose_turn(&mut self, game: &Game) -> io::Result<Turn> {
+        let fen = Fen {
+            board: game.board().clone(),
+            to_move: game.to_move(),
+        };
+        self.send(&Message::new("pos").arg("fen", fen))?;
+        let mut level = Message::new("level");
+        if let Some(depth) = self.limits.depth {
+            level = level.arg("depth", depth);
+        }
+        if let Some(nodes) = self.limits.nodes {
+            level = level.arg("nodes", nodes);
+        }
+        if let Some(time) = self.limits.time {
+            level = level.arg("move-time", time.as_secs_f64());
+        }
+        self.send(&level)?;
+        self.send(&Message::new("go").flag("think"))?;
+
+        let done = self.receive("done")?;
+        let notation = done.get("move").unwrap_or_default();
+        notation
+            .parse::<Turn>()
+            .ok()
+            .and_then(|turn| game.board().find_turn(game.to_move(), &turn))
+            .ok_or_else(|| invalid_data(format!("engine played illegal move \"{}\"", notation)))
+    }
+}
+
+impl Drop for HubEngine {
+    fn drop(&mut self) {
+        // The engine is left to exit by itself if it can't be told to quit
+        if self.send(&Message::new("quit")).is_ok() {
+            let _ = self.child.wait();
+        }
+    }
+}
+
+impl fmt::Debug for HubEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HubEngine")
+            .field("id", &self.child.id())
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+impl fmt::Display for ParseSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for ParseSpecError {}
+
+/// Returns the value of an argument parsed as a number, or None if it is missing.
+fn number<T: FromStr>(message: &Message, name: &str) -> Result<Option<T>, ParseSpecError> {
+    message
+        .get(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| ParseSpecError(format!("invalid value \"{}\" for {}", value, name)))
+        })
+        .transpose()
+}
+
+/// Returns an error for unexpected data from an engine.
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_specs() {
+        let spec: EngineSpec = "builtin name=new time=0.5 threads=2".parse().unwrap();
+        let hub: EngineSpec = r#"hub command=/bin/engine args="--fast -v" nodes=1000"#
+            .parse()
+            .unwrap();
+
+        assert_eq!(spec.name, "new");
+        assert_eq!(spec.limits.time, Some(Duration::from_millis(500)));
+        assert_eq!(spec.threads, Some(2));
+        assert_eq!(
+            hub.kind,
+            EngineKind::Hub {
+                command: PathBuf::from("/bin/engine"),
+                args: vec!["--fast".to_string(), "-v".to_string()],
+            }
+        );
+        assert_eq!(
+            "random seed=7".parse::<EngineSpec>().unwrap().kind,
+            EngineKind::Random { seed: Some(7) }
+        );
+        assert!("builtin".parse::<EngineSpec>().is_err());
+        assert!("builtin seed=7 depth=4".parse::<EngineSpec>().is_err());
+        assert!("hub depth=4".parse::<EngineSpec>().is_err());
+        assert!("builtin depth=four".parse::<EngineSpec>().is_err());
+        assert!("builtin depth=4 ponder=yes".parse::<EngineSpec>().is_err());
+    }
+
+    #[test]
+    fn should_choose_legal_turns_at_random() {
+        let mut game = Game::new();
+        let mut contestant = "random seed=1"
+            .parse::<EngineSpec>()
+            .unwrap()
+            .create()
+            .unwrap();
+
+        for _ in 0..20 {
+            let turn = contestant.choose_turn(&game).unwrap();
+            game.play(turn).unwrap();
+        }
+        assert_eq!(game.turns().len(), 20);
+    }
+
+    #[test]
+    fn should_choose_turns_with_builtin_engine() {
+        let fen: Fen = "B:W18,27:B14".parse().unwrap();
+        let game = Game::from_position(fen.board, fen.to_move);
+        let mut contestant = EngineSpec::builtin("builtin", SearchLimits::depth(2))
+            .create()
+            .unwrap();
+
+        contestant.new_game().unwrap();
+        assert_eq!(
+            contestant.choose_turn(&game).unwrap().to_string(),
+            "14x23x32"
+        );
+    }
+}
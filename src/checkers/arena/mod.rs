@@ -0,0 +1,440 @@
+mod contestant;
+mod stats;
+mod tournament;
+
+pub use contestant::{Contestant, EngineKind, EngineSpec, HubEngine, ParseSpecError};
+pub use stats::{EloEstimate, Score, Sprt, SprtDecision};
+pub use tournament::{
+    run_tournament, ConfigError, Format, Standing, TournamentConfig, TournamentGame,
+    TournamentTable, USAGE as TOURNAMENT_USAGE,
+};
+
+use super::args::{number, UsageError};
+use super::ballot::{Ballot, BALLOT_COUNT};
+use super::board::{Player1, Player2};
+use super::game::{Game, Outcome, DRAW_TURNS};
+use std::convert::TryInto;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Description of the command line arguments accepted by `MatchOptions::from_args`.
+pub const USAGE: &str = "\
+Usage: bit_checkers_match [options] ENGINE ENGINE
+
+Plays a match between two engines from the openings of the three-move ballot. Each opening is
+played twice so that both engines play each side, and results are given for the first engine.
+
+Engines are given as a kind followed by arguments, e.g. \"builtin depth=8\" or
+\"hub command=./old_engine time=0.1\":
+  builtin | hub command=PATH [args=\"ARGS\"] | random [seed=N]
+  name=NAME                    name shown in results
+  depth=N nodes=N time=SECONDS limits for each move, of which at least one is needed except
+                               by random
+  threads=N hash=MB            search threads and transposition table size
+
+Options:
+  --games N           maximum number of games (default 312, every opening twice)
+  --concurrency N     number of games played at once (default the number of CPUs)
+  --sprt ELO0 ELO1    stop once a sequential probability ratio test decides whether the first
+                      engine is ELO0 or ELO1 stronger
+  --alpha P           chance of deciding ELO1 if ELO0 is true (default 0.05)
+  --beta P            chance of deciding ELO0 if ELO1 is true (default 0.05)
+  --max-turns N       number of turns after which a game is drawn (default 300)
+  --help              show this message";
+
+/// Options for a match between two engines.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MatchOptions {
+    pub engines: [EngineSpec; 2],
+    /// Maximum number of games, played in pairs from each opening with the sides swapped.
+    pub games: usize,
+    /// Number of games played at the same time.
+    pub concurrency: usize,
+    /// Test of whether the first engine is stronger, which ends the match once it decides.
+    pub sprt: Option<Sprt>,
+    /// Number of turns after which a game is drawn.
+    pub max_turns: usize,
+}
+
+/// Type representing a finished game of a match.
+#[derive(Clone, Debug)]
+pub struct MatchGame {
+    /// Number of the game in the match, starting from 1.
+    pub number: usize,
+    /// Number of the ballot the game was started from.
+    pub ballot: usize,
+    /// Whether the first engine played Black.
+    pub first_black: bool,
+    /// The game, including the opening. It is drawn if it reached the turn limit.
+    pub game: Game,
+}
+
+/// Result of a match, from the point of view of the first engine.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MatchResult {
+    pub score: Score,
+    /// Hypothesis accepted by the SPRT, or None if the match ended without a decision.
+    pub decision: Option<SprtDecision>,
+}
+
+impl MatchOptions {
+    /// Returns options parsed from command line arguments, not including the program name. See
+    /// `USAGE` for the arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::arena::MatchOptions;
+    /// let args = ["builtin name=new depth=6", "builtin name=old depth=4", "--sprt", "0", "10"];
+    /// let options = MatchOptions::from_args(args.iter().map(|arg| arg.to_string())).unwrap();
+    ///
+    /// assert_eq!(options.engines[1].name, "old");
+    /// assert_eq!(options.sprt.unwrap().elo1, 10.0);
+    /// ```
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<MatchOptions, UsageError> {
+        let mut engines = Vec::new();
+        let concurrency = thread::available_parallelism().map_or(1, |threads| threads.get());
+        let (mut games, mut concurrency, mut max_turns) =
+            (2 * BALLOT_COUNT, concurrency, DRAW_TURNS);
+        let (mut elos, mut alpha, mut beta) = (None, 0.05, 0.05);
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| UsageError(format!("{} needs a value", arg)))
+            };
+            match arg.as_str() {
+                "--games" => games = number(&arg, &value()?)?,
+                "--concurrency" => concurrency = number(&arg, &value()?)?,
+                "--sprt" => elos = Some((number(&arg, &value()?)?, number(&arg, &value()?)?)),
+                "--alpha" => alpha = probability(&arg, &value()?)?,
+                "--beta" => beta = probability(&arg, &value()?)?,
+                "--max-turns" => max_turns = number(&arg, &value()?)?,
+                _ if arg.starts_with("--") => {
+                    return Err(UsageError(format!("unknown option \"{}\"", arg)))
+                }
+                _ => engines.push(
+                    arg.parse::<EngineSpec>()
+                        .map_err(|error| UsageError(error.to_string()))?,
+                ),
+            }
+        }
+        let engines: [EngineSpec; 2] = engines
+            .try_into()
+            .map_err(|_| UsageError("expected two engines".to_string()))?;
+        if games == 0 || concurrency == 0 {
+            return Err(UsageError(
+                "--games and --concurrency should be positive".to_string(),
+            ));
+        }
+        Ok(MatchOptions {
+            engines,
+            games,
+            concurrency,
+            sprt: elos.map(|(elo0, elo1)| Sprt {
+                elo0,
+                elo1,
+                alpha,
+                beta,
+            }),
+            max_turns,
+        })
+    }
+}
+
+impl MatchGame {
+    /// Returns how the game ended.
+    pub fn outcome(&self) -> Outcome {
+        self.game
+            .outcome()
+            .expect("played games should be finished")
+    }
+
+    /// Returns the result of the game as a PDN result token.
+    fn result(&self) -> &'static str {
+        match self.outcome() {
+            Outcome::Win(Player1) => "1-0",
+            Outcome::Win(Player2) => "0-1",
+            Outcome::Draw => "1/2-1/2",
+        }
+    }
+}
+
+/// Plays a match between two engines, writing a line to output as each game finishes followed by
+/// a summary of the result.
+///
+/// Games are played on `concurrency` threads, each with its own copy of both engines. If the
+/// match is stopped by the SPRT, games still being played are not counted.
+pub fn run<W: Write>(options: &MatchOptions, mut output: W) -> io::Result<MatchResult> {
+    let [first, second] = &options.engines;
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+    let mut score = Score::default();
+    let mut decision = None;
+    thread::scope(|scope| {
+        for _ in 0..options.concurrency.min(options.games) {
+            let sender = sender.clone();
+            let (next, stop) = (&next, &stop);
+            scope.spawn(move || {
+                let played = play_games(options, next, stop, |game| sender.send(Ok(game)).is_ok());
+                if let Err(error) = played {
+                    stop.store(true, Ordering::Relaxed);
+                    let _ = sender.send(Err(error));
+                }
+            });
+        }
+        drop(sender);
+
+        for game in receiver {
+            let game = game.inspect_err(|_| stop.store(true, Ordering::Relaxed))?;
+            let player = if game.first_black { Player1 } else { Player2 };
+            score.record(game.outcome(), player);
+            let (black, white) = match game.first_black {
+                true => (first, second),
+                false => (second, first),
+            };
+            write!(
+                output,
+                "Game {}/{} (ballot {}): {} vs {} {}  Score {}",
+                game.number,
+                options.games,
+                game.ballot,
+                black.name,
+                white.name,
+                game.result(),
+                score
+            )?;
+            if let Some(estimate) = score.elo() {
+                write!(output, "  Elo {}", estimate)?;
+            }
+            if let Some(sprt) = options.sprt {
+                write!(output, "  LLR {:.2}", sprt.llr(&score))?;
+                decision = sprt.decision(&score);
+            }
+            writeln!(output)?;
+            if decision.is_some() {
+                stop.store(true, Ordering::Relaxed);
+                break;
+            }
+        }
+        Ok::<(), io::Error>(())
+    })?;
+
+    writeln!(
+        output,
+        "\nScore of {} vs {}: {} ({:.3})",
+        first.name,
+        second.name,
+        score,
+        score.ratio().unwrap_or(0.5)
+    )?;
+    if let Some(estimate) = score.elo() {
+        writeln!(output, "Elo difference: {}", estimate)?;
+    }
+    if let Some(sprt) = options.sprt {
+        let (lower, upper) = sprt.bounds();
+        write!(
+            output,
+            "SPRT ({} to {} Elo): LLR {:.2} ({:.2}, {:.2})",
+            sprt.elo0,
+            sprt.elo1,
+            sprt.llr(&score),
+            lower,
+            upper
+        )?;
+        match decision {
+            Some(decision) => writeln!(output, ", {}", decision)?,
+            None => writeln!(output, ", no decision")?,
+        }
+    }
+    Ok(MatchResult { score, decision })
+}
+
+/// Plays a game from a ballot's opening until one side wins or the turn limit is reached,
+/// returning the game including the opening.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::arena::{self, EngineSpec};
+/// use bit_checkers::ballot::Ballot;
+/// use bit_checkers::engine::SearchLimits;
+/// let spec = EngineSpec::builtin("builtin", SearchLimits::depth(2));
+/// let (mut black, mut white) = (spec.create().unwrap(), spec.create().unwrap());
+/// let ballot = Ballot::get(1).unwrap();
+/// let game = arena::play_game(black.as_mut(), white.as_mut(), &ballot, 20).unwrap();
+///
+/// assert_eq!(game.turns()[..3], ballot.turns);
+/// assert!(game.turns().len() <= 20);
+/// ```
+pub fn play_game(
+    black: &mut dyn Contestant,
+    white: &mut dyn Contestant,
+    ballot: &Ballot,
+    max_turns: usize,
+) -> io::Result<Game> {
+    let mut game = Game::new();
+    for &turn in &ballot.turns {
+        game.play(turn).expect("ballot should be a legal opening");
+    }
+    let mut game = game.with_turn_limit(max_turns);
+    black.new_game()?;
+    white.new_game()?;
+    while game.outcome().is_none() {
+        let turn = match game.to_move() {
+            Player1 => black.choose_turn(&game)?,
+            Player2 => white.choose_turn(&game)?,
+        };
+        game.play(turn)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    }
+    Ok(game)
+}
+
+/// Plays the games of a match with the next unplayed numbers until every game has been started
+/// or the match is stopped, reporting each game as it finishes.
+///
+/// The report returns false if no more games should be played.
+fn play_games<F>(
+    options: &MatchOptions,
+    next: &AtomicUsize,
+    stop: &AtomicBool,
+    mut report: F,
+) -> io::Result<()>
+where
+    F: FnMut(MatchGame) -> bool,
+{
+    let mut first = options.engines[0].create()?;
+    let mut second = options.engines[1].create()?;
+    while !stop.load(Ordering::Relaxed) {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        if index >= options.games {
+            break;
+        }
+        // Each opening is played twice, with the first engine playing Black in the first game
+        let ballot = Ballot::get(index / 2 % BALLOT_COUNT + 1).expect("ballot should exist");
+        let first_black = index.is_multiple_of(2);
+        let game = match first_black {
+            true => play_game(first.as_mut(), second.as_mut(), &ballot, options.max_turns)?,
+            false => play_game(second.as_mut(), first.as_mut(), &ballot, options.max_turns)?,
+        };
+        let game = MatchGame {
+            number: index + 1,
+            ballot: ballot.number,
+            first_black,
+            game,
+        };
+        if !report(game) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the value of an option parsed as a probability strictly between 0 and 1.
+fn probability(option: &str, value: &str) -> Result<f64, UsageError> {
+    match number(option, value)? {
+        probability if probability > 0.0 && probability < 1.0 => Ok(probability),
+        _ => Err(UsageError(format!(
+            "{} should be between 0 and 1 but found {}",
+            option, value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::engine::SearchLimits;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn should_parse_args() {
+        let options = MatchOptions::from_args(args(&[
+            "builtin depth=4",
+            "--games",
+            "10",
+            "hub command=engine nodes=1000",
+            "--alpha",
+            "0.1",
+            "--sprt",
+            "-5",
+            "5",
+        ]))
+        .unwrap();
+
+        assert_eq!(options.engines[0].name, "builtin");
+        assert_eq!(options.engines[1].name, "engine");
+        assert_eq!(options.games, 10);
+        assert_eq!(options.max_turns, DRAW_TURNS);
+        assert_eq!(
+            options.sprt,
+            Some(Sprt {
+                elo0: -5.0,
+                elo1: 5.0,
+                alpha: 0.1,
+                beta: 0.05
+            })
+        );
+        assert!(MatchOptions::from_args(args(&["builtin depth=4"])).is_err());
+        assert!(MatchOptions::from_args(args(&[
+            "builtin depth=4",
+            "builtin depth=2",
+            "--beta",
+            "1"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn should_play_match_in_parallel() {
+        let options = MatchOptions {
+            engines: [
+                EngineSpec::builtin("deep", SearchLimits::depth(3)),
+                EngineSpec::builtin("shallow", SearchLimits::depth(1)),
+            ],
+            games: 4,
+            concurrency: 2,
+            sprt: None,
+            max_turns: 40,
+        };
+        let mut output = Vec::new();
+        let result = run(&options, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(result.score.games(), 4);
+        assert_eq!(result.decision, None);
+        assert!(output.contains("(ballot 2): shallow vs deep"));
+        assert!(output.contains("Score of deep vs shallow"));
+    }
+
+    #[test]
+    fn should_stop_match_when_sprt_decides() {
+        let options = MatchOptions {
+            engines: [
+                EngineSpec::builtin("deep", SearchLimits::depth(4)),
+                EngineSpec::builtin("shallow", SearchLimits::depth(1)),
+            ],
+            games: 200,
+            concurrency: 4,
+            // Wide error chances decide after only a few games
+            sprt: Some(Sprt {
+                elo0: 0.0,
+                elo1: 400.0,
+                alpha: 0.4,
+                beta: 0.4,
+            }),
+            max_turns: 60,
+        };
+        let result = run(&options, io::sink()).unwrap();
+
+        assert!(result.decision.is_some());
+        assert!(result.score.games() < 200);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::checkers::board::Player;
+use crate::checkers::game::Outcome;
+use std::fmt;
+
+/// Multiple of the standard error giving a 95% confidence interval.
+const CONFIDENCE_95: f64 = 1.959_964;
+
+/// Counts of game results from the point of view of one engine.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Score {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+/// Estimate of an Elo difference, with the half-width of its 95% confidence interval.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct EloEstimate {
+    pub elo: f64,
+    pub error: f64,
+}
+
+/// Settings for a sequential probability ratio test (SPRT) of whether an engine is stronger than
+/// its opponent.
+///
+/// The test decides between the hypothesis H0 that the Elo difference is `elo0` and H1 that it is
+/// `elo1`, where `alpha` is the chance of accepting H1 when H0 is true and `beta` the chance of
+/// accepting H0 when H1 is true. A match can stop as soon as the test makes a decision.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+/// Hypothesis accepted by an SPRT.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SprtDecision {
+    /// The Elo difference is more likely to be `elo0` than `elo1`.
+    AcceptH0,
+    /// The Elo difference is more likely to be `elo1` than `elo0`.
+    AcceptH1,
+}
+
+impl Score {
+    /// Returns the number of games played.
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Adds the outcome of a game played by the given player.
+    pub fn record(&mut self, outcome: Outcome, player: Player) {
+        match outcome {
+            Outcome::Win(winner) if winner == player => self.wins += 1,
+            Outcome::Win(_) => self.losses += 1,
+            Outcome::Draw => self.draws += 1,
+        }
+    }
+
+    /// Returns the score from the opponent's point of view.
+    pub fn reversed(&self) -> Score {
+        Score {
+            wins: self.losses,
+            draws: self.draws,
+            losses: self.wins,
+        }
+    }
+
+    /// Returns the points scored per game, where a win is worth 1 and a draw 1/2, or None if no
+    /// games have been played.
+    pub fn ratio(&self) -> Option<f64> {
+        match self.games() {
+            0 => None,
+            games => Some((f64::from(self.wins) + f64::from(self.draws) / 2.0) / f64::from(games)),
+        }
+    }
+
+    /// Returns the estimated Elo difference from the opponent, or None if no games have been
+    /// played.
+    ///
+    /// The estimate is infinite if every game was won or lost, and its error is infinite if the
+    /// confidence interval includes winning or losing every game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::arena::Score;
+    /// let score = Score { wins: 30, draws: 50, losses: 20 };
+    /// let estimate = score.elo().unwrap();
+    ///
+    /// assert_eq!(estimate.elo.round(), 35.0);
+    /// assert!(estimate.error > 40.0 && estimate.error < 50.0);
+    /// ```
+    pub fn elo(&self) -> Option<EloEstimate> {
+        let ratio = self.ratio()?;
+        let (_, variance) = self.moments(0.0);
+        let margin = CONFIDENCE_95 * (variance / f64::from(self.games())).sqrt();
+        let error = (ratio_to_elo(ratio + margin) - ratio_to_elo(ratio - margin)) / 2.0;
+        Some(EloEstimate {
+            elo: ratio_to_elo(ratio),
+            error,
+        })
+    }
+
+    /// Returns the mean and variance of the points scored in a game, with the given number of
+    /// games of each result added to the counts.
+    fn moments(&self, extra: f64) -> (f64, f64) {
+        let counts = [
+            (1.0, f64::from(self.wins) + extra),
+            (0.5, f64::from(self.draws) + extra),
+            (0.0, f64::from(self.losses) + extra),
+        ];
+        let games: f64 = counts.iter().map(|(_, count)| count).sum();
+        if games == 0.0 {
+            return (0.5, 0.0);
+        }
+        let mean = counts
+            .iter()
+            .map(|(points, count)| points * count)
+            .sum::<f64>()
+            / games;
+        let variance = counts
+            .iter()
+            .map(|(points, count)| count * (points - mean).powi(2))
+            .sum::<f64>()
+            / games;
+        (mean, variance)
+    }
+}
+
+impl Sprt {
+    /// Returns a test with the usual 5% error chances.
+    pub fn new(elo0: f64, elo1: f64) -> Sprt {
+        Sprt {
+            elo0,
+            elo1,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+
+    /// Returns the log-likelihood ratio of H1 to H0 for a score, using a normal approximation of
+    /// the distribution of results.
+    ///
+    /// Half a game of each result is added to the score, so that the ratio can still be found
+    /// when only one kind of result has occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::arena::{Score, Sprt};
+    /// let sprt = Sprt::new(0.0, 10.0);
+    ///
+    /// assert!(sprt.llr(&Score { wins: 60, draws: 100, losses: 40 }) > 0.0);
+    /// assert!(sprt.llr(&Score { wins: 40, draws: 100, losses: 60 }) < 0.0);
+    /// assert!(sprt.llr(&Score { wins: 10, draws: 0, losses: 0 }) > 0.0);
+    /// ```
+    pub fn llr(&self, score: &Score) -> f64 {
+        if score.games() == 0 {
+            return 0.0;
+        }
+        let (ratio, variance) = score.moments(0.5);
+        let (ratio0, ratio1) = (elo_to_ratio(self.elo0), elo_to_ratio(self.elo1));
+        f64::from(score.games()) * (ratio1 - ratio0) * (2.0 * ratio - ratio0 - ratio1)
+            / (2.0 * variance)
+    }
+
+    /// Returns the lower and upper bounds of the log-likelihood ratio, at which H0 or H1 are
+    /// accepted.
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// Returns the hypothesis accepted for a score, or None if more games are needed.
+    pub fn decision(&self, score: &Score) -> Option<SprtDecision> {
+        let llr = self.llr(score);
+        let (lower, upper) = self.bounds();
+        if llr <= lower {
+            Some(SprtDecision::AcceptH0)
+        } else if llr >= upper {
+            Some(SprtDecision::AcceptH1)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{} ={} -{}", self.wins, self.draws, self.losses)
+    }
+}
+
+impl fmt::Display for EloEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+.1} +/- {:.1}", self.elo, self.error)
+    }
+}
+
+impl fmt::Display for SprtDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SprtDecision::AcceptH0 => write!(f, "H0 accepted"),
+            SprtDecision::AcceptH1 => write!(f, "H1 accepted"),
+        }
+    }
+}
+
+/// Returns the Elo difference at which a player is expected to score the given points per game.
+fn ratio_to_elo(ratio: f64) -> f64 {
+    if ratio <= 0.0 {
+        f64::NEG_INFINITY
+    } else if ratio >= 1.0 {
+        f64::INFINITY
+    } else {
+        400.0 * (ratio / (1.0 - ratio)).log10()
+    }
+}
+
+/// Returns the points per game expected for a player with the given Elo difference.
+fn elo_to_ratio(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_convert_between_elo_and_ratio() {
+        assert_eq!(ratio_to_elo(0.5), 0.0);
+        assert!((ratio_to_elo(elo_to_ratio(100.0)) - 100.0).abs() < 1e-9);
+        assert!((elo_to_ratio(-400.0) - 1.0 / 11.0).abs() < 1e-9);
+        assert_eq!(ratio_to_elo(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn should_estimate_elo() {
+        let even = Score {
+            wins: 10,
+            draws: 0,
+            losses: 10,
+        };
+        let more_games = Score {
+            wins: 100,
+            draws: 0,
+            losses: 100,
+        };
+
+        assert_eq!(Score::default().elo(), None);
+        assert_eq!(even.elo().unwrap().elo, 0.0);
+        assert!(more_games.elo().unwrap().error < even.elo().unwrap().error);
+        assert_eq!(even.reversed(), even);
+        assert_eq!(
+            Score {
+                wins: 3,
+                draws: 0,
+                losses: 0
+            }
+            .elo()
+            .unwrap()
+            .elo,
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn should_decide_sprt() {
+        let sprt = Sprt::new(0.0, 10.0);
+        let (lower, upper) = sprt.bounds();
+
+        assert!((upper - 2.944).abs() < 1e-3 && (lower + 2.944).abs() < 1e-3);
+        assert_eq!(sprt.decision(&Score::default()), None);
+        assert_eq!(
+            sprt.decision(&Score {
+                wins: 600,
+                draws: 1000,
+                losses: 400
+            }),
+            Some(SprtDecision::AcceptH1)
+        );
+        assert_eq!(
+            sprt.decision(&Score {
+                wins: 400,
+                draws: 1000,
+                losses: 600
+            }),
+            Some(SprtDecision::AcceptH0)
+        );
+        assert_eq!(
+            sprt.decision(&Score {
+                wins: 5,
+                draws: 10,
+                losses: 4
+            }),
+            None
+        );
+    }
+}
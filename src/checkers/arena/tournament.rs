@@ -0,0 +1,892 @@
+use super::{play_game, Contestant, EngineSpec, Score};
+use crate::checkers::ballot::{Ballot, BALLOT_COUNT};
+use crate::checkers::board::{Player1, Player2};
+use crate::checkers::game::{Game, Outcome, DRAW_TURNS};
+use crate::checkers::hub::Message;
+use crate::checkers::pdn::PdnGame;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+/// Number of opponents tried when pairing a Swiss round before falling back to pairing players
+/// in order of rank, as there may be no pairings without rematches.
+const MAX_PAIRING_STEPS: usize = 100_000;
+
+/// Description of the command line arguments accepted by the tournament binary.
+pub const USAGE: &str = "\
+Usage: bit_checkers_tournament CONFIG
+
+Plays a tournament between the players listed in the config file, writing the result of each
+game as it finishes followed by the standings and crosstable.
+
+Each line of the config file is one of the following, and lines starting with # are ignored:
+  event name=NAME          name of the event in PDN output
+  round-robin [cycles=N]   every player plays every other player N times (default 1)
+  swiss rounds=N           players with similar scores are paired for N rounds, fewer than the
+                           number of players
+  settings [games=N] [max-turns=N] [concurrency=N] [pdn=FILE]
+                           games each pairing plays from consecutive openings with the sides
+                           swapped (default 2), turns after which a game is drawn (default 300),
+                           games played at once (default the number of CPUs) and a file to write
+                           the games to as PDN
+  player ENGINE            a player, given as for bit_checkers_match, e.g. player builtin
+                           name=deep depth=8 or player random";
+
+/// Type representing how players are paired in a tournament.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    /// Every player plays every other player once in each cycle, with the sides swapped between
+    /// cycles.
+    RoundRobin { cycles: usize },
+    /// Players are paired each round with an opponent with a similar score that they haven't
+    /// played yet, and a player left over gets a bye worth a win for each game of a pairing.
+    Swiss { rounds: usize },
+}
+
+/// Settings and players of a tournament, usually read from a config file described by `USAGE`.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::arena::{Format, TournamentConfig};
+/// let config: TournamentConfig = "
+///     swiss rounds=2
+///     settings games=4
+///     player builtin name=deep depth=6
+///     player builtin name=shallow depth=2
+///     player random
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// assert_eq!(config.format, Format::Swiss { rounds: 2 });
+/// assert_eq!(config.games, 4);
+/// assert_eq!(config.players[2].name, "random");
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct TournamentConfig {
+    /// Name of the event in PDN output.
+    pub event: String,
+    pub format: Format,
+    pub players: Vec<EngineSpec>,
+    /// Number of games each pairing plays.
+    pub games: usize,
+    /// Number of turns after which a game is drawn.
+    pub max_turns: usize,
+    /// Number of games played at the same time.
+    pub concurrency: usize,
+    /// File the games are written to as PDN as they finish.
+    pub pdn: Option<PathBuf>,
+}
+
+/// Error returned when a tournament config is invalid.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConfigError(String);
+
+/// Results of the games of a tournament so far.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TournamentTable {
+    names: Vec<String>,
+    /// Score of each player against each other player.
+    scores: Vec<Vec<Score>>,
+    /// Number of byes each player has had.
+    byes: Vec<usize>,
+    /// Points each player was given for byes.
+    bye_points: Vec<f64>,
+}
+
+/// A player's place in the standings of a tournament.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Standing {
+    /// Index of the player in the config.
+    pub player: usize,
+    pub name: String,
+    /// Points scored, including byes, where a win is worth 1 and a draw 1/2.
+    pub points: f64,
+    /// Results of the games played.
+    pub score: Score,
+    /// Sonneborn-Berger score, the sum of the points of each opponent weighted by the points
+    /// scored against them, used to break ties.
+    pub sonneborn_berger: f64,
+}
+
+/// Type representing a finished game of a tournament.
+#[derive(Clone, Debug)]
+pub struct TournamentGame {
+    /// Number of the round, starting from 1.
+    pub round: usize,
+    /// Number of the game in the round, starting from 1.
+    pub number: usize,
+    /// Index of the player playing Black.
+    pub black: usize,
+    /// Index of the player playing White.
+    pub white: usize,
+    /// Number of the ballot the game was started from.
+    pub ballot: usize,
+    /// The game, including the opening. It is drawn if it reached the turn limit.
+    pub game: Game,
+}
+
+/// A game waiting to be played by one of the threads of a tournament.
+struct Job {
+    round: usize,
+    number: usize,
+    black: usize,
+    white: usize,
+    ballot: usize,
+}
+
+impl FromStr for TournamentConfig {
+    type Err = ConfigError;
+
+    /// Parses a config file, described by `USAGE`.
+    fn from_str(s: &str) -> Result<TournamentConfig, ConfigError> {
+        let mut config = TournamentConfig {
+            event: "bit_checkers tournament".to_string(),
+            format: Format::RoundRobin { cycles: 1 },
+            players: Vec::new(),
+            games: 2,
+            max_turns: DRAW_TURNS,
+            concurrency: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            pdn: None,
+        };
+        let mut format = None;
+        for (line_number, line) in (1..).zip(s.lines()) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| line_error(&message, line_number);
+            if let Some(spec) = line.strip_prefix("player ") {
+                let spec = spec
+                    .parse()
+                    .map_err(|error| line_error(&error, line_number))?;
+                config.players.push(spec);
+                continue;
+            }
+
+            let message: Message = line
+                .parse()
+                .map_err(|error| line_error(&error, line_number))?;
+            let number = |name: &str, default: Option<usize>| match message
+                .get(name)
+                .map(str::parse::<usize>)
+            {
+                Some(Ok(value)) if value > 0 => Ok(value),
+                None => default.ok_or_else(|| error(format!("{} needs {}", line, name))),
+                _ => Err(error(format!("{} should be a positive number", name))),
+            };
+            let new_format = match message.command.as_str() {
+                "event" => {
+                    config.event = message
+                        .get("name")
+                        .ok_or_else(|| error("event needs a name".to_string()))?
+                        .to_string();
+                    None
+                }
+                "round-robin" => Some(Format::RoundRobin {
+                    cycles: number("cycles", Some(1))?,
+                }),
+                "swiss" => Some(Format::Swiss {
+                    rounds: number("rounds", None)?,
+                }),
+                "settings" => {
+                    config.games = number("games", Some(config.games))?;
+                    config.max_turns = number("max-turns", Some(config.max_turns))?;
+                    config.concurrency = number("concurrency", Some(config.concurrency))?;
+                    if let Some(path) = message.get("pdn") {
+                        config.pdn = Some(PathBuf::from(path));
+                    }
+                    None
+                }
+                command => return Err(error(format!("unknown setting \"{}\"", command))),
+            };
+            if let Some(new_format) = new_format {
+                if format.replace((new_format, line_number)).is_some() {
+                    return Err(error("the format is given more than once".to_string()));
+                }
+            }
+        }
+
+        if config.players.len() < 2 {
+            return Err(ConfigError(
+                "a tournament needs at least two players".to_string(),
+            ));
+        }
+        if let Some((Format::Swiss { rounds }, line_number)) = format {
+            // Later rounds would have to pair players who have already met
+            if rounds >= config.players.len() {
+                return Err(line_error(
+                    &"swiss needs fewer rounds than players",
+                    line_number,
+                ));
+            }
+        }
+        config.format = format.map_or(config.format, |(format, _)| format);
+        Ok(config)
+    }
+}
+
+impl TournamentConfig {
+    /// Returns the number of rounds of the tournament.
+    pub fn rounds(&self) -> usize {
+        match self.format {
+            // A round robin with an odd number of players has a round with a bye for each player
+            Format::RoundRobin { cycles } => {
+                cycles * (self.players.len() - 1 + self.players.len() % 2)
+            }
+            Format::Swiss { rounds } => rounds,
+        }
+    }
+}
+
+impl TournamentTable {
+    /// Returns a table for players with the given names, with no games played.
+    pub fn new(names: Vec<String>) -> TournamentTable {
+        let count = names.len();
+        TournamentTable {
+            names,
+            scores: vec![vec![Score::default(); count]; count],
+            byes: vec![0; count],
+            bye_points: vec![0.0; count],
+        }
+    }
+
+    /// Adds the outcome of a game between two players.
+    pub fn record(&mut self, black: usize, white: usize, outcome: Outcome) {
+        self.scores[black][white].record(outcome, Player1);
+        self.scores[white][black].record(outcome, Player2);
+    }
+
+    /// Gives a player a bye worth the given number of points.
+    pub fn bye(&mut self, player: usize, points: f64) {
+        self.byes[player] += 1;
+        self.bye_points[player] += points;
+    }
+
+    /// Returns the score of a player against an opponent.
+    pub fn score(&self, player: usize, opponent: usize) -> Score {
+        self.scores[player][opponent]
+    }
+
+    /// Returns the points scored by a player, including byes.
+    pub fn points(&self, player: usize) -> f64 {
+        let games: f64 = self.scores[player].iter().filter_map(points).sum();
+        games + self.bye_points[player]
+    }
+
+    /// Returns the standings, ordered by points and then by Sonneborn-Berger score. Players who
+    /// are still tied keep the order they were given in.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = (0..self.names.len())
+            .map(|player| {
+                let scores = &self.scores[player];
+                let sonneborn_berger = (0..self.names.len())
+                    .filter_map(|opponent| {
+                        points(&scores[opponent]).map(|points| points * self.points(opponent))
+                    })
+                    .sum();
+                Standing {
+                    player,
+                    name: self.names[player].clone(),
+                    points: self.points(player),
+                    score: scores.iter().fold(Score::default(), |total, score| Score {
+                        wins: total.wins + score.wins,
+                        draws: total.draws + score.draws,
+                        losses: total.losses + score.losses,
+                    }),
+                    sonneborn_berger,
+                }
+            })
+            .collect();
+        standings.sort_by(|a, b| {
+            b.points
+                .partial_cmp(&a.points)
+                .unwrap_or(Ordering::Equal)
+                .then(
+                    b.sonneborn_berger
+                        .partial_cmp(&a.sonneborn_berger)
+                        .unwrap_or(Ordering::Equal),
+                )
+                .then(a.player.cmp(&b.player))
+        });
+        standings
+    }
+
+    /// Returns whether two players have played each other.
+    fn played(&self, player: usize, opponent: usize) -> bool {
+        self.scores[player][opponent].games() > 0
+    }
+
+    /// Returns the players ordered by points, keeping the given order for players with the same
+    /// points, as used for pairing a Swiss round.
+    fn ranking(&self) -> Vec<usize> {
+        let mut players: Vec<usize> = (0..self.names.len()).collect();
+        players.sort_by(|&a, &b| {
+            self.points(b)
+                .partial_cmp(&self.points(a))
+                .unwrap_or(Ordering::Equal)
+        });
+        players
+    }
+}
+
+impl fmt::Display for TournamentTable {
+    /// Formats the standings followed by the crosstable, which gives the points scored by the
+    /// player in each row against the player in each column.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let standings = self.standings();
+        let width = self
+            .names
+            .iter()
+            .map(|name| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+        writeln!(
+            f,
+            "{:>3}  {:<width$}  {:>6}  {:>5}  {:>4}  {:>4}  {:>4}  {:>6}",
+            "#",
+            "Name",
+            "Points",
+            "Games",
+            "+",
+            "=",
+            "-",
+            "SB",
+            width = width
+        )?;
+        for (place, standing) in (1..).zip(&standings) {
+            writeln!(
+                f,
+                "{:>3}  {:<width$}  {:>6.1}  {:>5}  {:>4}  {:>4}  {:>4}  {:>6.2}",
+                place,
+                standing.name,
+                standing.points,
+                standing.score.games(),
+                standing.score.wins,
+                standing.score.draws,
+                standing.score.losses,
+                standing.sonneborn_berger,
+                width = width
+            )?;
+        }
+
+        writeln!(f)?;
+        write!(f, "{:>3}  {:<width$}", "", "", width = width)?;
+        for place in 1..=standings.len() {
+            write!(f, "  {:>4}", place)?;
+        }
+        writeln!(f)?;
+        for (place, row) in (1..).zip(&standings) {
+            write!(f, "{:>3}  {:<width$}", place, row.name, width = width)?;
+            for column in &standings {
+                let score = self.score(row.player, column.player);
+                match points(&score) {
+                    _ if row.player == column.player => write!(f, "  {:>4}", "x")?,
+                    Some(points) => write!(f, "  {:>4.1}", points)?,
+                    None => write!(f, "  {:>4}", ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl TournamentGame {
+    /// Returns how the game ended.
+    pub fn outcome(&self) -> Outcome {
+        self.game
+            .outcome()
+            .expect("played games should be finished")
+    }
+
+    /// Returns a record of the game, with tags for the event, round, players and result.
+    pub fn pdn(&self, config: &TournamentConfig) -> PdnGame {
+        let result = match self.outcome() {
+            Outcome::Win(Player1) => "1-0",
+            Outcome::Win(Player2) => "0-1",
+            Outcome::Draw => "1/2-1/2",
+        };
+        let mut pdn = PdnGame::from_turns(self.game.turns().iter().copied());
+        let tags = [
+            ("Event", config.event.clone()),
+            ("Round", format!("{}.{}", self.round, self.number)),
+            ("Black", config.players[self.black].name.clone()),
+            ("White", config.players[self.white].name.clone()),
+            ("Result", result.to_string()),
+        ];
+        pdn.tags = tags
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        pdn.result = Some(result.to_string());
+        pdn
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Plays a tournament, writing a line to output as each game finishes followed by the final
+/// standings and crosstable, and returns the table of results.
+///
+/// The games of each round are played on `concurrency` threads, each of which starts a copy of a
+/// player when it first plays a game for them. Each pairing plays its games from consecutive
+/// ballots, with every pairing in a round using the same ballots.
+pub fn run_tournament<W: Write>(
+    config: &TournamentConfig,
+    mut output: W,
+) -> io::Result<TournamentTable> {
+    let names = config
+        .players
+        .iter()
+        .map(|player| player.name.clone())
+        .collect();
+    let mut table = TournamentTable::new(names);
+    let mut pdn = config.pdn.as_ref().map(File::create).transpose()?;
+    let stop = AtomicBool::new(false);
+    let (job_sender, job_receiver) = mpsc::channel();
+    let job_receiver = Mutex::new(job_receiver);
+    let (game_sender, game_receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..config.concurrency {
+            let (jobs, games, stop) = (&job_receiver, game_sender.clone(), &stop);
+            scope.spawn(move || play_jobs(config, jobs, games, stop));
+        }
+        drop(game_sender);
+
+        let played = (|| {
+            for round in 1..=config.rounds() {
+                let (pairings, bye) = pairings(config, &table, round);
+                if let Some(player) = bye {
+                    table.bye(player, config.games as f64);
+                    writeln!(
+                        output,
+                        "Round {}: {} has a bye",
+                        round, config.players[player].name
+                    )?;
+                }
+                let jobs = jobs(config, round, &pairings);
+                let count = jobs.len();
+                for job in jobs {
+                    job_sender
+                        .send(job)
+                        .expect("threads should wait for jobs until the tournament ends");
+                }
+                for _ in 0..count {
+                    let game = game_receiver
+                        .recv()
+                        .expect("threads should report every game")?;
+                    table.record(game.black, game.white, game.outcome());
+                    writeln!(
+                        output,
+                        "Round {} game {} (ballot {}): {} vs {} {}",
+                        game.round,
+                        game.number,
+                        game.ballot,
+                        config.players[game.black].name,
+                        config.players[game.white].name,
+                        game.pdn(config).result.unwrap_or_default()
+                    )?;
+                    if let Some(file) = pdn.as_mut() {
+                        game.pdn(config).write(&mut *file)?;
+                        file.flush()?;
+                    }
+                }
+            }
+            Ok::<(), io::Error>(())
+        })();
+        // Threads stop once the jobs they are playing finish
+        stop.store(true, atomic::Ordering::Relaxed);
+        drop(job_sender);
+        played
+    })?;
+
+    writeln!(output, "\n{}", table)?;
+    Ok(table)
+}
+
+/// Returns the pairings for a round as the higher ranked player followed by their opponent,
+/// along with the player given a bye if any.
+fn pairings(
+    config: &TournamentConfig,
+    table: &TournamentTable,
+    round: usize,
+) -> (Vec<(usize, usize)>, Option<usize>) {
+    match config.format {
+        Format::RoundRobin { .. } => {
+            let players = config.players.len();
+            let cycle_rounds = players - 1 + players % 2;
+            let swapped = (round - 1) / cycle_rounds % 2 == 1;
+            // A player paired with the extra seat of an odd round robin sits out without points
+            let pairings = round_robin_round(players, (round - 1) % cycle_rounds)
+                .into_iter()
+                .filter(|&(first, second)| first < players && second < players)
+                .map(|(first, second)| match swapped {
+                    true => (second, first),
+                    false => (first, second),
+                })
+                .collect();
+            (pairings, None)
+        }
+        Format::Swiss { .. } => {
+            let mut ranking = table.ranking();
+            let bye = match ranking.len() % 2 {
+                0 => None,
+                // The lowest ranked player with the fewest byes sits out
+                _ => {
+                    let (index, _) = ranking
+                        .iter()
+                        .enumerate()
+                        .rev()
+                        .min_by_key(|&(_, &player)| table.byes[player])
+                        .expect("tournament should have players");
+                    Some(ranking.remove(index))
+                }
+            };
+            let pairings = swiss_pairings(table, &ranking, &mut 0)
+                .unwrap_or_else(|| ranking.chunks(2).map(|pair| (pair[0], pair[1])).collect());
+            (pairings, bye)
+        }
+    }
+}
+
+/// Returns the pairings of a round of a round robin by the circle method, with an extra seat of
+/// index `players` if the number of players is odd.
+fn round_robin_round(players: usize, round: usize) -> Vec<(usize, usize)> {
+    let seats = players + players % 2;
+    // The player in seat 0 stays in place while the others rotate around the circle
+    let player = |seat: usize| match seat {
+        0 => 0,
+        seat => (seat - 1 + round) % (seats - 1) + 1,
+    };
+    (0..seats / 2)
+        .map(|seat| (player(seat), player(seats - 1 - seat)))
+        .collect()
+}
+
+/// Returns pairings of the ranked players where nobody meets an opponent they have already
+/// played, pairing each player with the highest ranked opponent possible, or None if there are
+/// no such pairings. Gives up once `MAX_PAIRING_STEPS` opponents have been tried, counted in
+/// `steps`.
+fn swiss_pairings(
+    table: &TournamentTable,
+    ranking: &[usize],
+    steps: &mut usize,
+) -> Option<Vec<(usize, usize)>> {
+    let (&first, rest) = match ranking.split_first() {
+        Some(split) => split,
+        None => return Some(Vec::new()),
+    };
+    for (index, &opponent) in rest.iter().enumerate() {
+        if *steps == MAX_PAIRING_STEPS {
+            return None;
+        }
+        *steps += 1;
+        if table.played(first, opponent) {
+            continue;
+        }
+        let mut remaining = rest.to_vec();
+        remaining.remove(index);
+        if let Some(mut pairings) = swiss_pairings(table, &remaining, steps) {
+            pairings.insert(0, (first, opponent));
+            return Some(pairings);
+        }
+    }
+    None
+}
+
+/// Returns the games to play for the pairings of a round, numbered in order.
+fn jobs(config: &TournamentConfig, round: usize, pairings: &[(usize, usize)]) -> Vec<Job> {
+    // Each pair of games uses a new ballot, with the sides swapped for the second game
+    let ballots_per_round = config.games.div_ceil(2);
+    let mut jobs = Vec::new();
+    for &(first, second) in pairings {
+        for game in 0..config.games {
+            let ballot = ((round - 1) * ballots_per_round + game / 2) % BALLOT_COUNT + 1;
+            let (black, white) = match game % 2 {
+                0 => (first, second),
+                _ => (second, first),
+            };
+            jobs.push(Job {
+                round,
+                number: jobs.len() + 1,
+                black,
+                white,
+                ballot,
+            });
+        }
+    }
+    jobs
+}
+
+/// Plays jobs as they are received until there are no more or the tournament is stopped,
+/// sending each game as it finishes. Stops after sending an error.
+fn play_jobs(
+    config: &TournamentConfig,
+    jobs: &Mutex<Receiver<Job>>,
+    games: Sender<io::Result<TournamentGame>>,
+    stop: &AtomicBool,
+) {
+    let mut contestants: Vec<Option<Box<dyn Contestant>>> =
+        config.players.iter().map(|_| None).collect();
+    loop {
+        let job = jobs.lock().expect("jobs should not be poisoned").recv();
+        let job = match job {
+            Ok(job) if !stop.load(atomic::Ordering::Relaxed) => job,
+            _ => return,
+        };
+        let game = play_job(config, &mut contestants, job);
+        let failed = game.is_err();
+        if games.send(game).is_err() || failed {
+            return;
+        }
+    }
+}
+
+/// Plays a job, starting copies of its players if this thread doesn't have them yet.
+fn play_job(
+    config: &TournamentConfig,
+    contestants: &mut [Option<Box<dyn Contestant>>],
+    job: Job,
+) -> io::Result<TournamentGame> {
+    let mut take = |player: usize| match contestants[player].take() {
+        Some(contestant) => Ok(contestant),
+        None => config.players[player].create(),
+    };
+    let mut black = take(job.black)?;
+    let mut white = take(job.white)?;
+    let ballot = Ballot::get(job.ballot).expect("ballot should exist");
+    let game = play_game(black.as_mut(), white.as_mut(), &ballot, config.max_turns);
+    contestants[job.black] = Some(black);
+    contestants[job.white] = Some(white);
+    Ok(TournamentGame {
+        round: job.round,
+        number: job.number,
+        black: job.black,
+        white: job.white,
+        ballot: job.ballot,
+        game: game?,
+    })
+}
+
+/// Returns the points of a score, or None if no games have been played.
+fn points(score: &Score) -> Option<f64> {
+    score.ratio().map(|ratio| ratio * f64::from(score.games()))
+}
+
+/// Returns a config error for an error on a line.
+fn line_error<E: fmt::Display>(error: &E, line_number: usize) -> ConfigError {
+    ConfigError(format!("line {}: {}", line_number, error))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::engine::SearchLimits;
+
+    fn config(format: Format, players: usize) -> TournamentConfig {
+        TournamentConfig {
+            event: "Test".to_string(),
+            format,
+            players: (0..players)
+                .map(|player| {
+                    format!("random name=random{} seed={}", player, player + 1)
+                        .parse()
+                        .unwrap()
+                })
+                .collect(),
+            games: 2,
+            max_turns: 40,
+            concurrency: 2,
+            pdn: None,
+        }
+    }
+
+    #[test]
+    fn should_parse_config() {
+        let config: TournamentConfig = "
+            # Club championship
+            event name=\"Club championship\"
+            round-robin cycles=2
+            settings concurrency=3 pdn=club.pdn
+            player builtin name=deep depth=8
+            player hub command=./old time=0.5
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.event, "Club championship");
+        assert_eq!(config.format, Format::RoundRobin { cycles: 2 });
+        assert_eq!(config.rounds(), 2);
+        assert_eq!(config.concurrency, 3);
+        assert_eq!(config.games, 2);
+        assert_eq!(config.pdn, Some(PathBuf::from("club.pdn")));
+        assert_eq!(config.players[1].name, "old");
+
+        let errors = [
+            "player random\nswiss rounds=2\nplayer random\nswiss rounds=3",
+            "player random\nswiss\nplayer random",
+            "player random\nswiss rounds=2\nplayer random",
+            "player random\nsettings games=0\nplayer random",
+            "player random\nplayer random\nplayer builtin",
+            "player random\nknockout",
+            "player random",
+        ];
+        for text in errors.iter() {
+            assert!(text.parse::<TournamentConfig>().is_err(), "{}", text);
+        }
+        assert_eq!(
+            "player random\nknockout"
+                .parse::<TournamentConfig>()
+                .unwrap_err()
+                .to_string(),
+            "line 2: unknown setting \"knockout\""
+        );
+        assert_eq!(
+            "player random\nswiss rounds=2\nplayer random"
+                .parse::<TournamentConfig>()
+                .unwrap_err()
+                .to_string(),
+            "line 2: swiss needs fewer rounds than players"
+        );
+    }
+
+    #[test]
+    fn should_pair_every_player_once_in_round_robin() {
+        for &players in [4, 5].iter() {
+            let config = config(Format::RoundRobin { cycles: 1 }, players);
+            let table = TournamentTable::new(vec![String::new(); players]);
+            let mut met = vec![vec![0; players]; players];
+            for round in 1..=config.rounds() {
+                let (pairings, bye) = pairings(&config, &table, round);
+                assert_eq!(bye, None);
+                assert_eq!(pairings.len(), players / 2);
+                for (first, second) in pairings {
+                    met[first][second] += 1;
+                    met[second][first] += 1;
+                }
+            }
+
+            for (player, opponents) in met.iter().enumerate() {
+                for (opponent, &count) in opponents.iter().enumerate() {
+                    assert_eq!(count, if player == opponent { 0 } else { 1 });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_pair_swiss_rounds_by_points_without_rematches() {
+        let config = config(Format::Swiss { rounds: 3 }, 5);
+        let mut table = TournamentTable::new(vec![String::new(); 5]);
+        table.record(0, 1, Outcome::Win(Player1));
+        table.record(2, 3, Outcome::Win(Player1));
+        table.bye(4, 1.0);
+
+        let (second, bye) = pairings(&config, &table, 2);
+
+        // Players 0, 2 and 4 have a point, but 4 has had a bye so 3 sits out
+        assert_eq!(bye, Some(3));
+        assert_eq!(second, vec![(0, 2), (4, 1)]);
+        table.record(0, 2, Outcome::Draw);
+        table.record(4, 1, Outcome::Draw);
+        let (third, _) = pairings(&config, &table, 3);
+        assert!(third
+            .iter()
+            .all(|&(first, second)| !table.played(first, second)));
+    }
+
+    #[test]
+    fn should_give_up_on_swiss_rounds_without_pairings() {
+        let config = config(Format::Swiss { rounds: 23 }, 24);
+        let mut table = TournamentTable::new(vec![String::new(); 24]);
+        // Players 21, 22 and 23 are ranked last and have played everyone else, so there are no
+        // pairings without a rematch and searching them all would take far too long
+        for first in 21..24 {
+            for second in 0..21 {
+                table.record(first, second, Outcome::Win(Player2));
+            }
+        }
+
+        let (pairings, bye) = pairings(&config, &table, 22);
+
+        assert_eq!(bye, None);
+        assert_eq!(pairings.len(), 12);
+    }
+
+    #[test]
+    fn should_show_standings_and_crosstable() {
+        let mut table =
+            TournamentTable::new(vec!["a".to_string(), "bb".to_string(), "c".to_string()]);
+        table.record(0, 1, Outcome::Win(Player1));
+        table.record(1, 0, Outcome::Draw);
+        table.record(2, 1, Outcome::Win(Player2));
+        let standings = table.standings();
+
+        assert_eq!(
+            standings
+                .iter()
+                .map(|standing| standing.player)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(standings[0].points, 1.5);
+        assert_eq!(standings[1].sonneborn_berger, 0.5 * 1.5);
+        assert_eq!(
+            table.to_string(),
+            "  #  Name  Points  Games     +     =     -      SB
+  1  a        1.5      2     1     1     0    2.25
+  2  bb       1.5      3     1     1     1    0.75
+  3  c        0.0      1     0     0     1    0.00
+
+              1     2     3
+  1  a        x   1.5     .
+  2  bb     0.5     x   1.0
+  3  c        .   0.0     x
+"
+        );
+    }
+
+    #[test]
+    fn should_run_tournament_with_pdn_output() {
+        let path = std::env::temp_dir().join(format!(
+            "bit_checkers_tournament_{}.pdn",
+            std::process::id()
+        ));
+        let mut config = config(Format::Swiss { rounds: 2 }, 3);
+        config.players[0] = EngineSpec::builtin("deep", SearchLimits::depth(2));
+        config.pdn = Some(path.clone());
+        let mut output = Vec::new();
+        let table = run_tournament(&config, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let games = crate::checkers::pdn::parse_games(&std::fs::read_to_string(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let games = games.unwrap();
+
+        // Each round has one pairing of two games and a bye
+        assert_eq!(games.len(), 4);
+        assert_eq!(games[0].tag("Event"), Some("Test"));
+        assert!(games.iter().all(|game| game.replay().is_ok()));
+        assert_eq!(output.matches("has a bye").count(), 2);
+        assert!(output.contains("Round 2 game 2"));
+        let total: f64 = (0..3).map(|player| table.points(player)).sum();
+        assert_eq!(total, 8.0);
+    }
+}
@@ -0,0 +1,22 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when the command line arguments are invalid.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UsageError(pub(crate) String);
+
+/// Returns the value of an option parsed as a number.
+pub(crate) fn number<T: FromStr>(option: &str, value: &str) -> Result<T, UsageError> {
+    value
+        .parse()
+        .map_err(|_| UsageError(format!("invalid value \"{}\" for {}", value, option)))
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for UsageError {}
@@ -0,0 +1,308 @@
+use super::board::{Board, Player1};
+use super::turn::Turn;
+use super::util::Rng;
+use std::convert::TryInto;
+use std::sync::OnceLock;
+
+/// Number of openings in the three-move ballot.
+pub const BALLOT_COUNT: usize = 156;
+
+/// Notation for the openings of the three-move ballot used in English checkers tournaments, in
+/// deck order.
+///
+/// The openings and their numbering follow the 156-opening three-move deck of the American
+/// Checker Federation, which is sorted by Player1's first turn, then Player2's reply, then
+/// Player1's second turn.
+///
+/// Each opening is the notation for the first three turns of the game: Player1's first turn,
+/// Player2's reply and Player1's second turn. The rest of the game is played from the resulting
+/// position with Player2 to move. An opening that can be reached by more than one order of turns
+/// is listed once.
+pub const BALLOTS: [[&str; 3]; BALLOT_COUNT] = [
+    ["9-13", "21-17", "5-9"],
+    ["9-13", "21-17", "6-9"],
+    ["9-13", "21-17", "10-14"],
+    ["9-13", "22-17", "13x22"],
+    ["9-13", "22-18", "6-9"],
+    ["9-13", "22-18", "10-15"],
+    ["9-13", "22-18", "11-15"],
+    ["9-13", "23-18", "5-9"],
+    ["9-13", "23-18", "6-9"],
+    ["9-13", "23-18", "10-14"],
+    ["9-13", "23-19", "5-9"],
+    ["9-13", "23-19", "6-9"],
+    ["9-13", "23-19", "11-16"],
+    ["9-13", "24-19", "5-9"],
+    ["9-13", "24-19", "6-9"],
+    ["9-13", "24-19", "10-15"],
+    ["9-13", "24-19", "11-15"],
+    ["9-13", "24-20", "5-9"],
+    ["9-13", "24-20", "6-9"],
+    ["9-14", "22-17", "5-9"],
+    ["9-14", "22-18", "5-9"],
+    ["9-14", "22-18", "6-9"],
+    ["9-14", "22-18", "10-15"],
+    ["9-14", "22-18", "11-15"],
+    ["9-14", "22-18", "12-16"],
+    ["9-14", "22-18", "14-17"],
+    ["9-14", "23-18", "14x23"],
+    ["9-14", "23-19", "5-9"],
+    ["9-14", "23-19", "6-9"],
+    ["9-14", "23-19", "11-16"],
+    ["9-14", "23-19", "14-18"],
+    ["9-14", "24-19", "5-9"],
+    ["9-14", "24-19", "6-9"],
+    ["9-14", "24-19", "11-15"],
+    ["9-14", "24-20", "5-9"],
+    ["9-14", "24-20", "6-9"],
+    ["10-14", "22-18", "7-10"],
+    ["10-14", "22-18", "9-13"],
+    ["10-14", "22-18", "11-15"],
+    ["10-14", "23-18", "14x23"],
+    ["10-14", "23-19", "7-10"],
+    ["10-14", "23-19", "9-13"],
+    ["10-14", "23-19", "11-16"],
+    ["10-14", "24-19", "7-10"],
+    ["10-14", "24-19", "9-13"],
+    ["10-14", "24-20", "9-13"],
+    ["10-15", "21-17", "9-13"],
+    ["10-15", "21-17", "11-16"],
+    ["10-15", "21-17", "15-18"],
+    ["10-15", "22-17", "11-16"],
+    ["10-15", "22-17", "15-18"],
+    ["10-15", "22-17", "15-19"],
+    ["10-15", "22-18", "15x22"],
+    ["10-15", "23-18", "6-10"],
+    ["10-15", "23-18", "9-13"],
+    ["10-15", "23-18", "9-14"],
+    ["10-15", "23-18", "11-16"],
+    ["10-15", "23-18", "12-16"],
+    ["10-15", "23-19", "6-10"],
+    ["10-15", "23-19", "9-13"],
+    ["10-15", "23-19", "9-14"],
+    ["10-15", "23-19", "11-16"],
+    ["10-15", "23-19", "15-18"],
+    ["10-15", "24-19", "15x24"],
+    ["10-15", "24-20", "6-10"],
+    ["10-15", "24-20", "9-13"],
+    ["10-15", "24-20", "9-14"],
+    ["10-15", "24-20", "11-16"],
+    ["10-15", "24-20", "12-16"],
+    ["10-15", "24-20", "15-18"],
+    ["10-15", "24-20", "15-19"],
+    ["11-15", "21-17", "7-11"],
+    ["11-15", "21-17", "8-11"],
+    ["11-15", "21-17", "9-13"],
+    ["11-15", "21-17", "9-14"],
+    ["11-15", "21-17", "10-14"],
+    ["11-15", "21-17", "15-19"],
+    ["11-15", "22-17", "7-11"],
+    ["11-15", "22-17", "8-11"],
+    ["11-15", "22-17", "9-13"],
+    ["11-15", "22-17", "9-14"],
+    ["11-15", "22-17", "10-14"],
+    ["11-15", "22-17", "15-18"],
+    ["11-15", "22-17", "15-19"],
+    ["11-15", "22-18", "15x22"],
+    ["11-15", "23-18", "7-11"],
+    ["11-15", "23-18", "8-11"],
+    ["11-15", "23-18", "9-13"],
+    ["11-15", "23-18", "9-14"],
+    ["11-15", "23-18", "10-14"],
+    ["11-15", "23-18", "12-16"],
+    ["11-15", "23-18", "15-19"],
+    ["11-15", "23-19", "7-11"],
+    ["11-15", "23-19", "8-11"],
+    ["11-15", "23-19", "9-13"],
+    ["11-15", "23-19", "9-14"],
+    ["11-15", "23-19", "10-14"],
+    ["11-15", "24-19", "15x24"],
+    ["11-15", "24-20", "7-11"],
+    ["11-15", "24-20", "8-11"],
+    ["11-15", "24-20", "9-13"],
+    ["11-15", "24-20", "9-14"],
+    ["11-15", "24-20", "10-14"],
+    ["11-15", "24-20", "12-16"],
+    ["11-15", "24-20", "15-18"],
+    ["11-15", "24-20", "15-19"],
+    ["11-16", "21-17", "8-11"],
+    ["11-16", "21-17", "9-13"],
+    ["11-16", "21-17", "9-14"],
+    ["11-16", "21-17", "10-14"],
+    ["11-16", "21-17", "16-20"],
+    ["11-16", "22-17", "8-11"],
+    ["11-16", "22-17", "9-13"],
+    ["11-16", "22-17", "9-14"],
+    ["11-16", "22-17", "10-14"],
+    ["11-16", "22-17", "16-20"],
+    ["11-16", "22-18", "7-11"],
+    ["11-16", "22-18", "8-11"],
+    ["11-16", "22-18", "9-13"],
+    ["11-16", "22-18", "9-14"],
+    ["11-16", "22-18", "10-14"],
+    ["11-16", "22-18", "16-19"],
+    ["11-16", "22-18", "16-20"],
+    ["11-16", "23-18", "7-11"],
+    ["11-16", "23-18", "8-11"],
+    ["11-16", "23-18", "9-13"],
+    ["11-16", "23-18", "9-14"],
+    ["11-16", "23-18", "10-14"],
+    ["11-16", "23-18", "16-20"],
+    ["11-16", "23-19", "16x23"],
+    ["11-16", "24-19", "8-11"],
+    ["11-16", "24-19", "9-13"],
+    ["11-16", "24-19", "9-14"],
+    ["11-16", "24-19", "10-14"],
+    ["11-16", "24-19", "10-15"],
+    ["11-16", "24-19", "16-20"],
+    ["11-16", "24-20", "8-11"],
+    ["11-16", "24-20", "9-13"],
+    ["11-16", "24-20", "9-14"],
+    ["11-16", "24-20", "10-14"],
+    ["12-16", "21-17", "9-13"],
+    ["12-16", "21-17", "9-14"],
+    ["12-16", "21-17", "16-19"],
+    ["12-16", "21-17", "16-20"],
+    ["12-16", "22-17", "16-19"],
+    ["12-16", "22-17", "16-20"],
+    ["12-16", "22-18", "9-13"],
+    ["12-16", "22-18", "16-19"],
+    ["12-16", "22-18", "16-20"],
+    ["12-16", "23-18", "9-13"],
+    ["12-16", "23-18", "9-14"],
+    ["12-16", "23-18", "16-19"],
+    ["12-16", "23-18", "16-20"],
+    ["12-16", "23-19", "16x23"],
+    ["12-16", "24-19", "16-20"],
+    ["12-16", "24-20", "9-13"],
+];
+
+/// Type representing an opening from the three-move ballot.
+#[derive(Clone, Debug)]
+pub struct Ballot {
+    /// Number of the ballot in the deck, starting from 1.
+    pub number: usize,
+    /// The three turns of the opening.
+    pub turns: [Turn; 3],
+    /// The board after the opening has been played. It is Player2's turn to move.
+    pub board: Board,
+}
+
+impl Ballot {
+    /// Returns the ballot with the given number, or None if there is no such ballot.
+    ///
+    /// Ballots are numbered from 1 to `BALLOT_COUNT`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ballot = bit_checkers::ballot::Ballot::get(1).unwrap();
+    ///
+    /// assert_eq!(ballot.turns[0].to_string(), "9-13");
+    /// assert!(bit_checkers::ballot::Ballot::get(0).is_none());
+    /// ```
+    pub fn get(number: usize) -> Option<Ballot> {
+        deck().get(number.checked_sub(1)?).cloned()
+    }
+
+    /// Returns a ballot drawn at random from the deck.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut rng = bit_checkers::checkers::util::Rng::from_entropy();
+    /// let ballot = bit_checkers::ballot::Ballot::draw(&mut rng);
+    ///
+    /// assert!(ballot.number >= 1 && ballot.number <= bit_checkers::ballot::BALLOT_COUNT);
+    /// ```
+    pub fn draw(rng: &mut Rng) -> Ballot {
+        let number = rng.below(BALLOT_COUNT as u64) as usize + 1;
+        Ballot::get(number).unwrap()
+    }
+
+    /// Returns an iterator over every ballot in deck order.
+    pub fn all() -> impl Iterator<Item = Ballot> {
+        deck().iter().cloned()
+    }
+}
+
+/// Returns every ballot in deck order, playing out the notation the first time it is called.
+fn deck() -> &'static [Ballot] {
+    static DECK: OnceLock<Vec<Ballot>> = OnceLock::new();
+    DECK.get_or_init(|| {
+        BALLOTS
+            .iter()
+            .enumerate()
+            .map(|(i, notation)| {
+                let (board, turns) = play(notation).expect("ballot should be a legal opening");
+                Ballot {
+                    number: i + 1,
+                    turns,
+                    board,
+                }
+            })
+            .collect()
+    })
+}
+
+/// Plays the notation for an opening from the initial board, returning the resulting board and
+/// the turns played, or None if any turn is not legal.
+fn play(notation: &[&str; 3]) -> Option<(Board, [Turn; 3])> {
+    let mut board = Board::new();
+    let mut player = Player1;
+    let mut turns = Vec::with_capacity(notation.len());
+    for notation in notation {
+        let turn = board.find_turn(player, &notation.parse().ok()?)?;
+        board = board.apply_turn(player, turn);
+        player = player.opponent();
+        turns.push(turn);
+    }
+    Some((board, turns.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_have_legal_ballots() {
+        for (i, notation) in BALLOTS.iter().enumerate() {
+            assert!(play(notation).is_some(), "ballot {} {:?}", i + 1, notation);
+        }
+    }
+
+    #[test]
+    fn should_have_distinct_ballots() {
+        let ballots: Vec<Ballot> = Ballot::all().collect();
+
+        assert_eq!(ballots.len(), BALLOT_COUNT);
+        for (i, ballot) in ballots.iter().enumerate() {
+            assert_eq!(ballot.number, i + 1);
+            // Openings reaching the same position by a different order of moves are the same
+            assert!(
+                ballots[..i].iter().all(|other| other.board != ballot.board),
+                "ballot {} is repeated",
+                ballot.number
+            );
+        }
+    }
+
+    #[test]
+    fn should_number_ballots_in_deck_order() {
+        let notation = |number| {
+            let ballot = Ballot::get(number).unwrap();
+            let turns: Vec<String> = ballot.turns.iter().map(Turn::to_string).collect();
+            turns.join(" ")
+        };
+
+        assert_eq!(notation(1), "9-13 21-17 5-9");
+        assert_eq!(notation(4), "9-13 22-17 13x22");
+        assert_eq!(notation(27), "9-14 23-18 14x23");
+        assert_eq!(notation(85), "11-15 22-18 15x22");
+        assert_eq!(notation(94), "11-15 23-19 8-11");
+        assert_eq!(notation(95), "11-15 23-19 9-13");
+        assert_eq!(notation(97), "11-15 23-19 10-14");
+        assert_eq!(notation(98), "11-15 24-19 15x24");
+        assert_eq!(notation(BALLOT_COUNT), "12-16 24-20 9-13");
+    }
+}
@@ -1,11 +1,20 @@
+#[cfg(feature = "std")]
+use super::render::Renderer;
+use super::turn::{Turn, TurnList};
 use super::util::BitGrid;
-use std::fmt;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::error::Error;
 
 const BOARD_WIDTH: u32 = 8;
 const BOARD_HEIGHT: u32 = 8;
 const BOARD_MASK: u64 = 0x55AA55AA55AA55AA;
+const SQUARE_COUNT: u32 = 32;
 
 #[derive(Clone, Copy, PartialEq, fmt::Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Player {
     Player1,
     Player2,
@@ -13,29 +22,173 @@ pub enum Player {
 pub use Player::{Player1, Player2};
 
 /// Type representing a checkers board
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Board {
     player1: PlayerBoard,
     player2: PlayerBoard,
 }
 
-#[derive(Clone, Copy, PartialEq, fmt::Debug)]
+/// Type representing a position on the board as x and y coordinates.
+///
+/// Player1 starts at the top of the board (y = 0) and moves downwards, Player2 starts at the
+/// bottom (y = 7) and moves upwards. Only positions where x + y is odd are used by the game.
+///
+/// Positions can be converted to the standard 1 to 32 square numbering used in checkers
+/// literature, where Player1 is Black and square 1 is at Black's left-hand side of their back row
+/// as viewed from White's side, and to the index of the cell in a `BitGrid`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, fmt::Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position(pub u32, pub u32);
 
-#[derive(Clone, Copy, PartialEq, fmt::Debug)]
+/// Type representing a single step of a piece, either to an adjacent square or jumping over one.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, fmt::Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     pub from: Position,
     pub to: Position,
 }
 
-#[derive(PartialEq)]
+/// Error returned when parsing a move from standard notation fails.
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseMoveError(String);
+
+/// Type representing a way in which a board is not a valid position.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Violation {
+    /// A piece is not on a playable square.
+    LightSquare(Position),
+    /// Both players have a piece on the same square.
+    OverlappingPieces(Position),
+    /// More than one of a player's pieces were given for the same square.
+    DuplicatePiece(Player, Position),
+    /// A king is on a square without one of its player's pieces.
+    KingWithoutPiece(Player, Position),
+    /// A man is on its player's promotion row without having been crowned.
+    UncrownedMan(Player, Position),
+}
+
+/// Error returned when a board is not a valid position, containing every violation found.
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct InvalidBoard(pub Vec<Violation>);
+
+#[derive(PartialEq, fmt::Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Piece {
     pub position: Position,
     pub player: Player,
     pub king: bool,
 }
 
+impl Player {
+    /// Returns the other player.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Player1, Player2};
+    ///
+    /// assert_eq!(Player1.opponent(), Player2);
+    /// assert_eq!(Player2.opponent(), Player1);
+    /// ```
+    pub fn opponent(self) -> Player {
+        match self {
+            Player1 => Player2,
+            Player2 => Player1,
+        }
+    }
+}
+
+impl Position {
+    /// Returns the position of a square in the standard 1 to 32 numbering.
+    ///
+    /// Squares 1 to 4 are on Player1's back row (y = 0) and squares 29 to 32 are on Player2's back
+    /// row (y = 7). Returns None if the square number is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Position;
+    ///
+    /// assert_eq!(Position::from_square(1), Some(Position(1, 0)));
+    /// assert_eq!(Position::from_square(32), Some(Position(6, 7)));
+    /// assert_eq!(Position::from_square(33), None);
+    /// ```
+    pub fn from_square(square: u32) -> Option<Position> {
+        if square == 0 || square > SQUARE_COUNT {
+            return None;
+        }
+        let index = square - 1;
+        let y = index / (BOARD_WIDTH / 2);
+        let x = (index % (BOARD_WIDTH / 2)) * 2 + (1 - y % 2);
+        Some(Position(x, y))
+    }
+
+    /// Returns the standard 1 to 32 square number for the position, or None if the position is not
+    /// a playable square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Position;
+    ///
+    /// assert_eq!(Position(7, 0).square(), Some(4));
+    /// assert_eq!(Position(0, 0).square(), None);
+    /// ```
+    pub fn square(self) -> Option<u32> {
+        let Position(x, y) = self;
+        if x >= BOARD_WIDTH || y >= BOARD_HEIGHT || (x + y) % 2 == 0 {
+            None
+        } else {
+            Some(y * (BOARD_WIDTH / 2) + x / 2 + 1)
+        }
+    }
+
+    /// Returns the position of a cell in a `BitGrid`, or None if the index is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Position;
+    ///
+    /// assert_eq!(Position::from_index(9), Some(Position(1, 1)));
+    /// assert_eq!(Position::from_index(64), None);
+    /// ```
+    pub fn from_index(index: u32) -> Option<Position> {
+        if index < BOARD_WIDTH * BOARD_HEIGHT {
+            Some(Position(index % BOARD_WIDTH, index / BOARD_WIDTH))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of the position's cell in a `BitGrid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Position;
+    /// let index = Position::from_square(1).unwrap().index();
+    ///
+    /// assert_eq!(index, 1);
+    /// assert!(bit_checkers::board::Board::new()
+    ///     .pieces(bit_checkers::board::Player1)
+    ///     .get_at_index(index));
+    /// ```
+    pub fn index(self) -> u32 {
+        let Position(x, y) = self;
+        y * BOARD_WIDTH + x
+    }
+}
+
 impl Move {
+    /// Returns whether the move jumps over a square.
+    pub fn is_jump(self) -> bool {
+        let Move { from, to } = self;
+        (from.0 as i32 - to.0 as i32).abs() == 2
+    }
+
     fn new(from: Position, offset: (i32, i32)) -> Move {
         Move {
             from: Position(from.0, from.1),
@@ -47,7 +200,7 @@ impl Move {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct PlayerBoard {
     pub all: BitGrid,
     pub kings: BitGrid,
@@ -73,7 +226,8 @@ impl Board {
     /// Returns a new board containing the given pieces.
     ///
     /// Panics or returns an undefined result if the positions are invalid
-    /// (e.g. positions outside bounds of board, multiple pieces with same position)
+    /// (e.g. positions outside bounds of board, multiple pieces with same position). Use
+    /// `try_new_with_pieces` for pieces that may not be a valid position.
     pub fn new_with_pieces<T>(pieces: T) -> Board
     where
         T: IntoIterator<Item = Piece>,
@@ -104,10 +258,154 @@ impl Board {
         board
     }
 
+    /// Returns a board with the given pieces, or every way in which they are not a valid
+    /// position.
+    ///
+    /// Unlike `new_with_pieces` this doesn't panic, so it can be used for pieces from files or the
+    /// network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, InvalidBoard, Piece, Player1, Position, Violation};
+    /// let result = Board::try_new_with_pieces(vec![Piece {
+    ///     position: Position(0, 0),
+    ///     player: Player1,
+    ///     king: false,
+    /// }]);
+    ///
+    /// assert_eq!(
+    ///     result,
+    ///     Err(InvalidBoard(vec![Violation::LightSquare(Position(0, 0))]))
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_new_with_pieces<T>(pieces: T) -> Result<Board, InvalidBoard>
+    where
+        T: IntoIterator<Item = Piece>,
+    {
+        let mut board = Board::new_with_pieces(None);
+        let mut violations = Vec::new();
+        for piece in pieces {
+            if piece.position.square().is_none() {
+                violations.push(Violation::LightSquare(piece.position));
+                continue;
+            }
+            let index = piece.position.index();
+            let player_board = board.player_board_mut(piece.player);
+            if player_board.all.get_at_index(index) {
+                violations.push(Violation::DuplicatePiece(piece.player, piece.position));
+                continue;
+            }
+            player_board.all = player_board.all.set_at_index(index, true);
+            if piece.king {
+                player_board.kings = player_board.kings.set_at_index(index, true);
+            }
+        }
+        if let Err(InvalidBoard(others)) = board.validate() {
+            violations.extend(others);
+        }
+
+        if violations.is_empty() {
+            Ok(board)
+        } else {
+            Err(InvalidBoard(violations))
+        }
+    }
+
+    /// Returns whether the board is a valid position, or every way in which it is not.
+    ///
+    /// A valid position has pieces only on playable squares, at most one piece on each square,
+    /// kings only on squares with one of their player's pieces and no uncrowned men on their
+    /// player's promotion row. Violations are ordered by square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Board;
+    ///
+    /// assert_eq!(Board::new().validate(), Ok(()));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn validate(&self) -> Result<(), InvalidBoard> {
+        let violations: Vec<Violation> = self.violations().collect();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidBoard(violations))
+        }
+    }
+
+    /// Returns whether the board is a valid position, see `validate`.
+    pub fn is_valid(&self) -> bool {
+        self.violations().next().is_none()
+    }
+
+    /// Returns an iterator over every way in which the board is not a valid position, ordered by
+    /// square. See `validate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Piece, Player2, Position, Violation};
+    /// let board = Board::new_with_pieces(vec![Piece {
+    ///     position: Position(1, 0),
+    ///     player: Player2,
+    ///     king: false,
+    /// }]);
+    ///
+    /// assert_eq!(
+    ///     board.violations().collect::<Vec<_>>(),
+    ///     vec![Violation::UncrownedMan(Player2, Position(1, 0))]
+    /// );
+    /// assert!(!board.is_valid());
+    /// ```
+    pub fn violations(&self) -> impl Iterator<Item = Violation> + '_ {
+        let occupied = self
+            .player1
+            .all
+            .union(self.player1.kings)
+            .union(self.player2.all)
+            .union(self.player2.kings);
+        occupied
+            .iter_set_indexes()
+            .flat_map(move |index| self.violations_at(index))
+    }
+
+    /// Returns the violations on the square with the given index, see `violations`.
+    fn violations_at(&self, index: u32) -> impl Iterator<Item = Violation> {
+        let position = Position::from_index(index).expect("index should be on the board");
+        if position.square().is_none() {
+            let violations = [Some(Violation::LightSquare(position)), None, None];
+            return IntoIterator::into_iter(violations).flatten();
+        }
+        let overlapping = (self.player1.all.get_at_index(index)
+            && self.player2.all.get_at_index(index))
+        .then_some(Violation::OverlappingPieces(position));
+        let [player1, player2] = [Player1, Player2].map(|player| {
+            let player_board = self.player_board(player);
+            let piece = player_board.all.get_at_index(index);
+            let king = player_board.kings.get_at_index(index);
+            let promotion_row = match player {
+                Player1 => BOARD_HEIGHT - 1,
+                Player2 => 0,
+            };
+            if king && !piece {
+                Some(Violation::KingWithoutPiece(player, position))
+            } else if piece && !king && position.1 == promotion_row {
+                Some(Violation::UncrownedMan(player, position))
+            } else {
+                None
+            }
+        });
+        IntoIterator::into_iter([overlapping, player1, player2]).flatten()
+    }
+
     /// Returns a new board with a move applied to a particular player's piece.
     ///
     /// Panics or returns a board in an invalid state if the specified move is invalid.
-    /// Pieces will be promoted to kings when appropriate.
+    /// Pieces will be promoted to kings when appropriate, and the opponent's piece is removed when
+    /// the move is a jump.
     ///
     /// # Examples
     ///
@@ -117,7 +415,13 @@ impl Board {
     /// let board = board.move_piece(bit_checkers::board::Player1, a_move);
     /// ```
     pub fn move_piece(mut self, player: Player, Move { from, to }: Move) -> Board {
-        // Todo: create kings
+        // Remove the jumped piece if the move is a jump
+        if (from.0 as i32 - to.0 as i32).abs() == 2 {
+            let (x, y) = ((from.0 + to.0) / 2, (from.1 + to.1) / 2);
+            let opponent_board = self.player_board_mut(player.opponent());
+            opponent_board.all = opponent_board.all.set_at_cell(x, y, false);
+            opponent_board.kings = opponent_board.kings.set_at_cell(x, y, false);
+        }
         let player_board = self.player_board_mut(player);
         // Move the flag in the players 'all' board
         player_board.all = player_board
@@ -204,6 +508,110 @@ impl Board {
             .chain(get_jumps(upward_moving, -1, 1))
     }
 
+    /// Returns an iterator over the complete turns that the given player can make.
+    ///
+    /// Jumps are mandatory, so only turns made up of jumps are returned when any are available.
+    /// A jumping piece must keep jumping while it is able to, unless it was crowned by the jump.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let board = bit_checkers::board::Board::new();
+    /// let turns: Vec<String> = board
+    ///     .turns(bit_checkers::board::Player1)
+    ///     .map(|turn| turn.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(turns.len(), 7);
+    /// assert!(turns.contains(&"11-15".to_string()));
+    /// ```
+    pub fn turns(&self, player: Player) -> impl Iterator<Item = Turn> {
+        let mut turns = TurnList::new();
+        for m in self.jump_moves(player) {
+            let king = self
+                .player_board(player)
+                .kings
+                .get_at_cell(m.from.0, m.from.1);
+            let board = self.clone().move_piece(player, m);
+            board.add_jump_turns(player, king, Turn::from_move(m), &mut turns);
+        }
+        if turns.is_empty() {
+            for m in self.normal_moves(player) {
+                turns.push(Turn::from_move(m));
+            }
+        }
+        turns.into_iter()
+    }
+
+    /// Returns the legal turn matching the given notation, or None if there is no matching turn or
+    /// the notation is ambiguous.
+    ///
+    /// See `Turn::matches` for how notation is matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let board = bit_checkers::board::Board::new();
+    /// let notation = "11-15".parse().unwrap();
+    ///
+    /// assert!(board.find_turn(bit_checkers::board::Player1, &notation).is_some());
+    /// assert!(board.find_turn(bit_checkers::board::Player2, &notation).is_none());
+    /// ```
+    pub fn find_turn(&self, player: Player, notation: &Turn) -> Option<Turn> {
+        let mut matching = self.turns(player).filter(|turn| turn.matches(notation));
+        let turn = matching.next()?;
+        match matching.next() {
+            Some(_) => None,
+            None => Some(turn),
+        }
+    }
+
+    /// Returns a new board with every move of a turn applied to a particular player's piece.
+    ///
+    /// Panics or returns a board in an invalid state if the specified turn is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let board = bit_checkers::board::Board::new();
+    /// let turn = board.turns(bit_checkers::board::Player1).next().unwrap();
+    /// let board = board.apply_turn(bit_checkers::board::Player1, turn);
+    /// ```
+    pub fn apply_turn(self, player: Player, turn: Turn) -> Board {
+        turn.moves()
+            .fold(self, |board, m| board.move_piece(player, m))
+    }
+
+    /// Returns a hash of the board together with the player whose turn it is.
+    ///
+    /// The hash does not depend on the platform or process, so it can be stored and compared
+    /// later (e.g. as the key for an opening book).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let board = bit_checkers::board::Board::new();
+    ///
+    /// assert_ne!(
+    ///     board.position_hash(bit_checkers::board::Player1),
+    ///     board.position_hash(bit_checkers::board::Player2)
+    /// );
+    /// ```
+    pub fn position_hash(&self, player: Player) -> u64 {
+        let seed = match player {
+            Player1 => 0x9E37_79B9_7F4A_7C15,
+            Player2 => 0xD1B5_4A32_D192_ED03,
+        };
+        [
+            self.player1.all,
+            self.player1.kings,
+            self.player2.all,
+            self.player2.kings,
+        ]
+        .iter()
+        .fold(seed, |hash, grid| mix_bits(hash ^ grid.mask()))
+    }
+
     /// Returns the winning player based on the current board, or None if the game is still in
     /// progress.
     ///
@@ -304,6 +712,65 @@ impl Board {
         }
     }
 
+    /// Returns a BitGrid with flag set for every piece belonging to the given player.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let board = bit_checkers::board::Board::new();
+    ///
+    /// assert_eq!(board.pieces(bit_checkers::board::Player1).count(), 12);
+    /// ```
+    pub fn pieces(&self, player: Player) -> BitGrid {
+        self.player_board(player).all
+    }
+
+    /// Returns a BitGrid with flag set for every king belonging to the given player.
+    pub fn kings(&self, player: Player) -> BitGrid {
+        self.player_board(player).kings
+    }
+
+    /// Returns a board with each player's pieces and kings given by BitGrids.
+    ///
+    /// The grids are not checked, so the board may be in an invalid state.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn from_grids(
+        player1: BitGrid,
+        player1_kings: BitGrid,
+        player2: BitGrid,
+        player2_kings: BitGrid,
+    ) -> Board {
+        Board {
+            player1: PlayerBoard {
+                all: player1,
+                kings: player1_kings,
+            },
+            player2: PlayerBoard {
+                all: player2,
+                kings: player2_kings,
+            },
+        }
+    }
+
+    /// Adds turns continuing on from a jump to the list of turns.
+    ///
+    /// The board should have the jumps made so far already applied.
+    fn add_jump_turns(&self, player: Player, king: bool, turn: Turn, turns: &mut TurnList) {
+        let Position(x, y) = turn.to();
+        let crowned = !king && self.player_board(player).kings.get_at_cell(x, y);
+        let mut continued = false;
+        if !crowned {
+            for m in self.jump_moves(player).filter(|m| m.from == turn.to()) {
+                let board = self.clone().move_piece(player, m);
+                board.add_jump_turns(player, king, turn.then(m.to), turns);
+                continued = true;
+            }
+        }
+        if !continued {
+            turns.push(turn);
+        }
+    }
+
     /// Returns a BitGrid with flag set for valid, empty squares.
     fn empty_squares(&self) -> BitGrid {
         self.player1
@@ -348,6 +815,153 @@ impl Board {
     }
 }
 
+impl fmt::Display for Move {
+    /// Formats the move in standard notation using square numbers, e.g. `11-15` or `15x24`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Move, Position};
+    /// let a_move = Move {
+    ///     from: Position(5, 2),
+    ///     to: Position(3, 4),
+    /// };
+    ///
+    /// assert_eq!(a_move.to_string(), "11x18");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.is_jump() { 'x' } else { '-' };
+        for (i, position) in [self.from, self.to].iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", separator)?;
+            }
+            match position.square() {
+                Some(square) => write!(f, "{}", square)?,
+                None => write!(f, "{:?}", position)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    /// Parses a move from standard notation using square numbers.
+    ///
+    /// A normal move is separated by `-` and must be to an adjacent diagonal square. A jump is
+    /// separated by `x` or `:` and must be over an adjacent diagonal square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Move, Position};
+    /// let a_move: Move = "22-18".parse().unwrap();
+    ///
+    /// assert_eq!(a_move.from, Position(2, 5));
+    /// assert_eq!(a_move.to, Position(3, 4));
+    /// assert!("22x18".parse::<Move>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Move, ParseMoveError> {
+        let error = || ParseMoveError(s.to_string());
+        let jump = s.contains(&['x', ':'][..]);
+        let mut squares = s.split(&['-', 'x', ':'][..]).map(|square| {
+            square
+                .parse()
+                .ok()
+                .and_then(Position::from_square)
+                .ok_or_else(error)
+        });
+        let (from, to) = match (squares.next(), squares.next(), squares.next()) {
+            (Some(from), Some(to), None) => (from?, to?),
+            _ => return Err(error()),
+        };
+        let distance = if jump { 2 } else { 1 };
+        if (from.0 as i32 - to.0 as i32).abs() != distance
+            || (from.1 as i32 - to.1 as i32).abs() != distance
+        {
+            return Err(error());
+        }
+        Ok(Move { from, to })
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid move notation \"{}\"", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseMoveError {}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::LightSquare(position) => {
+                write!(f, "piece at {:?} is not on a playable square", position)
+            }
+            Violation::OverlappingPieces(position) => {
+                write!(f, "both players have a piece at {:?}", position)
+            }
+            Violation::DuplicatePiece(player, position) => {
+                write!(f, "{:?} has more than one piece at {:?}", player, position)
+            }
+            Violation::KingWithoutPiece(player, position) => {
+                write!(
+                    f,
+                    "{:?} has a king without a piece at {:?}",
+                    player, position
+                )
+            }
+            Violation::UncrownedMan(player, position) => write!(
+                f,
+                "{:?} has an uncrowned man on its promotion row at {:?}",
+                player, position
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for InvalidBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid board")?;
+        for (i, violation) in self.0.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { ":" } else { ";" }, violation)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for InvalidBoard {}
+
+impl Default for Board {
+    fn default() -> Board {
+        Board::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Board {
+    /// Formats the board as text using the default `Renderer`, with Player1's pieces as `b` at
+    /// the top and Player2's pieces as `w` at the bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let text = bit_checkers::board::Board::new().to_string();
+    ///
+    /// assert_eq!(text.lines().next(), Some("8     b     b     b     b "));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Renderer::new().write(f, self)
+    }
+}
+
 impl fmt::Debug for Board {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for y in 0..BOARD_HEIGHT {
@@ -377,9 +991,18 @@ impl fmt::Debug for Board {
     }
 }
 
+/// Mixes the bits of a value (the finaliser from SplitMix64).
+fn mix_bits(value: u64) -> u64 {
+    let value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    let value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    #[cfg(not(feature = "std"))]
+    use std::{vec, vec::Vec};
 
     #[test]
     fn should_move_pieces() {
@@ -404,7 +1027,7 @@ mod test {
         assert!(board3.piece_at(Position(0, 5)).is_none());
         assert!(board.piece_at(Position(1, 4)).is_none());
         assert_eq!(board2.piece_at(Position(1, 4)).unwrap().player, Player2);
-        assert_eq!(board2.piece_at(Position(1, 4)).unwrap().king, false);
+        assert!(!board2.piece_at(Position(1, 4)).unwrap().king);
         assert_eq!(board3.piece_at(Position(1, 4)).unwrap().player, Player2);
 
         assert_eq!(board.piece_at(Position(5, 2)).unwrap().player, Player1);
@@ -413,7 +1036,7 @@ mod test {
         assert!(board.piece_at(Position(4, 3)).is_none());
         assert!(board2.piece_at(Position(4, 3)).is_none());
         assert_eq!(board3.piece_at(Position(4, 3)).unwrap().player, Player1);
-        assert_eq!(board3.piece_at(Position(4, 3)).unwrap().king, false);
+        assert!(!board3.piece_at(Position(4, 3)).unwrap().king);
     }
 
     #[test]
@@ -529,6 +1152,40 @@ mod test {
         assert!(player2_moves.contains(&Move::new(Position(2, 5), (-1, -1))));
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_get_normal_moves_from_diagram() {
+        let board = crate::board![
+               _  (1) (2)  _ ;
+             _   _   _   _   ;
+               _   _   _   _ ;
+             1   _   _   _   ;
+               _  (2)  _   1 ;
+             _   2   1   _   ;
+               _  (1)  _   _ ;
+             _   _  (1)  _
+        ];
+
+        let player1_moves: Vec<Move> = board.normal_moves(Player1).collect();
+        let player2_moves: Vec<Move> = board.normal_moves(Player2).collect();
+
+        assert_eq!(player1_moves.len(), 7);
+        assert!(player1_moves.contains(&Move::new(Position(3, 0), (-1, 1))));
+        assert!(player1_moves.contains(&Move::new(Position(3, 0), (1, 1))));
+        assert!(player1_moves.contains(&Move::new(Position(0, 3), (1, 1))));
+        assert!(player1_moves.contains(&Move::new(Position(7, 4), (-1, 1))));
+        assert!(player1_moves.contains(&Move::new(Position(4, 5), (1, 1))));
+        assert!(player1_moves.contains(&Move::new(Position(3, 6), (-1, 1))));
+        assert!(player1_moves.contains(&Move::new(Position(4, 7), (1, -1))));
+
+        assert_eq!(player2_moves.len(), 5);
+        assert!(player2_moves.contains(&Move::new(Position(5, 0), (1, 1))));
+        assert!(player2_moves.contains(&Move::new(Position(5, 0), (-1, 1))));
+        assert!(player2_moves.contains(&Move::new(Position(3, 4), (1, -1))));
+        assert!(player2_moves.contains(&Move::new(Position(3, 4), (-1, -1))));
+        assert!(player2_moves.contains(&Move::new(Position(2, 5), (-1, -1))));
+    }
+
     #[test]
     fn should_get_jump_moves() {
         let board = Board::new_with_pieces(vec![
@@ -579,4 +1236,183 @@ mod test {
         assert_eq!(player2_moves.len(), 1);
         assert!(player2_moves.contains(&Move::new(Position(1, 6), (2, -2))));
     }
+
+    #[test]
+    fn should_remove_jumped_pieces() {
+        let board = Board::new_with_pieces(vec![
+            Piece {
+                player: Player1,
+                king: false,
+                position: Position(1, 2),
+            },
+            Piece {
+                player: Player2,
+                king: true,
+                position: Position(2, 3),
+            },
+        ]);
+        let board = board.move_piece(
+            Player1,
+            Move {
+                from: Position(1, 2),
+                to: Position(3, 4),
+            },
+        );
+
+        assert!(board.piece_at(Position(2, 3)).is_none());
+        assert_eq!(board.winner(), Some(Player1));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_get_multi_jump_turns() {
+        let board = Board::new_with_pieces(vec![
+            Piece {
+                player: Player1,
+                king: false,
+                position: Position(1, 2),
+            },
+            Piece {
+                player: Player1,
+                king: false,
+                position: Position(7, 0),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(2, 3),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(2, 5),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(4, 5),
+            },
+        ]);
+
+        let turns: Vec<String> = board.turns(Player1).map(|t| t.to_string()).collect();
+
+        assert_eq!(turns.len(), 2);
+        assert!(turns.contains(&"9x18x25".to_string()));
+        assert!(turns.contains(&"9x18x27".to_string()));
+        assert!(board.find_turn(Player1, &"9x25".parse().unwrap()).is_some());
+        assert!(board.find_turn(Player1, &"9x18".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_end_turn_when_crowned() {
+        let board = Board::new_with_pieces(vec![
+            Piece {
+                player: Player1,
+                king: false,
+                position: Position(2, 5),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(3, 6),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(5, 6),
+            },
+        ]);
+
+        let turns: Vec<String> = board.turns(Player1).map(|t| t.to_string()).collect();
+
+        assert_eq!(turns, vec!["22x31"]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_report_all_violations() {
+        let board = Board::from_grids(
+            BitGrid::new_from_mask(0x4000_0000_0000_0002),
+            BitGrid::new_from_mask(0x0000_0000_0000_0400),
+            BitGrid::new_from_mask(0x0000_0000_0001_0002),
+            BitGrid::new(),
+        );
+
+        assert_eq!(
+            board.validate(),
+            Err(InvalidBoard(vec![
+                Violation::OverlappingPieces(Position(1, 0)),
+                Violation::UncrownedMan(Player2, Position(1, 0)),
+                Violation::KingWithoutPiece(Player1, Position(2, 1)),
+                Violation::LightSquare(Position(0, 2)),
+                Violation::UncrownedMan(Player1, Position(6, 7)),
+            ]))
+        );
+        assert_eq!(Board::new().validate(), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_not_construct_invalid_board() {
+        let piece = |x, y, player, king| Piece {
+            position: Position(x, y),
+            player,
+            king,
+        };
+
+        assert_eq!(
+            Board::try_new_with_pieces(vec![
+                piece(2, 3, Player1, false),
+                piece(8, 1, Player1, true),
+                piece(2, 3, Player1, true),
+                piece(2, 3, Player2, false),
+            ]),
+            Err(InvalidBoard(vec![
+                Violation::LightSquare(Position(8, 1)),
+                Violation::DuplicatePiece(Player1, Position(2, 3)),
+                Violation::OverlappingPieces(Position(2, 3)),
+            ]))
+        );
+        assert_eq!(
+            Board::try_new_with_pieces(vec![piece(4, 7, Player1, true)]),
+            Ok(Board::new_with_pieces(vec![piece(4, 7, Player1, true)]))
+        );
+    }
+
+    #[test]
+    fn should_convert_square_numbers() {
+        for square in 1..=SQUARE_COUNT {
+            let position = Position::from_square(square).unwrap();
+
+            assert_eq!(position.square(), Some(square));
+            assert_eq!(Position::from_index(position.index()), Some(position));
+            assert!(BitGrid::new_from_mask(BOARD_MASK).get_at_index(position.index()));
+        }
+        assert_eq!(Position::from_square(29), Some(Position(0, 7)));
+        assert_eq!(Position(1, 1).square(), None);
+        assert_eq!(Position(8, 1).square(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_parse_and_format_moves() {
+        let moves: Vec<Move> = Board::new()
+            .normal_moves(Player2)
+            .chain(Board::new().jump_moves(Player1))
+            .collect();
+        for m in moves {
+            assert_eq!(m.to_string().parse::<Move>(), Ok(m));
+        }
+        assert_eq!(
+            "15:24".parse::<Move>().map(|m| m.to_string()),
+            Ok("15x24".to_string())
+        );
+        for notation in &["11-17", "11x15", "11-15-18", "0-4", "11-", "a-b"] {
+            assert_eq!(
+                notation.parse::<Move>(),
+                Err(ParseMoveError(notation.to_string()))
+            );
+        }
+    }
 }
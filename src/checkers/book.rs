@@ -0,0 +1,375 @@
+use super::ballot::Ballot;
+use super::board::{Board, Player, Player1};
+use super::pdn::{self, PdnError};
+use super::turn::Turn;
+use super::util::Rng;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/// Default maximum number of turns from the start of a game that are added to a book.
+const DEFAULT_MAX_PLIES: usize = 16;
+
+/// A turn stored in an opening book, with a weight used to choose between turns.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BookMove {
+    pub turn: Turn,
+    pub weight: u32,
+}
+
+/// Type representing an opening book: a set of weighted turns for known positions.
+///
+/// Positions are keyed by `Board::position_hash`, so a book only needs to store the hash of each
+/// position rather than the position itself.
+///
+/// Books are stored as text with one turn per line, containing the position hash in hexadecimal,
+/// the turn in standard notation and the weight:
+///
+/// ```text
+/// 8d2b6e8c0f1a2b3c 11-15 120
+/// 8d2b6e8c0f1a2b3c 9-14 45
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct OpeningBook {
+    positions: HashMap<u64, Vec<BookMove>>,
+}
+
+/// Type for building an opening book from known lines of play.
+///
+/// Each time a turn is played from a position in the added lines its weight is increased by one.
+#[derive(Clone, Debug)]
+pub struct OpeningBookBuilder {
+    positions: HashMap<u64, Vec<BookMove>>,
+    max_plies: usize,
+}
+
+/// Error returned when building or reading an opening book fails.
+#[derive(Debug)]
+pub enum BookError {
+    Io(io::Error),
+    Pdn(PdnError),
+    /// A line of the book file could not be parsed. Contains the line number.
+    Parse(usize),
+    /// A turn in an added line is not legal. Contains the index of the turn within the line.
+    IllegalTurn(usize, Turn),
+}
+
+impl OpeningBook {
+    /// Returns an empty opening book.
+    pub fn new() -> OpeningBook {
+        OpeningBook::default()
+    }
+
+    /// Returns the number of positions in the book.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns whether the book contains no positions.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the book moves for the given board and player to move.
+    ///
+    /// Only turns that are legal on the board are returned, so positions with colliding hashes
+    /// can't produce illegal turns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1};
+    /// use bit_checkers::turn::Turn;
+    /// let mut builder = bit_checkers::book::OpeningBookBuilder::new();
+    /// for line in &[["11-15", "23-19"], ["11-15", "22-17"], ["9-14", "22-18"]] {
+    ///     let turns: Vec<Turn> = line.iter().map(|turn| turn.parse().unwrap()).collect();
+    ///     builder.add_line(Board::new(), Player1, &turns).unwrap();
+    /// }
+    /// let book = builder.build();
+    /// let moves = book.moves(&Board::new(), Player1);
+    ///
+    /// assert_eq!(moves.len(), 2);
+    /// assert_eq!(moves[0].turn.to_string(), "11-15");
+    /// assert_eq!(moves[0].weight, 2);
+    /// ```
+    pub fn moves(&self, board: &Board, player: Player) -> Vec<BookMove> {
+        match self.positions.get(&board.position_hash(player)) {
+            Some(moves) => moves
+                .iter()
+                .filter(|m| board.turns(player).any(|turn| turn == m.turn))
+                .copied()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns a turn from the book for the given board and player to move, or None if the
+    /// position is not in the book.
+    ///
+    /// Turns are chosen at random with probability proportional to their weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1, Player2};
+    /// let mut builder = bit_checkers::book::OpeningBookBuilder::new();
+    /// let turn = "11-15".parse().unwrap();
+    /// builder.add_line(Board::new(), Player1, &[turn]).unwrap();
+    /// let book = builder.build();
+    /// let mut rng = bit_checkers::checkers::util::Rng::new(1);
+    ///
+    /// assert!(book.choose(&Board::new(), Player1, &mut rng).is_some());
+    /// assert!(book.choose(&Board::new(), Player2, &mut rng).is_none());
+    /// ```
+    pub fn choose(&self, board: &Board, player: Player, rng: &mut Rng) -> Option<Turn> {
+        let moves = self.moves(board, player);
+        let total: u64 = moves.iter().map(|m| m.weight as u64).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut sample = rng.below(total);
+        for m in moves {
+            if sample < m.weight as u64 {
+                return Some(m.turn);
+            }
+            sample -= m.weight as u64;
+        }
+        None
+    }
+
+    /// Reads a book in the text format.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn read<R: BufRead>(reader: R) -> Result<OpeningBook, BookError> {
+        let mut book = OpeningBook::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let parsed = match fields[..] {
+                [hash, turn, weight] => u64::from_str_radix(hash, 16)
+                    .ok()
+                    .zip(turn.parse().ok())
+                    .zip(weight.parse().ok()),
+                _ => None,
+            };
+            let ((hash, turn), weight) = parsed.ok_or(BookError::Parse(i + 1))?;
+            book.positions
+                .entry(hash)
+                .or_default()
+                .push(BookMove { turn, weight });
+        }
+        Ok(book)
+    }
+
+    /// Writes the book in the text format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1};
+    /// let mut builder = bit_checkers::book::OpeningBookBuilder::new();
+    /// let turn = "11-15".parse().unwrap();
+    /// builder.add_line(Board::new(), Player1, &[turn]).unwrap();
+    /// let book = builder.build();
+    /// let mut text = Vec::new();
+    /// book.write(&mut text).unwrap();
+    /// let read_book = bit_checkers::book::OpeningBook::read(&text[..]).unwrap();
+    ///
+    /// assert_eq!(read_book.len(), book.len());
+    /// ```
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut hashes: Vec<&u64> = self.positions.keys().collect();
+        hashes.sort();
+        for hash in hashes {
+            for m in &self.positions[hash] {
+                writeln!(writer, "{:016x} {} {}", hash, m.turn, m.weight)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OpeningBookBuilder {
+    /// Returns a builder for an empty book.
+    pub fn new() -> OpeningBookBuilder {
+        OpeningBookBuilder {
+            positions: HashMap::new(),
+            max_plies: DEFAULT_MAX_PLIES,
+        }
+    }
+
+    /// Sets the maximum number of turns from the start of each line that are added to the book.
+    pub fn max_plies(mut self, max_plies: usize) -> OpeningBookBuilder {
+        self.max_plies = max_plies;
+        self
+    }
+
+    /// Adds a line of play starting from the given board and player to move.
+    ///
+    /// Returns an error if any of the turns is not legal, in which case the turns before it are
+    /// still added.
+    pub fn add_line(
+        &mut self,
+        mut board: Board,
+        mut player: Player,
+        turns: &[Turn],
+    ) -> Result<(), BookError> {
+        for (i, &turn) in turns.iter().enumerate().take(self.max_plies) {
+            if !board.turns(player).any(|legal| legal == turn) {
+                return Err(BookError::IllegalTurn(i, turn));
+            }
+            let moves = self
+                .positions
+                .entry(board.position_hash(player))
+                .or_default();
+            match moves.iter_mut().find(|m| m.turn == turn) {
+                Some(m) => m.weight += 1,
+                None => moves.push(BookMove { turn, weight: 1 }),
+            }
+            board = board.apply_turn(player, turn);
+            player = player.opponent();
+        }
+        Ok(())
+    }
+
+    /// Adds the main line of every game in a PDN collection, returning the number of games.
+    ///
+    /// Games are played from their starting position, which is the initial board unless the game
+    /// has a `FEN` tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1};
+    /// let mut builder = bit_checkers::book::OpeningBookBuilder::new();
+    /// let games = builder
+    ///     .add_pdn("1. 11-15 23-19 * 1. 11-15 22-17 * 1. 9-14 22-18 *")
+    ///     .unwrap();
+    /// let moves = builder.build().moves(&Board::new(), Player1);
+    ///
+    /// assert_eq!(games, 3);
+    /// assert_eq!(moves[0].turn.to_string(), "11-15");
+    /// assert_eq!(moves[0].weight, 2);
+    /// ```
+    pub fn add_pdn(&mut self, text: &str) -> Result<usize, BookError> {
+        let games = pdn::parse_games(text)?;
+        for game in &games {
+            let start = game.start()?;
+            self.add_line(start.board, start.to_move, &game.replay()?)?;
+        }
+        Ok(games.len())
+    }
+
+    /// Adds every opening of the three-move ballot.
+    pub fn add_ballots(&mut self) {
+        for ballot in Ballot::all() {
+            self.add_line(Board::new(), Player1, &ballot.turns)
+                .expect("ballot should be legal");
+        }
+    }
+
+    /// Returns the built opening book.
+    ///
+    /// The moves for each position are ordered from highest to lowest weight.
+    pub fn build(self) -> OpeningBook {
+        let mut positions = self.positions;
+        for moves in positions.values_mut() {
+            moves.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        }
+        OpeningBook { positions }
+    }
+}
+
+impl Default for OpeningBookBuilder {
+    fn default() -> OpeningBookBuilder {
+        OpeningBookBuilder::new()
+    }
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookError::Io(error) => write!(f, "{}", error),
+            BookError::Pdn(error) => write!(f, "{}", error),
+            BookError::Parse(line) => write!(f, "line {}: invalid book entry", line),
+            BookError::IllegalTurn(index, turn) => {
+                write!(f, "turn {} ({}) is not legal", index + 1, turn)
+            }
+        }
+    }
+}
+
+impl Error for BookError {}
+
+impl From<io::Error> for BookError {
+    fn from(error: io::Error) -> BookError {
+        BookError::Io(error)
+    }
+}
+
+impl From<PdnError> for BookError {
+    fn from(error: PdnError) -> BookError {
+        BookError::Pdn(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_choose_by_weight() {
+        let mut builder = OpeningBookBuilder::new();
+        let common: Turn = "11-15".parse().unwrap();
+        let rare: Turn = "9-14".parse().unwrap();
+        for _ in 0..9 {
+            builder.add_line(Board::new(), Player1, &[common]).unwrap();
+        }
+        builder.add_line(Board::new(), Player1, &[rare]).unwrap();
+        let book = builder.build();
+        let mut rng = Rng::new(3);
+
+        let chosen: Vec<Turn> = (0..1000)
+            .filter_map(|_| book.choose(&Board::new(), Player1, &mut rng))
+            .collect();
+        let rare_count = chosen.iter().filter(|&&turn| turn == rare).count();
+
+        assert_eq!(chosen.len(), 1000);
+        assert!(rare_count > 50 && rare_count < 150, "{}", rare_count);
+    }
+
+    #[test]
+    fn should_limit_plies() {
+        let mut builder = OpeningBookBuilder::new().max_plies(1);
+        let turns: Vec<Turn> = ["11-15", "23-19", "8-11"]
+            .iter()
+            .map(|t| t.parse().unwrap())
+            .collect();
+        builder.add_line(Board::new(), Player1, &turns).unwrap();
+
+        assert_eq!(builder.build().len(), 1);
+    }
+
+    #[test]
+    fn should_reject_illegal_lines() {
+        let mut builder = OpeningBookBuilder::new();
+        let turns: Vec<Turn> = ["11-15", "15-19"]
+            .iter()
+            .map(|t| t.parse().unwrap())
+            .collect();
+
+        assert!(matches!(
+            builder.add_line(Board::new(), Player1, &turns),
+            Err(BookError::IllegalTurn(1, _))
+        ));
+        assert!(matches!(
+            OpeningBook::read("0123 11-15".as_bytes()),
+            Err(BookError::Parse(1))
+        ));
+    }
+}
@@ -0,0 +1,448 @@
+use super::board::{Board, Piece, Player, Player1, Player2, Position};
+use super::engine::{Engine, SearchLimits, SearchResult, WIN_SCORE};
+use super::turn::Turn;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_double, c_int};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// Value of an empty square in a CheckerBoard board array.
+pub const CB_EMPTY: c_int = 0;
+/// Value of White (Player2), both as a colour and in a piece.
+pub const CB_WHITE: c_int = 1;
+/// Value of Black (Player1), both as a colour and in a piece.
+pub const CB_BLACK: c_int = 2;
+/// Flag set in the value of a man.
+pub const CB_MAN: c_int = 4;
+/// Flag set in the value of a king.
+pub const CB_KING: c_int = 8;
+
+/// Result returned by `getmove` when the engine expects a draw.
+pub const CB_DRAW: c_int = 0;
+/// Result returned by `getmove` when the engine has found a win.
+pub const CB_WIN: c_int = 1;
+/// Result returned by `getmove` when the engine has found a loss.
+pub const CB_LOSS: c_int = 2;
+/// Result returned by `getmove` when the result is not known.
+pub const CB_UNKNOWN: c_int = 3;
+
+/// Flag set in the `info` argument of `getmove` when the game was reset or changed, so previous
+/// search results should be discarded.
+const CB_RESET: c_int = 1;
+/// Game type reported to CheckerBoard, which is English checkers.
+const GAME_TYPE: &str = "21";
+/// Version of the engine interface that is implemented.
+const PROTOCOL_VERSION: &str = "2";
+/// Size of the `str` argument of `getmove` and the `reply` argument of `enginecommand`.
+const REPLY_SIZE: usize = 1024;
+/// Default size of the transposition table in megabytes.
+const DEFAULT_HASH_SIZE: usize = 64;
+/// How often the `playnow` flag is checked during a search.
+const PLAY_NOW_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Coordinates of a square in a CheckerBoard board array, with x from left to right and y from
+/// White's back row (y = 0) to Black's back row (y = 7).
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct CbCoordinates {
+    pub x: c_int,
+    pub y: c_int,
+}
+
+/// Move returned to CheckerBoard by `getmove`, matching CheckerBoard's `struct CBmove`.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct CbMove {
+    /// Number of pieces captured.
+    pub jumps: c_int,
+    /// Value of the moving piece after the move, which is different if it was crowned.
+    pub newpiece: c_int,
+    /// Value of the moving piece before the move.
+    pub oldpiece: c_int,
+    pub from: CbCoordinates,
+    pub to: CbCoordinates,
+    /// Squares visited by the moving piece, starting with `from` and not including `to`.
+    pub path: [CbCoordinates; 12],
+    /// Squares of the captured pieces.
+    pub del: [CbCoordinates; 12],
+    /// Values of the captured pieces.
+    pub delpiece: [c_int; 12],
+}
+
+/// State of the engine shared between calls from CheckerBoard.
+struct State {
+    engine: Engine,
+    hash_size: usize,
+}
+
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+
+/// Chooses a move for the given colour, as called by CheckerBoard.
+///
+/// The board is updated to the position after the move, the move is written to `cbmove` and a
+/// description of the search is written to `status`. Searches for about `maxtime` seconds, or
+/// until the value pointed to by `playnow` is set. Returns `CB_WIN`, `CB_LOSS` or `CB_UNKNOWN`
+/// depending on the result of the search, or `CB_LOSS` without a move if there are no legal
+/// moves.
+///
+/// # Safety
+///
+/// `board` must point to an 8 by 8 array, `status` to a buffer of 1024 bytes and `cbmove` to a
+/// `CbMove`. `playnow` must be null or point to an integer that stays valid during the call.
+#[no_mangle]
+pub unsafe extern "system" fn getmove(
+    board: *mut [[c_int; 8]; 8],
+    color: c_int,
+    maxtime: c_double,
+    status: *mut c_char,
+    playnow: *const c_int,
+    info: c_int,
+    _moreinfo: c_int,
+    cbmove: *mut CbMove,
+) -> c_int {
+    let player = if color == CB_BLACK { Player1 } else { Player2 };
+    let position = match from_cb_board(&*board) {
+        Some(position) => position,
+        None => {
+            write_reply(status, "invalid board");
+            return CB_UNKNOWN;
+        }
+    };
+    let mut turns = position.turns(player);
+    let (turn, result) = match (turns.next(), turns.next()) {
+        (None, _) => {
+            write_reply(status, "no legal moves");
+            return CB_LOSS;
+        }
+        (Some(turn), None) => {
+            write_reply(status, "only move");
+            (turn, CB_UNKNOWN)
+        }
+        _ => {
+            let mut state = STATE.lock().unwrap_or_else(|error| error.into_inner());
+            let state = state.get_or_insert_with(State::new);
+            if info & CB_RESET != 0 {
+                state.engine.clear_hash();
+            }
+            let limits = SearchLimits::time(Duration::from_secs_f64(maxtime.max(0.0)));
+            let result = search(&mut state.engine, &position, player, limits, playnow);
+            let pv: Vec<String> = result.pv.iter().map(Turn::to_string).collect();
+            write_reply(
+                status,
+                &format!(
+                    "depth {} score {} nodes {} pv {}",
+                    result.depth,
+                    result.score,
+                    result.nodes,
+                    pv.join(" ")
+                ),
+            );
+            let code = match result.score {
+                score if score > WIN_SCORE / 2 => CB_WIN,
+                score if score < -WIN_SCORE / 2 => CB_LOSS,
+                _ => CB_UNKNOWN,
+            };
+            (result.turn.expect("player should have a legal turn"), code)
+        }
+    };
+    *cbmove = cb_move(&position, player, turn);
+    *board = to_cb_board(&position.apply_turn(player, turn));
+    result
+}
+
+/// Handles a command from CheckerBoard, writing the reply to `reply`.
+///
+/// Supports `name`, `about`, `get protocolversion`, `get gametype`, `get hashsize` and
+/// `set hashsize <megabytes>`. Returns 1 if the command was handled, or 0 otherwise.
+///
+/// # Safety
+///
+/// `command` must point to a null terminated string and `reply` to a buffer of 1024 bytes.
+#[no_mangle]
+pub unsafe extern "system" fn enginecommand(command: *const c_char, reply: *mut c_char) -> c_int {
+    let command = CStr::from_ptr(command).to_string_lossy();
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let mut state = STATE.lock().unwrap_or_else(|error| error.into_inner());
+    let text = match words.as_slice() {
+        ["name"] => "bit_checkers".to_string(),
+        ["about"] => format!(
+            "bit_checkers {}\nA bitboard checkers engine.",
+            env!("CARGO_PKG_VERSION")
+        ),
+        ["get", "protocolversion"] => PROTOCOL_VERSION.to_string(),
+        ["get", "gametype"] => GAME_TYPE.to_string(),
+        ["get", "hashsize"] => state
+            .as_ref()
+            .map_or(DEFAULT_HASH_SIZE, |state| state.hash_size)
+            .to_string(),
+        ["set", "hashsize", size] => match size.parse::<usize>() {
+            Ok(size) if size > 0 => {
+                *state = Some(State {
+                    engine: Engine::new().with_hash_size(size),
+                    hash_size: size,
+                });
+                size.to_string()
+            }
+            _ => {
+                write_reply(reply, "?");
+                return 0;
+            }
+        },
+        _ => {
+            write_reply(reply, "?");
+            return 0;
+        }
+    };
+    write_reply(reply, &text);
+    1
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            engine: Engine::new().with_hash_size(DEFAULT_HASH_SIZE),
+            hash_size: DEFAULT_HASH_SIZE,
+        }
+    }
+}
+
+/// Searches the position, stopping early if the value pointed to by `playnow` is set.
+unsafe fn search(
+    engine: &mut Engine,
+    board: &Board,
+    player: Player,
+    limits: SearchLimits,
+    playnow: *const c_int,
+) -> SearchResult {
+    if playnow.is_null() {
+        return engine.search(board, player, limits);
+    }
+    let stop = engine.stop_handle();
+    let finished = AtomicBool::new(false);
+    // Raw pointers can't be sent between threads, but the pointer stays valid until this
+    // function returns, which waits for the scoped thread to finish
+    let playnow = playnow as usize;
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            while !finished.load(Ordering::Relaxed) {
+                if ptr::read_volatile(playnow as *const c_int) != 0 {
+                    stop.stop();
+                    break;
+                }
+                thread::sleep(PLAY_NOW_INTERVAL);
+            }
+        });
+        let result = engine.search(board, player, limits);
+        finished.store(true, Ordering::Relaxed);
+        result
+    })
+}
+
+/// Returns the position of a square in a CheckerBoard board array.
+fn position(CbCoordinates { x, y }: CbCoordinates) -> Position {
+    Position(x as u32, 7 - y as u32)
+}
+
+/// Returns the coordinates in a CheckerBoard board array of a position.
+fn coordinates(Position(x, y): Position) -> CbCoordinates {
+    CbCoordinates {
+        x: x as c_int,
+        y: 7 - y as c_int,
+    }
+}
+
+/// Returns the CheckerBoard value of a piece.
+fn piece_value(player: Player, king: bool) -> c_int {
+    let color = match player {
+        Player1 => CB_BLACK,
+        Player2 => CB_WHITE,
+    };
+    color | if king { CB_KING } else { CB_MAN }
+}
+
+/// Returns the board for a CheckerBoard board array, or None if it is not a valid position.
+fn from_cb_board(cb_board: &[[c_int; 8]; 8]) -> Option<Board> {
+    let mut pieces = Vec::new();
+    for (x, column) in cb_board.iter().enumerate() {
+        for (y, &value) in column.iter().enumerate() {
+            let player = match value & (CB_WHITE | CB_BLACK) {
+                _ if value == CB_EMPTY => continue,
+                CB_BLACK => Player1,
+                CB_WHITE => Player2,
+                _ => return None,
+            };
+            pieces.push(Piece {
+                position: position(CbCoordinates {
+                    x: x as c_int,
+                    y: y as c_int,
+                }),
+                player,
+                king: value & CB_KING != 0,
+            });
+        }
+    }
+    Board::try_new_with_pieces(pieces).ok()
+}
+
+/// Returns the CheckerBoard board array for a board.
+fn to_cb_board(board: &Board) -> [[c_int; 8]; 8] {
+    let mut cb_board = [[CB_EMPTY; 8]; 8];
+    for piece in board.clone().pieces_iter() {
+        let CbCoordinates { x, y } = coordinates(piece.position);
+        cb_board[x as usize][y as usize] = piece_value(piece.player, piece.king);
+    }
+    cb_board
+}
+
+/// Returns the CheckerBoard description of a turn made by the player on the board.
+fn cb_move(board: &Board, player: Player, turn: Turn) -> CbMove {
+    let moving = board
+        .piece_at(turn.from())
+        .expect("turn should start at a piece");
+    let after = board.clone().apply_turn(player, turn);
+    let crowned = after
+        .piece_at(turn.to())
+        .expect("turn should end at a piece")
+        .king;
+    let mut cb_move = CbMove {
+        oldpiece: piece_value(player, moving.king),
+        newpiece: piece_value(player, crowned),
+        from: coordinates(turn.from()),
+        to: coordinates(turn.to()),
+        ..CbMove::default()
+    };
+    if turn.is_jump() {
+        for (i, a_move) in turn.moves().enumerate() {
+            let (from, to) = (a_move.from, a_move.to);
+            let captured = Position((from.0 + to.0) / 2, (from.1 + to.1) / 2);
+            let piece = board
+                .piece_at(captured)
+                .expect("jump should capture a piece");
+            cb_move.path[i] = coordinates(from);
+            cb_move.del[i] = coordinates(captured);
+            cb_move.delpiece[i] = piece_value(piece.player, piece.king);
+            cb_move.jumps += 1;
+        }
+    } else {
+        cb_move.path[0] = coordinates(turn.from());
+    }
+    cb_move
+}
+
+/// Writes text to a reply buffer as a null terminated string, truncating it if necessary.
+unsafe fn write_reply(buffer: *mut c_char, text: &str) {
+    let len = text.len().min(REPLY_SIZE - 1);
+    ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, buffer, len);
+    *buffer.add(len) = 0;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn reply(buffer: &[c_char]) -> String {
+        unsafe { CStr::from_ptr(buffer.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn should_convert_boards() {
+        let board = Board::new().apply_turn(Player1, "11-15".parse().unwrap());
+        let cb_board = to_cb_board(&board);
+
+        assert_eq!(cb_board[0][0], CB_WHITE | CB_MAN);
+        assert_eq!(cb_board[1][7], CB_BLACK | CB_MAN);
+        assert_eq!(cb_board[4][4], CB_BLACK | CB_MAN);
+        assert_eq!(from_cb_board(&cb_board), Some(board));
+        assert_eq!(from_cb_board(&[[CB_BLACK | CB_MAN; 8]; 8]), None);
+    }
+
+    #[test]
+    fn should_get_move_with_captures() {
+        let mut board = to_cb_board(&crate::board![
+            _  _  _  _;
+          _  _  _  _;
+            _  _  1  _;
+          _  _  2  _;
+            _  _  _  _;
+          _  _  2  _;
+            _  _  _  _;
+          _  _  _  2
+        ]);
+        let mut status: [c_char; REPLY_SIZE] = [0; REPLY_SIZE];
+        let mut cb_move = CbMove::default();
+        let playnow = 0;
+        let result = unsafe {
+            getmove(
+                &mut board,
+                CB_BLACK,
+                0.1,
+                status.as_mut_ptr(),
+                &playnow,
+                0,
+                0,
+                &mut cb_move,
+            )
+        };
+
+        assert_eq!(result, CB_UNKNOWN);
+        assert_eq!(reply(&status), "only move");
+        assert_eq!(cb_move.jumps, 2);
+        assert_eq!(cb_move.from, CbCoordinates { x: 5, y: 5 });
+        assert_eq!(cb_move.to, CbCoordinates { x: 5, y: 1 });
+        assert_eq!(cb_move.path[1], CbCoordinates { x: 3, y: 3 });
+        assert_eq!(cb_move.del[1], CbCoordinates { x: 4, y: 2 });
+        assert_eq!(cb_move.delpiece[1], CB_WHITE | CB_MAN);
+        assert_eq!(board[5][1], CB_BLACK | CB_MAN);
+        assert_eq!(board[4][4], CB_EMPTY);
+    }
+
+    #[test]
+    fn should_search_for_move() {
+        let mut board = to_cb_board(&Board::new());
+        let mut status: [c_char; REPLY_SIZE] = [0; REPLY_SIZE];
+        let mut cb_move = CbMove::default();
+        let playnow = 0;
+        let result = unsafe {
+            getmove(
+                &mut board,
+                CB_WHITE,
+                0.05,
+                status.as_mut_ptr(),
+                &playnow,
+                CB_RESET,
+                0,
+                &mut cb_move,
+            )
+        };
+
+        assert_eq!(result, CB_UNKNOWN);
+        assert!(reply(&status).starts_with("depth "));
+        assert_eq!(cb_move.jumps, 0);
+        assert_eq!(cb_move.from.y, 2);
+        assert_eq!(cb_move.oldpiece, CB_WHITE | CB_MAN);
+        assert_eq!(board[cb_move.to.x as usize][3], CB_WHITE | CB_MAN);
+    }
+
+    #[test]
+    fn should_handle_engine_commands() {
+        let mut buffer: [c_char; REPLY_SIZE] = [0; REPLY_SIZE];
+        let mut command = |text: &str| {
+            let text = std::ffi::CString::new(text).unwrap();
+            let handled = unsafe { enginecommand(text.as_ptr(), buffer.as_mut_ptr()) };
+            (handled, reply(&buffer))
+        };
+
+        assert_eq!(command("name"), (1, "bit_checkers".to_string()));
+        assert_eq!(command("get gametype"), (1, "21".to_string()));
+        assert_eq!(command("get protocolversion"), (1, "2".to_string()));
+        assert_eq!(command("set hashsize 8"), (1, "8".to_string()));
+        assert_eq!(command("get hashsize"), (1, "8".to_string()));
+        assert_eq!(command("set hashsize x"), (0, "?".to_string()));
+        assert_eq!(command("foo"), (0, "?".to_string()));
+    }
+}
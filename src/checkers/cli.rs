@@ -0,0 +1,620 @@
+pub use super::args::UsageError;
+
+use super::args::number;
+use super::board::{Player, Player1, Player2};
+use super::engine::{Engine, SearchLimits};
+use super::fen::Fen;
+use super::game::{Game, Outcome, DRAW_TURNS};
+use super::pdn::{self, PdnGame};
+use super::render::{Labels, Renderer};
+use super::turn::Turn;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Depth searched by the engine when neither a depth nor a time is given.
+const DEFAULT_DEPTH: u32 = 6;
+
+/// Description of the command line arguments accepted by `Options::from_args`.
+pub const USAGE: &str = "\
+Usage: bit_checkers [options]
+
+Options:
+  --black human|engine   who plays Black, who moves first (default human)
+  --white human|engine   who plays White (default engine)
+  --depth N              depth searched by the engine (default 6)
+  --time SECONDS         time the engine spends on each move, instead of a fixed depth
+  --variant NAME         rules to play by, only english (American checkers) is supported
+  --fen FEN              starting position, e.g. B:W21-32:B1-12
+  --load FILE            continue the first game in a PDN file
+  --hints                list the legal moves before each human move
+  --multipv N            number of best moves shown by the hint command (default 1)
+  --unicode              draw pieces with Unicode glyphs
+  --color                colour the board with ANSI escape codes
+  --tui                  play in a full-screen terminal UI with mouse support, if built with
+                         the tui feature
+  --help                 show this message";
+
+/// Text shown by the `help` command.
+const HELP: &str = "\
+Enter a move in standard notation, e.g. 11-15 or 15x24. A multi-jump can be given by its
+first and last squares if there is only one way to make it.
+
+Commands:
+  moves        list the legal moves
+  hint         ask the engine for the best moves
+  undo         take back your last move
+  resign       resign the game
+  new          start a new game from the starting position
+  save FILE    save the game as PDN
+  load FILE    load the first game in a PDN file
+  help         show this message
+  quit         leave the game";
+
+/// Type representing who chooses the turns for a player.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Controller {
+    /// Turns are entered at the terminal.
+    Human,
+    /// Turns are chosen by the engine.
+    Engine,
+}
+
+/// Options for a game played in the terminal.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Options {
+    pub player1: Controller,
+    pub player2: Controller,
+    /// Limits for the engine's searches.
+    pub limits: SearchLimits,
+    /// Position the game starts from, and returns to for a new game.
+    pub start: Fen,
+    /// PDN file containing a game to continue.
+    pub load: Option<PathBuf>,
+    /// Whether the legal moves are listed before each human turn.
+    pub hints: bool,
+    /// Number of best turns shown by the `hint` command.
+    pub multi_pv: usize,
+    pub unicode: bool,
+    pub colors: bool,
+    /// Whether to play in the full-screen terminal UI instead of at a prompt.
+    pub tui: bool,
+}
+
+/// How a game ended, including ways that `Game` doesn't track.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Ending {
+    Outcome(Outcome),
+    Resigned(Player),
+}
+
+/// State of a game being played in the terminal.
+struct Session<R, W> {
+    options: Options,
+    input: R,
+    output: W,
+    engine: Engine,
+    start: Fen,
+    game: Game,
+    ending: Option<Ending>,
+}
+
+impl Options {
+    /// Returns options parsed from command line arguments, not including the program name. See
+    /// `USAGE` for the arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::cli::{Controller, Options};
+    /// let args = ["--white", "human", "--depth", "4"].iter().map(|arg| arg.to_string());
+    /// let options = Options::from_args(args).unwrap();
+    ///
+    /// assert_eq!(options.player2, Controller::Human);
+    /// assert_eq!(options.limits.depth, Some(4));
+    /// ```
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, UsageError> {
+        let mut options = Options::default();
+        let (mut depth, mut time) = (None, None);
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| UsageError(format!("{} needs a value", arg)))
+            };
+            match arg.as_str() {
+                "--black" => options.player1 = controller(&value()?)?,
+                "--white" => options.player2 = controller(&value()?)?,
+                "--depth" => depth = Some(number(&arg, &value()?)?),
+                "--time" => {
+                    let value = value()?;
+                    time = Some(Duration::try_from_secs_f64(number(&arg, &value)?).map_err(
+                        |_| UsageError(format!("invalid value \"{}\" for {}", value, arg)),
+                    )?)
+                }
+                "--variant" => match value()?.as_str() {
+                    "english" | "american" => (),
+                    variant => {
+                        return Err(UsageError(format!(
+                            "unsupported variant \"{}\", only english is supported",
+                            variant
+                        )))
+                    }
+                },
+                "--fen" => {
+                    options.start = value()?
+                        .parse()
+                        .map_err(|error| UsageError(format!("{}", error)))?
+                }
+                "--load" => options.load = Some(PathBuf::from(value()?)),
+                "--hints" => options.hints = true,
+                "--multipv" => match number(&arg, &value()?)? {
+                    0 => return Err(UsageError("--multipv must be at least 1".to_string())),
+                    lines => options.multi_pv = lines,
+                },
+                "--unicode" => options.unicode = true,
+                "--color" => options.colors = true,
+                "--tui" if cfg!(feature = "tui") => options.tui = true,
+                "--tui" => return Err(UsageError("--tui needs the tui feature".to_string())),
+                _ => return Err(UsageError(format!("unknown option \"{}\"", arg))),
+            }
+        }
+        options.limits = match (depth, time) {
+            (None, None) => SearchLimits::depth(DEFAULT_DEPTH),
+            (depth, time) => SearchLimits {
+                depth,
+                time,
+                ..SearchLimits::default()
+            },
+        };
+        Ok(options)
+    }
+
+    /// Returns who chooses the turns for the given player.
+    pub(crate) fn controller(&self, player: Player) -> Controller {
+        match player {
+            Player1 => self.player1,
+            Player2 => self.player2,
+        }
+    }
+}
+
+impl Default for Options {
+    /// Returns options for a human playing Black against the engine from the initial position.
+    fn default() -> Options {
+        Options {
+            player1: Controller::Human,
+            player2: Controller::Engine,
+            limits: SearchLimits::depth(DEFAULT_DEPTH),
+            start: Fen::new(),
+            load: None,
+            hints: false,
+            multi_pv: 1,
+            unicode: false,
+            colors: false,
+            tui: false,
+        }
+    }
+}
+
+/// Plays a game in the terminal, reading commands from input and writing the board and messages
+/// to output. Returns when the player quits, the input ends, or an engine game finishes.
+pub fn run<R: BufRead, W: Write>(options: Options, input: R, output: W) -> io::Result<()> {
+    let start = options.start.clone();
+    let mut session = Session {
+        game: replay(&start, &[]),
+        start,
+        options,
+        input,
+        output,
+        engine: Engine::new(),
+        ending: None,
+    };
+    if let Some(path) = session.options.load.clone() {
+        session.load(&path.to_string_lossy())?;
+    }
+    session.play()
+}
+
+impl<R: BufRead, W: Write> Session<R, W> {
+    /// Plays turns until the game is left.
+    fn play(&mut self) -> io::Result<()> {
+        self.show_board()?;
+        loop {
+            let ending = self
+                .ending
+                .or_else(|| self.game.outcome().map(Ending::Outcome));
+            let human = self.options.player1 == Controller::Human
+                || self.options.player2 == Controller::Human;
+            match ending {
+                Some(_) if !human => return Ok(()),
+                None if self.options.controller(self.game.to_move()) == Controller::Engine => {
+                    self.play_engine_turn()?;
+                    continue;
+                }
+                _ => (),
+            }
+
+            if ending.is_none() && self.options.hints {
+                self.show_moves()?;
+            }
+            let prompt = match ending {
+                Some(_) => "game over".to_string(),
+                None => format!("{} to move", name(self.game.to_move())),
+            };
+            write!(self.output, "{}> ", prompt)?;
+            self.output.flush()?;
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            if !self.handle(line.trim())? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handles a command or move entered at the terminal, returning whether to keep playing.
+    fn handle(&mut self, line: &str) -> io::Result<bool> {
+        let (command, argument) = match line.find(char::is_whitespace) {
+            Some(index) => (&line[..index], line[index..].trim()),
+            None => (line, ""),
+        };
+        match (command, argument) {
+            ("", _) => (),
+            ("quit", _) | ("exit", _) => return Ok(false),
+            ("help", _) | ("?", _) => writeln!(self.output, "{}", HELP)?,
+            ("moves", _) => self.show_moves()?,
+            ("hint", _) if self.ending.is_none() => self.show_hint()?,
+            ("undo", _) => self.undo()?,
+            ("resign", _) if self.ending.is_none() => {
+                let player = self.game.to_move();
+                self.ending = Some(Ending::Resigned(player));
+                writeln!(self.output, "{} resigns.", name(player))?;
+                self.show_ending()?;
+            }
+            ("new", _) => {
+                self.game = replay(&self.start, &[]);
+                self.ending = None;
+                self.show_board()?;
+            }
+            ("save", path) if !path.is_empty() => {
+                match fs::write(path, self.record().to_string()) {
+                    Ok(()) => writeln!(self.output, "Saved the game to {}.", path)?,
+                    Err(error) => writeln!(self.output, "Could not save the game: {}", error)?,
+                }
+            }
+            ("load", path) if !path.is_empty() => self.load(path)?,
+            ("save", _) | ("load", _) => writeln!(self.output, "Give the name of a PDN file.")?,
+            _ if self.ending.is_some() || self.game.outcome().is_some() => {
+                writeln!(self.output, "The game is over, enter undo, new or quit.")?
+            }
+            _ => self.play_human_turn(line)?,
+        }
+        Ok(true)
+    }
+
+    /// Plays a turn entered in standard notation.
+    fn play_human_turn(&mut self, notation: &str) -> io::Result<()> {
+        let turn = notation
+            .parse::<Turn>()
+            .ok()
+            .and_then(|turn| self.game.board().find_turn(self.game.to_move(), &turn));
+        match turn {
+            Some(turn) => self.play_turn(turn),
+            None => writeln!(
+                self.output,
+                "\"{}\" is not a legal move, enter moves to list them or help for commands.",
+                notation
+            ),
+        }
+    }
+
+    /// Plays the engine's choice of turn.
+    fn play_engine_turn(&mut self) -> io::Result<()> {
+        let (board, player) = (self.game.board(), self.game.to_move());
+        let turn = self
+            .engine
+            .search(board, player, self.options.limits)
+            .turn
+            .expect("player to move should have a legal turn");
+        writeln!(self.output, "{} plays {}.", name(player), turn)?;
+        self.play_turn(turn)
+    }
+
+    fn play_turn(&mut self, turn: Turn) -> io::Result<()> {
+        self.game
+            .play(turn)
+            .expect("turn should be legal in a game in progress");
+        self.show_board()?;
+        if let Some(outcome) = self.game.outcome() {
+            self.ending = self.ending.or(Some(Ending::Outcome(outcome)));
+            self.show_ending()?;
+        }
+        Ok(())
+    }
+
+    /// Takes back turns until it is a human's turn again, at least one turn earlier.
+    fn undo(&mut self) -> io::Result<()> {
+        let mut turns = self.game.turns().to_vec();
+        if turns.is_empty() {
+            return writeln!(self.output, "There are no moves to take back.");
+        }
+        turns.pop();
+        let mut to_move = self.game.to_move().opponent();
+        while !turns.is_empty() && self.options.controller(to_move) == Controller::Engine {
+            turns.pop();
+            to_move = to_move.opponent();
+        }
+        self.game = replay(&self.start, &turns);
+        self.ending = None;
+        self.show_board()
+    }
+
+    /// Loads the first game in a PDN file, keeping the current game if it can't be read.
+    fn load(&mut self, path: &str) -> io::Result<()> {
+        match load_game(Path::new(path)) {
+            Ok((start, turns)) => {
+                self.game = replay(&start, &turns);
+                self.start = start;
+                self.ending = None;
+                writeln!(self.output, "Loaded {} moves from {}.", turns.len(), path)?;
+                self.show_board()
+            }
+            Err(error) => writeln!(self.output, "Could not load {}: {}", path, error),
+        }
+    }
+
+    /// Returns the game as PDN, with the starting position if it isn't the initial one.
+    fn record(&self) -> PdnGame {
+        let mut record = PdnGame::from_game(&self.game);
+        let result = match self.ending {
+            Some(Ending::Resigned(Player1)) => Some("0-1"),
+            Some(Ending::Resigned(Player2)) => Some("1-0"),
+            _ => None,
+        };
+        if let Some(result) = result {
+            record.tags = vec![("Result".to_string(), result.to_string())];
+            record.result = Some(result.to_string());
+        }
+        let mut tags = vec![
+            ("Black".to_string(), self.player_name(Player1).to_string()),
+            ("White".to_string(), self.player_name(Player2).to_string()),
+        ];
+        if self.start != Fen::new() {
+            tags.push(("FEN".to_string(), self.start.to_string()));
+        }
+        record.tags.splice(0..0, tags);
+        record
+    }
+
+    fn player_name(&self, player: Player) -> &'static str {
+        match self.options.controller(player) {
+            Controller::Human => "Human",
+            Controller::Engine => "bit_checkers",
+        }
+    }
+
+    fn show_board(&mut self) -> io::Result<()> {
+        let perspective = match (self.options.player1, self.options.player2) {
+            (Controller::Human, Controller::Engine) => Player1,
+            _ => Player2,
+        };
+        let mut renderer = Renderer::new()
+            .labels(Labels::Squares)
+            .unicode(self.options.unicode)
+            .colors(self.options.colors)
+            .perspective(perspective);
+        if let Some(&turn) = self.game.turns().last() {
+            renderer = renderer.highlight(turn);
+        }
+        writeln!(self.output)?;
+        write!(self.output, "{}", renderer.render(self.game.board()))
+    }
+
+    /// Shows the engine's best turns, with their scores and principal variations if more than one
+    /// is asked for.
+    fn show_hint(&mut self) -> io::Result<()> {
+        let (board, player) = (self.game.board(), self.game.to_move());
+        let (limits, lines) = (self.options.limits, self.options.multi_pv);
+        let results = self.engine.analyse(board, player, limits, lines, |_| ());
+        match results.as_slice() {
+            [] => Ok(()),
+            [result] => match result.turn {
+                Some(turn) => writeln!(self.output, "Hint: {}", turn),
+                None => Ok(()),
+            },
+            results => {
+                writeln!(self.output, "Best moves:")?;
+                for (rank, result) in results.iter().enumerate() {
+                    let pv: Vec<String> = result.pv.iter().map(Turn::to_string).collect();
+                    writeln!(
+                        self.output,
+                        "{:>3}. {:+.2}  {}",
+                        rank + 1,
+                        result.score as f64 / 100.0,
+                        pv.join(" ")
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn show_moves(&mut self) -> io::Result<()> {
+        let moves: Vec<String> = self
+            .game
+            .legal_turns()
+            .map(|turn| turn.to_string())
+            .collect();
+        writeln!(self.output, "Legal moves: {}", moves.join(" "))
+    }
+
+    fn show_ending(&mut self) -> io::Result<()> {
+        let text = match self.ending {
+            Some(Ending::Outcome(Outcome::Win(player))) => {
+                format!(
+                    "{} wins, {} can't move.",
+                    name(player),
+                    name(player.opponent())
+                )
+            }
+            Some(Ending::Outcome(Outcome::Draw)) => {
+                format!("The game is drawn after {} moves.", DRAW_TURNS)
+            }
+            Some(Ending::Resigned(player)) => format!("{} wins.", name(player.opponent())),
+            None => return Ok(()),
+        };
+        writeln!(self.output, "{}", text)
+    }
+}
+
+/// Returns the starting position and turns of the first game in a PDN file.
+pub(crate) fn load_game(path: &Path) -> Result<(Fen, Vec<Turn>), String> {
+    let text = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let games = pdn::parse_games(&text).map_err(|error| error.to_string())?;
+    let game = games.first().ok_or("the file has no games")?;
+    let start = game.start().map_err(|error| error.to_string())?;
+    let turns = game.replay().map_err(|error| error.to_string())?;
+    Ok((start, turns))
+}
+
+/// Returns a game with the given turns played from a starting position, which is drawn after
+/// `DRAW_TURNS` turns.
+pub(crate) fn replay(start: &Fen, turns: &[Turn]) -> Game {
+    let mut game = Game::from_position(start.board.clone(), start.to_move);
+    for &turn in turns {
+        game.play(turn).expect("turns should be legal");
+    }
+    game.with_turn_limit(DRAW_TURNS)
+}
+
+/// Returns the name of a player's colour.
+pub(crate) fn name(player: Player) -> &'static str {
+    match player {
+        Player1 => "Black",
+        Player2 => "White",
+    }
+}
+
+fn controller(value: &str) -> Result<Controller, UsageError> {
+    match value {
+        "human" => Ok(Controller::Human),
+        "engine" => Ok(Controller::Engine),
+        _ => Err(UsageError(format!(
+            "expected human or engine but found \"{}\"",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run_lines(options: Options, input: &str) -> String {
+        let mut output = Vec::new();
+        run(options, input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn human_game() -> Options {
+        Options {
+            player2: Controller::Human,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn should_parse_args() {
+        let args = |args: &[&str]| Options::from_args(args.iter().map(|arg| arg.to_string()));
+        let options = args(&["--black", "engine", "--time", "0.5", "--fen", "W:W21:B1"]).unwrap();
+
+        assert_eq!(options.player1, Controller::Engine);
+        assert_eq!(options.limits.time, Some(Duration::from_millis(500)));
+        assert_eq!(options.limits.depth, None);
+        assert_eq!(options.start.to_move, Player2);
+        assert_eq!(args(&[]), Ok(Options::default()));
+        assert!(args(&["--variant", "english"]).is_ok());
+        assert!(args(&["--variant", "international"]).is_err());
+        assert!(args(&["--depth"]).is_err());
+        assert!(args(&["--time", "-1"]).is_err());
+        assert!(args(&["--time", "1e30"]).is_err());
+        assert!(args(&["--white", "robot"]).is_err());
+        assert_eq!(args(&["--multipv", "3"]).unwrap().multi_pv, 3);
+        assert!(args(&["--multipv", "0"]).is_err());
+    }
+
+    #[test]
+    fn should_play_moves_and_undo() {
+        let output = run_lines(
+            human_game(),
+            "11-15\n11-15\n23-19\nundo\nmoves\nresign\n12-16\nquit\n",
+        );
+
+        assert!(output.contains("\"11-15\" is not a legal move"));
+        assert!(output.contains("White to move> "));
+        assert!(output.contains("Legal moves: 22-17 23-18 24-19 21-17 22-18 23-19 24-20"));
+        assert!(output.contains("White resigns.\nBlack wins."));
+        assert!(output.contains("The game is over"));
+    }
+
+    #[test]
+    fn should_show_best_moves() {
+        let options = Options {
+            multi_pv: 3,
+            limits: SearchLimits::depth(2),
+            ..human_game()
+        };
+        let output = run_lines(options, "hint\n");
+
+        assert!(output.contains("Best moves:\n  1. "));
+        assert!(output.contains("\n  3. "));
+        assert!(!output.contains("\n  4. "));
+    }
+
+    #[test]
+    fn should_undo_engine_turns() {
+        let output = run_lines(Options::default(), "11-15\nundo\n");
+        let prompts = output.matches("Black to move> ").count();
+
+        assert!(output.contains("White plays "));
+        assert_eq!(prompts, 3);
+        assert!(output.ends_with("Black to move> "));
+    }
+
+    #[test]
+    fn should_play_engine_game() {
+        let options = Options {
+            player1: Controller::Engine,
+            limits: SearchLimits::depth(2),
+            start: "B:W18,27:B14".parse().unwrap(),
+            ..Options::default()
+        };
+        let output = run_lines(options, "");
+
+        assert!(output.contains("Black plays 14x23x32."));
+        assert!(output.contains("Black wins, White can't move."));
+    }
+
+    #[test]
+    fn should_save_and_load_games() {
+        let path =
+            std::env::temp_dir().join(format!("bit_checkers_cli_{}.pdn", std::process::id()));
+        let path = path.to_string_lossy();
+        let options = Options {
+            start: "W:W21,22:B1".parse().unwrap(),
+            ..human_game()
+        };
+        run_lines(options, &format!("21-17\n1-5\nsave {}\n", path));
+        let saved = fs::read_to_string(path.as_ref()).unwrap();
+        let output = run_lines(human_game(), &format!("load {}\nundo\nmoves\n", path));
+        fs::remove_file(path.as_ref()).unwrap();
+
+        assert!(saved.contains("[FEN \"W:W21,22:B1\"]"));
+        assert!(saved.contains("1... 21-17 2. 1-5 *"));
+        assert!(output.contains("Loaded 2 moves"));
+        assert!(output.contains("Legal moves: 1-5 1-6"));
+    }
+}
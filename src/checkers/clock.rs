@@ -0,0 +1,249 @@
+use super::board::{Player, Player1, Player2};
+use std::time::{Duration, Instant};
+
+/// Type representing the rules for how much time each player has.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TimeControl {
+    /// Each player has a fixed amount of time for the whole game.
+    SuddenDeath { time: Duration },
+    /// An increment is added to the player's time after each of their turns.
+    Fischer { time: Duration, increment: Duration },
+    /// Time used during each turn, up to the delay, is given back to the player after the turn.
+    Bronstein { time: Duration, delay: Duration },
+    /// The player must make a number of turns within each period. The time for the next period is
+    /// added when the last turn of a period is made, and unused time carries over. With zero moves
+    /// per period no time is ever added.
+    MovesPerPeriod { time: Duration, moves: u32 },
+}
+
+/// Type representing a game clock for both players.
+///
+/// The clock can either be driven in real time with `start` and `stop`, or by recording the time
+/// used for each turn with `record`.
+#[derive(Clone, Debug)]
+pub struct Clock {
+    control: TimeControl,
+    remaining: [Duration; 2],
+    turns: [u32; 2],
+    running: Option<(Player, Instant)>,
+}
+
+impl TimeControl {
+    /// Returns the time each player starts with.
+    pub fn initial_time(self) -> Duration {
+        match self {
+            TimeControl::SuddenDeath { time }
+            | TimeControl::Fischer { time, .. }
+            | TimeControl::Bronstein { time, .. }
+            | TimeControl::MovesPerPeriod { time, .. } => time,
+        }
+    }
+}
+
+impl Clock {
+    /// Returns a stopped clock with both players having the initial time for the time control.
+    pub fn new(control: TimeControl) -> Clock {
+        let time = control.initial_time();
+        Clock {
+            control,
+            remaining: [time, time],
+            turns: [0, 0],
+            running: None,
+        }
+    }
+
+    /// Returns the time control used by the clock.
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Returns the time remaining for the given player, including time used by the current turn
+    /// if the player's clock is running.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Player1;
+    /// use bit_checkers::clock::{Clock, TimeControl};
+    /// use std::time::Duration;
+    /// let mut clock = Clock::new(TimeControl::SuddenDeath {
+    ///     time: Duration::from_secs(60),
+    /// });
+    /// clock.record(Player1, Duration::from_secs(15));
+    ///
+    /// assert_eq!(clock.remaining(Player1), Duration::from_secs(45));
+    /// ```
+    pub fn remaining(&self, player: Player) -> Duration {
+        let remaining = self.remaining[Clock::index(player)];
+        match self.running {
+            Some((running, started)) if running == player => {
+                remaining.checked_sub(started.elapsed()).unwrap_or_default()
+            }
+            _ => remaining,
+        }
+    }
+
+    /// Returns the number of turns the player has to make before more time is added, if the time
+    /// control has periods with at least one move.
+    pub fn moves_to_go(&self, player: Player) -> Option<u32> {
+        match self.control {
+            TimeControl::MovesPerPeriod { moves, .. } => self.turns[Clock::index(player)]
+                .checked_rem(moves)
+                .map(|made| moves - made),
+            _ => None,
+        }
+    }
+
+    /// Returns the time that will be given back to a player after each turn.
+    ///
+    /// For Bronstein delay this is the most that can be given back.
+    pub fn increment(&self) -> Duration {
+        match self.control {
+            TimeControl::Fischer { increment, .. } => increment,
+            TimeControl::Bronstein { delay, .. } => delay,
+            _ => Duration::default(),
+        }
+    }
+
+    /// Returns whether the given player has run out of time.
+    pub fn flagged(&self, player: Player) -> bool {
+        self.remaining(player) == Duration::default()
+    }
+
+    /// Returns the player whose clock is running, if any.
+    pub fn running(&self) -> Option<Player> {
+        self.running.map(|(player, _)| player)
+    }
+
+    /// Starts the clock for the given player's turn, stopping the other player's clock without
+    /// recording a turn for them.
+    pub fn start(&mut self, player: Player) {
+        self.running = Some((player, Instant::now()));
+    }
+
+    /// Stops the running clock and records a turn for its player using the time since the clock was
+    /// started.
+    ///
+    /// Returns false if the player ran out of time during the turn.
+    pub fn stop(&mut self) -> bool {
+        match self.running.take() {
+            Some((player, started)) => self.record(player, started.elapsed()),
+            None => true,
+        }
+    }
+
+    /// Records a turn for a player that took the given amount of time.
+    ///
+    /// Returns false if the player ran out of time during the turn, in which case no time is added
+    /// back for the turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Player1;
+    /// use bit_checkers::clock::{Clock, TimeControl};
+    /// use std::time::Duration;
+    /// let mut clock = Clock::new(TimeControl::Fischer {
+    ///     time: Duration::from_secs(60),
+    ///     increment: Duration::from_secs(2),
+    /// });
+    ///
+    /// assert!(clock.record(Player1, Duration::from_secs(10)));
+    /// assert_eq!(clock.remaining(Player1), Duration::from_secs(52));
+    /// assert!(!clock.record(Player1, Duration::from_secs(60)));
+    /// assert!(clock.flagged(Player1));
+    /// ```
+    pub fn record(&mut self, player: Player, elapsed: Duration) -> bool {
+        let index = Clock::index(player);
+        let remaining = match self.remaining[index].checked_sub(elapsed) {
+            Some(remaining) if remaining > Duration::default() => remaining,
+            _ => {
+                self.remaining[index] = Duration::default();
+                return false;
+            }
+        };
+        self.turns[index] += 1;
+        self.remaining[index] = match self.control {
+            TimeControl::SuddenDeath { .. } => remaining,
+            TimeControl::Fischer { increment, .. } => remaining + increment,
+            TimeControl::Bronstein { delay, .. } => remaining + elapsed.min(delay),
+            TimeControl::MovesPerPeriod { time, moves } => {
+                if moves > 0 && self.turns[index].is_multiple_of(moves) {
+                    remaining + time
+                } else {
+                    remaining
+                }
+            }
+        };
+        true
+    }
+
+    fn index(player: Player) -> usize {
+        match player {
+            Player1 => 0,
+            Player2 => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_apply_bronstein_delay() {
+        let mut clock = Clock::new(TimeControl::Bronstein {
+            time: Duration::from_secs(60),
+            delay: Duration::from_secs(5),
+        });
+
+        clock.record(Player1, Duration::from_secs(3));
+        clock.record(Player2, Duration::from_secs(8));
+
+        assert_eq!(clock.remaining(Player1), Duration::from_secs(60));
+        assert_eq!(clock.remaining(Player2), Duration::from_secs(57));
+    }
+
+    #[test]
+    fn should_add_time_each_period() {
+        let mut clock = Clock::new(TimeControl::MovesPerPeriod {
+            time: Duration::from_secs(60),
+            moves: 2,
+        });
+
+        assert_eq!(clock.moves_to_go(Player1), Some(2));
+        clock.record(Player1, Duration::from_secs(20));
+        assert_eq!(clock.moves_to_go(Player1), Some(1));
+        assert_eq!(clock.remaining(Player1), Duration::from_secs(40));
+        clock.record(Player1, Duration::from_secs(20));
+        assert_eq!(clock.moves_to_go(Player1), Some(2));
+        assert_eq!(clock.remaining(Player1), Duration::from_secs(80));
+    }
+
+    #[test]
+    fn should_not_add_time_without_moves_per_period() {
+        let mut clock = Clock::new(TimeControl::MovesPerPeriod {
+            time: Duration::from_secs(60),
+            moves: 0,
+        });
+
+        assert_eq!(clock.moves_to_go(Player1), None);
+        clock.record(Player1, Duration::from_secs(20));
+        assert_eq!(clock.moves_to_go(Player1), None);
+        assert_eq!(clock.remaining(Player1), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn should_run_in_real_time() {
+        let mut clock = Clock::new(TimeControl::SuddenDeath {
+            time: Duration::from_secs(60),
+        });
+
+        clock.start(Player2);
+        assert_eq!(clock.running(), Some(Player2));
+        assert!(clock.remaining(Player2) <= Duration::from_secs(60));
+        assert!(clock.stop());
+        assert_eq!(clock.running(), None);
+        assert_eq!(clock.remaining(Player1), Duration::from_secs(60));
+    }
+}
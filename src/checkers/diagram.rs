@@ -0,0 +1,211 @@
+use super::board::{Board, Piece, Player1, Player2, Position};
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when parsing a board diagram fails.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DiagramError {
+    /// The diagram does not have 8 rows. Contains the number of rows.
+    RowCount(usize),
+    /// A row does not have 4 or 8 cells. Contains the row (y coordinate) and number of cells.
+    CellCount(u32, usize),
+    /// A cell is not one of `1`, `(1)`, `2`, `(2)`, `_` or `.`. Contains the cell's position and
+    /// text.
+    InvalidCell(Position, String),
+    /// A piece is on a square that is not playable.
+    LightSquare(Position),
+}
+
+impl Board {
+    /// Returns a board parsed from a diagram in the style of the `Debug` output.
+    ///
+    /// The diagram has 8 rows, from y = 0 to y = 7, separated by newlines, or by `;` if it
+    /// contains any. Cells are usually separated by whitespace and are `1` or `2` for a man
+    /// belonging to Player1 or Player2, `(1)` or `(2)` for a king and `_` for an empty square. A
+    /// row either has the 4 playable squares only, as in the `Debug` output, or all 8 cells with
+    /// `.` for the squares that are not playable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1, Position};
+    /// let board = Board::from_diagram(
+    ///     "
+    ///     _  1  _  _
+    ///   _  _  _  _
+    ///     _  _  _  _
+    ///   _  _  _  _
+    ///     _  _  _  _
+    ///   _  _  _  _
+    ///     _  _  _  _
+    ///   _  _ (2) _
+    ///     ",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(board.piece_at(Position(3, 0)).unwrap().player, Player1);
+    /// assert!(board.piece_at(Position(4, 7)).unwrap().king);
+    /// ```
+    pub fn from_diagram(diagram: &str) -> Result<Board, DiagramError> {
+        // `stringify!` may add line breaks within rows, so only `;` separates rows if present.
+        let separator = if diagram.contains(';') { ';' } else { '\n' };
+        let rows: Vec<&str> = diagram
+            .split(separator)
+            .filter(|row| !row.trim().is_empty())
+            .collect();
+        if rows.len() != 8 {
+            return Err(DiagramError::RowCount(rows.len()));
+        }
+
+        let mut pieces = Vec::new();
+        for (y, row) in (0..).zip(rows) {
+            let cells = cells(row);
+            let positions: Vec<Position> = match cells.len() {
+                4 => (0..4).map(|i| Position(i * 2 + (1 - y % 2), y)).collect(),
+                8 => (0..8).map(|x| Position(x, y)).collect(),
+                count => return Err(DiagramError::CellCount(y, count)),
+            };
+            for (&cell, position) in cells.iter().zip(positions) {
+                let (player, king) = match cell {
+                    "_" | "." => continue,
+                    "1" => (Player1, false),
+                    "(1)" => (Player1, true),
+                    "2" => (Player2, false),
+                    "(2)" => (Player2, true),
+                    _ => return Err(DiagramError::InvalidCell(position, cell.to_string())),
+                };
+                if position.square().is_none() {
+                    return Err(DiagramError::LightSquare(position));
+                }
+                pieces.push(Piece {
+                    position,
+                    player,
+                    king,
+                });
+            }
+        }
+        Ok(Board::new_with_pieces(pieces))
+    }
+}
+
+/// Returns the cells of a row of a diagram.
+///
+/// Cells don't need to be separated by whitespace, since `stringify!` in the `board!` macro may
+/// remove it. A cell is a single character, or a bracketed king such as `(1)`.
+fn cells(row: &str) -> Vec<&str> {
+    let mut cells = Vec::new();
+    let mut rest = row.trim_start();
+    while let Some(first) = rest.chars().next() {
+        let len = match first {
+            '(' => rest.find(')').map_or(rest.len(), |end| end + 1),
+            _ => first.len_utf8(),
+        };
+        cells.push(&rest[..len]);
+        rest = rest[len..].trim_start();
+    }
+    cells
+}
+
+/// Creates a `Board` from a diagram, see `Board::from_diagram`.
+///
+/// The diagram can either be a string, or written directly as tokens with rows separated by `;`.
+/// Panics if the diagram is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::board;
+/// use bit_checkers::board::{Board, Player2, Position};
+/// let board = board![
+///       _   _   _   _;
+///     _   _   _   _;
+///       _   _   _   _;
+///     _   _   _   _;
+///       _   _   _   _;
+///     _   _   _   _;
+///       _   _   _   _;
+///     2   _   _   _
+/// ];
+///
+/// assert_eq!(board.piece_at(Position(0, 7)).unwrap().player, Player2);
+/// ```
+#[macro_export]
+macro_rules! board {
+    ($diagram:literal) => {
+        $crate::board::Board::from_diagram($diagram).expect("diagram should be valid")
+    };
+    ($($cell:tt)*) => {
+        $crate::board::Board::from_diagram(stringify!($($cell)*))
+            .expect("diagram should be valid")
+    };
+}
+
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagramError::RowCount(count) => write!(f, "expected 8 rows but found {}", count),
+            DiagramError::CellCount(y, count) => {
+                write!(f, "expected 4 or 8 cells in row {} but found {}", y, count)
+            }
+            DiagramError::InvalidCell(position, cell) => {
+                write!(f, "invalid cell \"{}\" at {:?}", cell, position)
+            }
+            DiagramError::LightSquare(position) => {
+                write!(f, "piece at {:?} is not on a playable square", position)
+            }
+        }
+    }
+}
+
+impl Error for DiagramError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_debug_output() {
+        let board = Board::new().apply_turn(Player1, "11-15".parse().unwrap());
+
+        assert_eq!(Board::from_diagram(&format!("{:?}", board)), Ok(board));
+    }
+
+    #[test]
+    fn should_parse_full_rows() {
+        let board = crate::board![
+            .  _  .  _  .  _  .  _;
+            _  .  _  .  _  .  _  .;
+            .  _  .  _  .  _  .  _;
+            _  .  _  .  _  .  _  .;
+            .  _  .  _  .  _  .  _;
+            _  .  _  .  _  .  _  .;
+            . (1) .  _  .  _  .  _;
+            _  .  _  .  _  .  _  .
+        ];
+
+        assert_eq!(board.clone().pieces_iter().count(), 1);
+        assert!(board.piece_at(Position(1, 6)).unwrap().king);
+    }
+
+    #[test]
+    fn should_report_errors() {
+        let row = "_ _ _ _\n";
+
+        assert_eq!(
+            Board::from_diagram(&row.repeat(7)),
+            Err(DiagramError::RowCount(7))
+        );
+        assert_eq!(
+            Board::from_diagram(&(row.repeat(7) + "_ _ _")),
+            Err(DiagramError::CellCount(7, 3))
+        );
+        assert_eq!(
+            Board::from_diagram(&(row.repeat(7) + "_ _ 3 _")),
+            Err(DiagramError::InvalidCell(Position(4, 7), "3".to_string()))
+        );
+        assert_eq!(
+            Board::from_diagram(&(row.repeat(7) + "_ . _ . _ . _ 2")),
+            Err(DiagramError::LightSquare(Position(7, 7)))
+        );
+    }
+}
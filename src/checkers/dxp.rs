@@ -0,0 +1,909 @@
+use super::board::{Board, Piece, Player, Player1, Player2, Position};
+use super::clock::{Clock, TimeControl};
+use super::engine::Engine;
+use super::game::{Game, GameError, Outcome, DRAW_TURNS};
+use super::turn::Turn;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Version of the protocol sent in game requests.
+const VERSION: u32 = 1;
+/// Length of the name fields of game requests and acceptances.
+const NAME_LENGTH: usize = 32;
+/// Most bytes read for one message including its null byte, more than any valid message needs.
+const MAX_MESSAGE_LEN: u64 = 256;
+
+/// Type representing a message of the DamExchange Protocol (DXP).
+///
+/// Each message is a letter followed by fixed width fields, and is terminated by a null byte
+/// when sent. Player1 (Black) is `Z` and Player2 (White) is `W`, and squares use the standard 1
+/// to 32 numbering.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::dxp::{DxpMove, Message};
+/// let message: Message = "M001209180114".parse().unwrap();
+///
+/// assert_eq!(
+///     message,
+///     Message::Move(DxpMove {
+///         time: 12,
+///         from: 9,
+///         to: 18,
+///         captured: vec![14],
+///     })
+/// );
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub enum Message {
+    /// `C`: text to show to the opponent.
+    Chat(String),
+    /// `R`: request from the initiator to start a game.
+    GameRequest(GameRequest),
+    /// `A`: reply from the follower to a game request.
+    GameAccept {
+        name: String,
+        acceptance: Acceptance,
+    },
+    /// `M`: a turn played by the sender.
+    Move(DxpMove),
+    /// `E`: the sender ends the game, or replies to the opponent ending it. `stop` is set if the
+    /// sender doesn't want to play another game.
+    GameEnd { reason: EndReason, stop: bool },
+    /// `B`: request to take back turns, so that the given move number and player are to move.
+    BackRequest { move_number: u32, to_move: Player },
+    /// `K`: reply to a back request.
+    BackAccept(BackAcceptance),
+}
+
+/// Type representing a request to start a game.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GameRequest {
+    /// Name of the initiator's engine.
+    pub name: String,
+    /// Player the follower plays as.
+    pub follower: Player,
+    /// Thinking time for each player in minutes, or 0 if there is no time limit.
+    pub minutes: u32,
+    /// Number of turns to make within the thinking time, or 0 for the whole game.
+    pub moves: u32,
+    /// Starting board and player to move, or None for the initial position.
+    pub position: Option<(Board, Player)>,
+}
+
+/// Type representing a turn in a `MOVE` message, as the squares the piece moved from and to and
+/// the squares of the captured pieces in any order.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DxpMove {
+    /// Time spent on the turn in seconds.
+    pub time: u32,
+    pub from: u32,
+    pub to: u32,
+    pub captured: Vec<u32>,
+}
+
+/// Type representing the reply to a game request.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Acceptance {
+    Accepted = 0,
+    /// The follower doesn't want to play as the requested player.
+    ColorRefused = 1,
+    /// The follower doesn't accept the thinking time.
+    TimeRefused = 2,
+    /// The follower doesn't accept the starting position.
+    PositionRefused = 3,
+    Refused = 4,
+}
+
+/// Type representing the reason a game ended, from the point of view of the sender.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EndReason {
+    Unknown = 0,
+    Loss = 1,
+    Draw = 2,
+    Win = 3,
+}
+
+/// Type representing the reply to a back request.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BackAcceptance {
+    Accepted = 0,
+    NotSupported = 1,
+    Refused = 2,
+}
+
+/// Type representing a connection to another engine, over any stream such as a `TcpStream`.
+#[derive(Debug)]
+pub struct Connection<S> {
+    stream: BufReader<S>,
+}
+
+/// Error returned when communicating with another engine fails.
+#[derive(Debug)]
+pub enum DxpError {
+    Io(io::Error),
+    /// The connection was closed.
+    Disconnected,
+    /// A message was longer than any valid message, so it was not read to its end.
+    MessageTooLong,
+    /// A message could not be parsed. Contains the message.
+    InvalidMessage(String),
+    /// A message was received that was not expected at this point of the game.
+    UnexpectedMessage(Message),
+    /// The opponent's turn is not legal.
+    IllegalMove(DxpMove),
+    /// A turn chosen for this side could not be played.
+    Game(GameError),
+    /// The game request was refused.
+    Refused(Acceptance),
+}
+
+/// Waits for a game request on the stream and plays the game with the engine as the follower.
+///
+/// Returns the game as played and how it ended, which is None if the opponent ended it without
+/// giving a reason. The engine uses the thinking time from the request, and claims a draw after
+/// `DRAW_TURNS` turns.
+pub fn host_game<S: Read + Write>(
+    stream: S,
+    name: &str,
+    engine: &mut Engine,
+) -> Result<(Game, Option<Outcome>), DxpError> {
+    let mut connection = Connection::new(stream);
+    let request = connection.accept_game(name)?;
+    let game = request.game().with_turn_limit(DRAW_TURNS);
+    connection.play(game, request.follower, engine_turns(engine, &request))
+}
+
+/// Sends a game request on the stream and plays the game with the engine as the initiator if it
+/// is accepted.
+///
+/// Returns the game as played and how it ended, which is None if the opponent ended it without
+/// giving a reason. The engine uses the thinking time from the request, and claims a draw after
+/// `DRAW_TURNS` turns.
+pub fn join_game<S: Read + Write>(
+    stream: S,
+    request: &GameRequest,
+    engine: &mut Engine,
+) -> Result<(Game, Option<Outcome>), DxpError> {
+    let mut connection = Connection::new(stream);
+    connection.request_game(request)?;
+    let me = request.follower.opponent();
+    let game = request.game().with_turn_limit(DRAW_TURNS);
+    connection.play(game, me, engine_turns(engine, request))
+}
+
+/// Returns a function choosing turns with the engine, keeping track of its own thinking time.
+fn engine_turns<'a>(
+    engine: &'a mut Engine,
+    request: &GameRequest,
+) -> impl FnMut(&Game) -> Option<Turn> + 'a {
+    let mut clock = request.clock();
+    move |game| {
+        let started = Instant::now();
+        let mut position = Game::from_position(game.board().clone(), game.to_move());
+        if let Some(clock) = &clock {
+            position = position.with_clock(clock.clone());
+        }
+        let turn = engine.choose_turn(&position);
+        if let Some(clock) = &mut clock {
+            clock.record(game.to_move(), started.elapsed());
+        }
+        turn
+    }
+}
+
+impl GameRequest {
+    /// Returns a request for a game from the initial position without a time limit.
+    pub fn new(name: &str, follower: Player) -> GameRequest {
+        GameRequest {
+            name: name.to_string(),
+            follower,
+            minutes: 0,
+            moves: 0,
+            position: None,
+        }
+    }
+
+    /// Returns a game from the starting position of the request.
+    pub fn game(&self) -> Game {
+        match &self.position {
+            Some((board, to_move)) => Game::from_position(board.clone(), *to_move),
+            None => Game::new(),
+        }
+    }
+
+    /// Returns a clock for the thinking time of the request, or None if there is no time limit.
+    fn clock(&self) -> Option<Clock> {
+        let time = Duration::from_secs(u64::from(self.minutes) * 60);
+        match (self.minutes, self.moves) {
+            (0, _) => None,
+            (_, 0) => Some(Clock::new(TimeControl::SuddenDeath { time })),
+            (_, moves) => Some(Clock::new(TimeControl::MovesPerPeriod { time, moves })),
+        }
+    }
+}
+
+impl DxpMove {
+    /// Returns the DXP representation of a turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::dxp::DxpMove;
+    /// let dxp_move = DxpMove::from_turn(&"9x18x27".parse().unwrap(), 5);
+    ///
+    /// assert_eq!((dxp_move.from, dxp_move.to), (9, 27));
+    /// assert_eq!(dxp_move.captured, vec![14, 23]);
+    /// ```
+    pub fn from_turn(turn: &Turn, time: u32) -> DxpMove {
+        let captured = turn
+            .moves()
+            .filter(|a_move| a_move.is_jump())
+            .map(|a_move| {
+                let (from, to) = (a_move.from, a_move.to);
+                Position((from.0 + to.0) / 2, (from.1 + to.1) / 2)
+            })
+            .map(square)
+            .collect();
+        DxpMove {
+            time,
+            from: square(turn.from()),
+            to: square(turn.to()),
+            captured,
+        }
+    }
+
+    /// Returns the legal turn for the player matching the move, or None if there isn't one.
+    ///
+    /// The captured squares identify the path of a multi-jump when more than one path leads from
+    /// the same square to the same square.
+    pub fn to_turn(&self, board: &Board, player: Player) -> Option<Turn> {
+        let mut captured = self.captured.clone();
+        captured.sort_unstable();
+        board.turns(player).find(|turn| {
+            let mut dxp_move = DxpMove::from_turn(turn, self.time);
+            dxp_move.captured.sort_unstable();
+            dxp_move.from == self.from && dxp_move.to == self.to && dxp_move.captured == captured
+        })
+    }
+}
+
+impl EndReason {
+    /// Returns the reason from the opponent's point of view.
+    fn opposite(self) -> EndReason {
+        match self {
+            EndReason::Loss => EndReason::Win,
+            EndReason::Win => EndReason::Loss,
+            reason => reason,
+        }
+    }
+
+    /// Returns the outcome for a reason given from the point of view of the player.
+    fn outcome(self, player: Player) -> Option<Outcome> {
+        match self {
+            EndReason::Unknown => None,
+            EndReason::Loss => Some(Outcome::Win(player.opponent())),
+            EndReason::Draw => Some(Outcome::Draw),
+            EndReason::Win => Some(Outcome::Win(player)),
+        }
+    }
+}
+
+impl<S: Read + Write> Connection<S> {
+    /// Returns a connection using the stream.
+    pub fn new(stream: S) -> Connection<S> {
+        Connection {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    /// Sends a message.
+    pub fn send(&mut self, message: &Message) -> Result<(), DxpError> {
+        let stream = self.stream.get_mut();
+        write!(stream, "{}\0", message)?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    /// Waits for the next message.
+    pub fn receive(&mut self) -> Result<Message, DxpError> {
+        let mut bytes = Vec::new();
+        let len = (&mut self.stream)
+            .take(MAX_MESSAGE_LEN)
+            .read_until(0, &mut bytes)?;
+        if bytes.pop() != Some(0) {
+            return Err(match len as u64 {
+                MAX_MESSAGE_LEN => DxpError::MessageTooLong,
+                _ => DxpError::Disconnected,
+            });
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        text.parse()
+    }
+
+    /// Sends a game request and waits for the reply, returning the follower's name if the request
+    /// is accepted.
+    pub fn request_game(&mut self, request: &GameRequest) -> Result<String, DxpError> {
+        self.send(&Message::GameRequest(request.clone()))?;
+        loop {
+            match self.receive()? {
+                Message::Chat(_) => {}
+                Message::GameAccept {
+                    name,
+                    acceptance: Acceptance::Accepted,
+                } => return Ok(name),
+                Message::GameAccept { acceptance, .. } => {
+                    return Err(DxpError::Refused(acceptance))
+                }
+                message => return Err(DxpError::UnexpectedMessage(message)),
+            }
+        }
+    }
+
+    /// Waits for a game request and accepts it, replying with the given name.
+    pub fn accept_game(&mut self, name: &str) -> Result<GameRequest, DxpError> {
+        loop {
+            match self.receive()? {
+                Message::Chat(_) => {}
+                Message::GameRequest(request) => {
+                    self.send(&Message::GameAccept {
+                        name: name.to_string(),
+                        acceptance: Acceptance::Accepted,
+                    })?;
+                    return Ok(request);
+                }
+                message => return Err(DxpError::UnexpectedMessage(message)),
+            }
+        }
+    }
+
+    /// Plays a game as the given player until it ends, choosing turns with the given function.
+    ///
+    /// If the function returns None the game is ended as a draw. Back requests from the opponent
+    /// are accepted if the move number has been reached, and chat messages are ignored. Returns
+    /// the game as played and how it ended, which is None if the opponent ended it without giving
+    /// a reason.
+    pub fn play<F>(
+        &mut self,
+        mut game: Game,
+        me: Player,
+        mut choose: F,
+    ) -> Result<(Game, Option<Outcome>), DxpError>
+    where
+        F: FnMut(&Game) -> Option<Turn>,
+    {
+        let start = game.clone();
+        loop {
+            if let Some(outcome) = game.outcome() {
+                let reason = match outcome {
+                    Outcome::Win(player) if player == me => EndReason::Win,
+                    Outcome::Win(_) => EndReason::Loss,
+                    Outcome::Draw => EndReason::Draw,
+                };
+                return self.end_game(game, me, reason);
+            }
+
+            if game.to_move() == me {
+                let started = Instant::now();
+                let turn = match choose(&game) {
+                    Some(turn) => turn,
+                    None => return self.end_game(game, me, EndReason::Draw),
+                };
+                game.play(turn).map_err(DxpError::Game)?;
+                let time = started.elapsed().as_secs().min(9999) as u32;
+                self.send(&Message::Move(DxpMove::from_turn(&turn, time)))?;
+                continue;
+            }
+
+            match self.receive()? {
+                Message::Chat(_) => {}
+                Message::Move(dxp_move) => {
+                    let turn = dxp_move
+                        .to_turn(game.board(), game.to_move())
+                        .ok_or(DxpError::IllegalMove(dxp_move))?;
+                    game.play(turn).map_err(DxpError::Game)?;
+                }
+                Message::GameEnd { reason, .. } => {
+                    let reason = reason.opposite();
+                    self.send(&Message::GameEnd {
+                        reason,
+                        stop: false,
+                    })?;
+                    return Ok((game, reason.outcome(me)));
+                }
+                Message::BackRequest {
+                    move_number,
+                    to_move,
+                } => {
+                    let acceptance = match take_back(&start, &game, move_number, to_move) {
+                        Some(taken_back) => {
+                            game = taken_back;
+                            BackAcceptance::Accepted
+                        }
+                        None => BackAcceptance::Refused,
+                    };
+                    self.send(&Message::BackAccept(acceptance))?;
+                }
+                message => return Err(DxpError::UnexpectedMessage(message)),
+            }
+        }
+    }
+
+    /// Ends the game for the given reason and waits for the opponent to reply.
+    fn end_game(
+        &mut self,
+        game: Game,
+        me: Player,
+        reason: EndReason,
+    ) -> Result<(Game, Option<Outcome>), DxpError> {
+        self.send(&Message::GameEnd {
+            reason,
+            stop: false,
+        })?;
+        loop {
+            match self.receive()? {
+                Message::GameEnd { .. } => return Ok((game, reason.outcome(me))),
+                // A turn or request may have been sent before the opponent saw the game end
+                Message::Chat(_) | Message::Move(_) | Message::BackRequest { .. } => {}
+                message => return Err(DxpError::UnexpectedMessage(message)),
+            }
+        }
+    }
+}
+
+/// Returns the game replayed from its start until the given move number and player are to
+/// move, or None if that point hasn't been reached.
+///
+/// Move numbers start at 1 and count a turn by each player.
+fn take_back(start: &Game, game: &Game, move_number: u32, to_move: Player) -> Option<Game> {
+    let plies = (move_number.checked_sub(1)? * 2) as usize + (to_move != start.to_move()) as usize;
+    if plies > game.turns().len() {
+        return None;
+    }
+    let mut taken_back = start.clone();
+    for &turn in &game.turns()[..plies] {
+        taken_back.play(turn).ok()?;
+    }
+    Some(taken_back)
+}
+
+/// Returns the square number of a position on the board.
+fn square(position: Position) -> u32 {
+    position
+        .square()
+        .expect("turns should be on playable squares")
+}
+
+/// Returns the DXP letter for a player.
+fn color(player: Player) -> char {
+    match player {
+        Player1 => 'Z',
+        Player2 => 'W',
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Chat(text) => write!(f, "C{}", text),
+            Message::GameRequest(request) => {
+                write!(
+                    f,
+                    "R{:02}{:<width$.width$}{}{:04}{:04}",
+                    VERSION,
+                    request.name,
+                    color(request.follower),
+                    request.minutes,
+                    request.moves,
+                    width = NAME_LENGTH
+                )?;
+                match &request.position {
+                    None => write!(f, "A"),
+                    Some((board, to_move)) => {
+                        write!(f, "B{}", color(*to_move))?;
+                        for square in 1..=32 {
+                            let position = Position::from_square(square).unwrap();
+                            let piece = board.piece_at(position);
+                            let letter = match piece.map(|piece| (piece.player, piece.king)) {
+                                None => 'e',
+                                Some((Player1, false)) => 'z',
+                                Some((Player1, true)) => 'Z',
+                                Some((Player2, false)) => 'w',
+                                Some((Player2, true)) => 'W',
+                            };
+                            write!(f, "{}", letter)?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            Message::GameAccept { name, acceptance } => write!(
+                f,
+                "A{:<width$.width$}{}",
+                name,
+                *acceptance as u8,
+                width = NAME_LENGTH
+            ),
+            Message::Move(dxp_move) => {
+                write!(
+                    f,
+                    "M{:04}{:02}{:02}{:02}",
+                    dxp_move.time,
+                    dxp_move.from,
+                    dxp_move.to,
+                    dxp_move.captured.len()
+                )?;
+                for square in &dxp_move.captured {
+                    write!(f, "{:02}", square)?;
+                }
+                Ok(())
+            }
+            Message::GameEnd { reason, stop } => write!(f, "E{}{}", *reason as u8, *stop as u8),
+            Message::BackRequest {
+                move_number,
+                to_move,
+            } => write!(f, "B{:03}{}", move_number, color(*to_move)),
+            Message::BackAccept(acceptance) => write!(f, "K{}", *acceptance as u8),
+        }
+    }
+}
+
+/// Fixed width fields of a message being parsed.
+struct Fields<'a> {
+    message: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Fields<'a> {
+    fn invalid(&self) -> DxpError {
+        DxpError::InvalidMessage(self.message.to_string())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a str, DxpError> {
+        if !self.rest.is_char_boundary(len) {
+            return Err(self.invalid());
+        }
+        let (field, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(field)
+    }
+
+    fn number(&mut self, len: usize) -> Result<u32, DxpError> {
+        let field = self.take(len)?;
+        field.trim().parse().map_err(|_| self.invalid())
+    }
+
+    fn player(&mut self) -> Result<Player, DxpError> {
+        match self.take(1)? {
+            "Z" => Ok(Player1),
+            "W" => Ok(Player2),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn board(&mut self) -> Result<Board, DxpError> {
+        let mut pieces = Vec::new();
+        for (square, letter) in (1..).zip(self.take(32)?.chars()) {
+            let (player, king) = match letter {
+                'e' => continue,
+                'z' => (Player1, false),
+                'Z' => (Player1, true),
+                'w' => (Player2, false),
+                'W' => (Player2, true),
+                _ => return Err(self.invalid()),
+            };
+            pieces.push(Piece {
+                position: Position::from_square(square).unwrap(),
+                player,
+                king,
+            });
+        }
+        Board::try_new_with_pieces(pieces).map_err(|_| self.invalid())
+    }
+
+    /// Returns the value for a numeric code using the given conversion.
+    fn code<T>(&mut self, convert: fn(u32) -> Option<T>) -> Result<T, DxpError> {
+        let code = self.number(1)?;
+        convert(code).ok_or_else(|| self.invalid())
+    }
+
+    fn end(&self) -> Result<(), DxpError> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.invalid())
+        }
+    }
+}
+
+impl FromStr for Message {
+    type Err = DxpError;
+
+    /// Parses a message, without its terminating null byte.
+    fn from_str(s: &str) -> Result<Message, DxpError> {
+        let mut fields = Fields {
+            message: s,
+            rest: s,
+        };
+        let message = match fields.take(1)? {
+            "C" => return Ok(Message::Chat(fields.rest.to_string())),
+            "R" => {
+                fields.number(2)?;
+                let name = fields.take(NAME_LENGTH)?.trim_end().to_string();
+                let follower = fields.player()?;
+                let minutes = fields.number(4)?;
+                let moves = fields.number(4)?;
+                let position = match fields.take(1)? {
+                    "A" => None,
+                    "B" => {
+                        let to_move = fields.player()?;
+                        Some((fields.board()?, to_move))
+                    }
+                    _ => return Err(fields.invalid()),
+                };
+                Message::GameRequest(GameRequest {
+                    name,
+                    follower,
+                    minutes,
+                    moves,
+                    position,
+                })
+            }
+            "A" => Message::GameAccept {
+                name: fields.take(NAME_LENGTH)?.trim_end().to_string(),
+                acceptance: fields.code(|code| match code {
+                    0 => Some(Acceptance::Accepted),
+                    1 => Some(Acceptance::ColorRefused),
+                    2 => Some(Acceptance::TimeRefused),
+                    3 => Some(Acceptance::PositionRefused),
+                    4 => Some(Acceptance::Refused),
+                    _ => None,
+                })?,
+            },
+            "M" => {
+                let time = fields.number(4)?;
+                let from = fields.number(2)?;
+                let to = fields.number(2)?;
+                let count = fields.number(2)?;
+                let captured = (0..count)
+                    .map(|_| fields.number(2))
+                    .collect::<Result<_, _>>()?;
+                Message::Move(DxpMove {
+                    time,
+                    from,
+                    to,
+                    captured,
+                })
+            }
+            "E" => Message::GameEnd {
+                reason: fields.code(|code| match code {
+                    0 => Some(EndReason::Unknown),
+                    1 => Some(EndReason::Loss),
+                    2 => Some(EndReason::Draw),
+                    3 => Some(EndReason::Win),
+                    _ => None,
+                })?,
+                stop: fields.code(|code| match code {
+                    0 => Some(false),
+                    1 => Some(true),
+                    _ => None,
+                })?,
+            },
+            "B" => Message::BackRequest {
+                move_number: fields.number(3)?,
+                to_move: fields.player()?,
+            },
+            "K" => Message::BackAccept(fields.code(|code| match code {
+                0 => Some(BackAcceptance::Accepted),
+                1 => Some(BackAcceptance::NotSupported),
+                2 => Some(BackAcceptance::Refused),
+                _ => None,
+            })?),
+            _ => return Err(fields.invalid()),
+        };
+        fields.end()?;
+        Ok(message)
+    }
+}
+
+impl fmt::Display for DxpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DxpError::Io(error) => write!(f, "{}", error),
+            DxpError::Disconnected => write!(f, "the connection was closed"),
+            DxpError::MessageTooLong => write!(f, "a message was too long"),
+            DxpError::InvalidMessage(message) => write!(f, "invalid message \"{}\"", message),
+            DxpError::UnexpectedMessage(message) => write!(f, "unexpected message \"{}\"", message),
+            DxpError::IllegalMove(dxp_move) => {
+                write!(f, "{} is not a legal move", Message::Move(dxp_move.clone()))
+            }
+            DxpError::Game(error) => write!(f, "{}", error),
+            DxpError::Refused(acceptance) => write!(f, "the game was refused ({:?})", acceptance),
+        }
+    }
+}
+
+impl Error for DxpError {}
+
+impl From<io::Error> for DxpError {
+    fn from(error: io::Error) -> DxpError {
+        DxpError::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::engine::SearchLimits;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    /// Returns a function choosing turns with a shallow search, ending the game after a number of
+    /// turns.
+    fn shallow_turns(mut engine: Engine, turns: usize) -> impl FnMut(&Game) -> Option<Turn> {
+        move |game| {
+            if game.turns().len() >= turns {
+                return None;
+            }
+            engine
+                .search(game.board(), game.to_move(), SearchLimits::depth(2))
+                .turn
+        }
+    }
+
+    #[test]
+    fn should_format_and_parse_messages() {
+        let board = Board::new_with_pieces(vec![
+            Piece {
+                position: Position::from_square(1).unwrap(),
+                player: Player2,
+                king: true,
+            },
+            Piece {
+                position: Position::from_square(14).unwrap(),
+                player: Player1,
+                king: false,
+            },
+        ]);
+        let mut request = GameRequest::new("bit_checkers", Player1);
+        request.minutes = 5;
+        request.position = Some((board, Player2));
+        let messages = [
+            (Message::Chat("hello".to_string()), "Chello".to_string()),
+            (
+                Message::GameRequest(request),
+                format!(
+                    "R01bit_checkers{}Z00050000BWW{}z{}",
+                    " ".repeat(20),
+                    "e".repeat(12),
+                    "e".repeat(18)
+                ),
+            ),
+            (
+                Message::GameAccept {
+                    name: "opponent".to_string(),
+                    acceptance: Acceptance::TimeRefused,
+                },
+                format!("Aopponent{}2", " ".repeat(24)),
+            ),
+            (
+                Message::GameEnd {
+                    reason: EndReason::Win,
+                    stop: true,
+                },
+                "E31".to_string(),
+            ),
+            (
+                Message::BackRequest {
+                    move_number: 12,
+                    to_move: Player2,
+                },
+                "B012W".to_string(),
+            ),
+            (
+                Message::BackAccept(BackAcceptance::NotSupported),
+                "K1".to_string(),
+            ),
+        ];
+
+        for (message, text) in &messages {
+            assert_eq!(&message.to_string(), text);
+            assert_eq!(&text.parse::<Message>().unwrap(), message);
+        }
+        for invalid in &["", "X", "M00120918", "M001209180114extra", "E41", "B012X"] {
+            assert!(invalid.parse::<Message>().is_err());
+        }
+    }
+
+    #[test]
+    fn should_convert_multi_jump_moves() {
+        let board = crate::board![
+            _  _  _  _;
+          _  _  _  _;
+            _  _  1  _;
+          _  _  2  _;
+            _  _  _  _;
+          _  2  2  _;
+            _  _  _  _;
+          _  _  _  _
+        ];
+        let dxp_move = DxpMove {
+            time: 0,
+            from: 11,
+            to: 27,
+            captured: vec![23, 15],
+        };
+        let turn = dxp_move.to_turn(&board, Player1).unwrap();
+
+        assert_eq!(turn.to_string(), "11x18x27");
+        assert_eq!(DxpMove::from_turn(&turn, 0).captured, vec![15, 23]);
+        assert_eq!(
+            DxpMove {
+                captured: vec![15],
+                ..dxp_move
+            }
+            .to_turn(&board, Player1),
+            None
+        );
+    }
+
+    #[test]
+    fn should_take_back_turns() {
+        let start = Game::new();
+        let mut game = start.clone();
+        for turn in &["11-15", "23-19", "8-11", "22-17"] {
+            game.play(turn.parse().unwrap()).unwrap();
+        }
+
+        let taken_back = take_back(&start, &game, 2, Player2).unwrap();
+        assert_eq!(taken_back.turns(), &game.turns()[..3]);
+        assert_eq!(take_back(&start, &game, 1, Player1).unwrap().turns(), &[]);
+        assert!(take_back(&start, &game, 3, Player2).is_none());
+        assert!(take_back(&start, &game, 0, Player1).is_none());
+    }
+
+    #[test]
+    fn should_limit_message_length() {
+        let mut bytes = b"CHello\0".to_vec();
+        bytes.extend(vec![b'C'; 1000]);
+        let mut connection = Connection::new(io::Cursor::new(bytes));
+
+        assert!(matches!(connection.receive(), Ok(Message::Chat(text)) if text == "Hello"));
+        assert!(matches!(
+            connection.receive(),
+            Err(DxpError::MessageTooLong)
+        ));
+    }
+
+    #[test]
+    fn should_play_game_over_loopback() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let host = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut connection = Connection::new(stream);
+            let request = connection.accept_game("host").unwrap();
+            let turns = shallow_turns(Engine::new(), 40);
+            connection
+                .play(request.game(), request.follower, turns)
+                .unwrap()
+        });
+
+        let mut connection = Connection::new(TcpStream::connect(address).unwrap());
+        let request = GameRequest::new("initiator", Player2);
+        assert_eq!(connection.request_game(&request).unwrap(), "host");
+        let (game, outcome) = connection
+            .play(request.game(), Player1, shallow_turns(Engine::new(), 40))
+            .unwrap();
+        let (host_game, host_outcome) = host.join().unwrap();
+
+        assert_eq!(game.turns(), host_game.turns());
+        assert!(!game.turns().is_empty());
+        assert_eq!(outcome, host_outcome);
+        assert!(outcome.is_some());
+    }
+}
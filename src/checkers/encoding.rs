@@ -0,0 +1,235 @@
+use super::board::{Board, InvalidBoard, Player, Player1, Player2};
+use super::util::BitGrid;
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+
+/// Number of bytes in an encoded position.
+///
+/// The encoding is three little-endian 32-bit masks of the playable squares, containing Player1's
+/// pieces, Player2's pieces and kings, followed by a byte for the player to move (0 for Player1
+/// and 1 for Player2). Bit `n - 1` of a mask is square `n` in the standard numbering.
+pub const ENCODED_SIZE: usize = 13;
+
+/// Playable squares in even rows of a BitGrid, which are in odd columns.
+const EVEN_ROW_SQUARES: u64 = 0x00AA_00AA_00AA_00AA;
+/// Playable squares in odd rows of a BitGrid, which are in even columns.
+const ODD_ROW_SQUARES: u64 = 0x5500_5500_5500_5500;
+
+/// Error returned when decoding a position fails.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DecodeError {
+    /// The data is not `ENCODED_SIZE` bytes long. Contains the actual length.
+    InvalidLength(usize),
+    /// The byte for the player to move is not 0 or 1.
+    InvalidPlayer(u8),
+    /// Both players have a piece on the same square.
+    OverlappingPieces,
+    /// A king is on a square without a piece.
+    InvalidKings,
+    /// The pieces are not a valid position, see `Board::validate`.
+    InvalidBoard(InvalidBoard),
+}
+
+/// Returns the playable squares of a BitGrid packed into 32 bits, with square `n` in bit `n - 1`.
+///
+/// Cells that are not playable squares are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::board::{Board, Player1};
+/// use bit_checkers::encoding::pack_squares;
+///
+/// assert_eq!(pack_squares(Board::new().pieces(Player1)), 0xFFF);
+/// ```
+pub fn pack_squares(grid: BitGrid) -> u32 {
+    let mask = grid.mask();
+    // Move the squares of each row onto the even bits, then gather the even bits together.
+    let mut bits = ((mask & EVEN_ROW_SQUARES) >> 1) | (mask & ODD_ROW_SQUARES);
+    bits = (bits | bits >> 1) & 0x3333_3333_3333_3333;
+    bits = (bits | bits >> 2) & 0x0F0F_0F0F_0F0F_0F0F;
+    bits = (bits | bits >> 4) & 0x00FF_00FF_00FF_00FF;
+    bits = (bits | bits >> 8) & 0x0000_FFFF_0000_FFFF;
+    bits = (bits | bits >> 16) & 0x0000_0000_FFFF_FFFF;
+    bits as u32
+}
+
+/// Returns a BitGrid with the playable squares set from 32 packed bits, the inverse of
+/// `pack_squares`.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::encoding::unpack_squares;
+///
+/// assert!(unpack_squares(1).get_at_cell(1, 0));
+/// assert!(unpack_squares(1 << 31).get_at_cell(6, 7));
+/// ```
+pub fn unpack_squares(squares: u32) -> BitGrid {
+    let mut bits = squares as u64;
+    bits = (bits | bits << 16) & 0x0000_FFFF_0000_FFFF;
+    bits = (bits | bits << 8) & 0x00FF_00FF_00FF_00FF;
+    bits = (bits | bits << 4) & 0x0F0F_0F0F_0F0F_0F0F;
+    bits = (bits | bits << 2) & 0x3333_3333_3333_3333;
+    bits = (bits | bits << 1) & 0x5555_5555_5555_5555;
+    BitGrid::new_from_mask(((bits << 1) & EVEN_ROW_SQUARES) | (bits & ODD_ROW_SQUARES))
+}
+
+impl Board {
+    /// Returns the board and player to move encoded in `ENCODED_SIZE` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player2};
+    /// let bytes = Board::new().to_bytes(Player2);
+    ///
+    /// assert_eq!(Board::from_bytes(&bytes), Ok((Board::new(), Player2)));
+    /// ```
+    pub fn to_bytes(&self, to_move: Player) -> [u8; ENCODED_SIZE] {
+        let masks = [
+            pack_squares(self.pieces(Player1)),
+            pack_squares(self.pieces(Player2)),
+            pack_squares(self.kings(Player1).union(self.kings(Player2))),
+        ];
+        let mut bytes = [0; ENCODED_SIZE];
+        for (chunk, mask) in bytes.chunks_exact_mut(4).zip(&masks) {
+            chunk.copy_from_slice(&mask.to_le_bytes());
+        }
+        bytes[ENCODED_SIZE - 1] = match to_move {
+            Player1 => 0,
+            Player2 => 1,
+        };
+        bytes
+    }
+
+    /// Returns the board and player to move decoded from bytes produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Board, Player), DecodeError> {
+        if bytes.len() != ENCODED_SIZE {
+            return Err(DecodeError::InvalidLength(bytes.len()));
+        }
+        let mask = |i: usize| u32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+        let (player1, player2, kings) = (mask(0), mask(1), mask(2));
+        let to_move = match bytes[ENCODED_SIZE - 1] {
+            0 => Player1,
+            1 => Player2,
+            byte => return Err(DecodeError::InvalidPlayer(byte)),
+        };
+        if player1 & player2 != 0 {
+            return Err(DecodeError::OverlappingPieces);
+        }
+        if kings & !(player1 | player2) != 0 {
+            return Err(DecodeError::InvalidKings);
+        }
+
+        let board = Board::from_grids(
+            unpack_squares(player1),
+            unpack_squares(player1 & kings),
+            unpack_squares(player2),
+            unpack_squares(player2 & kings),
+        );
+        board.validate().map_err(DecodeError::InvalidBoard)?;
+        Ok((board, to_move))
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidLength(len) => {
+                write!(f, "expected {} bytes but got {}", ENCODED_SIZE, len)
+            }
+            DecodeError::InvalidPlayer(byte) => write!(f, "invalid player to move {}", byte),
+            DecodeError::OverlappingPieces => {
+                write!(f, "both players have a piece on the same square")
+            }
+            DecodeError::InvalidKings => write!(f, "king is on a square without a piece"),
+            DecodeError::InvalidBoard(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::board::{Position, Violation};
+
+    #[test]
+    fn should_pack_squares_in_order() {
+        for square in 1..=32 {
+            let position = Position::from_square(square).unwrap();
+            let grid = BitGrid::new().set_at_index(position.index(), true);
+
+            assert_eq!(pack_squares(grid), 1 << (square - 1));
+            assert_eq!(unpack_squares(1 << (square - 1)), grid);
+        }
+        assert_eq!(pack_squares(BitGrid::new_from_mask(!0)), !0);
+        assert_eq!(
+            unpack_squares(!0).mask(),
+            EVEN_ROW_SQUARES | ODD_ROW_SQUARES
+        );
+    }
+
+    #[test]
+    fn should_round_trip_positions() {
+        let board = Board::new()
+            .apply_turn(Player1, "11-15".parse().unwrap())
+            .apply_turn(Player2, "22-18".parse().unwrap())
+            .apply_turn(Player1, "15x22".parse().unwrap());
+        let fen: crate::checkers::fen::Fen = "W:WK3,21:B1,K32".parse().unwrap();
+
+        assert_eq!(
+            Board::from_bytes(&board.to_bytes(Player2)),
+            Ok((board, Player2))
+        );
+        assert_eq!(
+            Board::from_bytes(&fen.board.to_bytes(Player1)),
+            Ok((fen.board, Player1))
+        );
+    }
+
+    #[test]
+    fn should_report_errors() {
+        let mut bytes = Board::new().to_bytes(Player1);
+
+        assert_eq!(
+            Board::from_bytes(&bytes[1..]),
+            Err(DecodeError::InvalidLength(12))
+        );
+        bytes[12] = 2;
+        assert_eq!(
+            Board::from_bytes(&bytes),
+            Err(DecodeError::InvalidPlayer(2))
+        );
+        bytes[12] = 0;
+        bytes[4] = 1;
+        assert_eq!(
+            Board::from_bytes(&bytes),
+            Err(DecodeError::OverlappingPieces)
+        );
+        bytes[4] = 0;
+        bytes[10] = 1;
+        assert_eq!(Board::from_bytes(&bytes), Err(DecodeError::InvalidKings));
+    }
+
+    #[test]
+    fn should_reject_uncrowned_men_on_promotion_rows() {
+        let mut bytes = [0; ENCODED_SIZE];
+        bytes[3] = 0x80;
+        bytes[4] = 0x01;
+
+        assert_eq!(
+            Board::from_bytes(&bytes),
+            Err(DecodeError::InvalidBoard(InvalidBoard(vec![
+                Violation::UncrownedMan(Player2, Position::from_square(1).unwrap()),
+                Violation::UncrownedMan(Player1, Position::from_square(32).unwrap()),
+            ])))
+        );
+        bytes[11] = 0x80;
+        bytes[8] = 0x01;
+        assert!(Board::from_bytes(&bytes).is_ok());
+    }
+}
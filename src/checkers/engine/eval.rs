@@ -0,0 +1,39 @@
+use crate::checkers::board::{Board, Player, Player1, Player2};
+use crate::checkers::util::BitGrid;
+
+const MAN_VALUE: i32 = 100;
+const KING_VALUE: i32 = 140;
+/// Value for each row a man has advanced towards being crowned.
+const ADVANCE_VALUE: i32 = 2;
+/// Value for each man still guarding the back row, which stops the opponent being crowned.
+const BACK_ROW_VALUE: i32 = 6;
+const PLAYER1_BACK_ROW: u64 = 0x0000_0000_0000_00FF;
+const PLAYER2_BACK_ROW: u64 = 0xFF00_0000_0000_0000;
+
+/// Returns a static evaluation of the board from the point of view of the given player.
+///
+/// Positive scores are better for the player.
+pub(crate) fn evaluate(board: &Board, player: Player) -> i32 {
+    player_value(board, player) - player_value(board, player.opponent())
+}
+
+fn player_value(board: &Board, player: Player) -> i32 {
+    let kings = board.kings(player);
+    let men = board.pieces(player).intersect(kings.negate());
+    let back_row = BitGrid::new_from_mask(match player {
+        Player1 => PLAYER1_BACK_ROW,
+        Player2 => PLAYER2_BACK_ROW,
+    });
+    let advancement: u32 = men
+        .iter_set_cells()
+        .map(|(_, y)| match player {
+            Player1 => y,
+            Player2 => 7 - y,
+        })
+        .sum();
+
+    men.count() as i32 * MAN_VALUE
+        + kings.count() as i32 * KING_VALUE
+        + advancement as i32 * ADVANCE_VALUE
+        + men.intersect(back_row).count() as i32 * BACK_ROW_VALUE
+}
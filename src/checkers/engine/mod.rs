@@ -0,0 +1,433 @@
+mod eval;
+mod search;
+mod time;
+mod tt;
+
+pub use time::allocate_time;
+
+use super::board::{Board, Player};
+use super::book::OpeningBook;
+use super::game::Game;
+use super::turn::Turn;
+use super::util::Rng;
+use search::Searcher;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tt::TranspositionTable;
+
+/// Score for a position where the player to move has won. Scores for wins found during a search
+/// are reduced by the number of turns needed to win.
+pub const WIN_SCORE: i32 = 10_000;
+/// Maximum depth of an iterative deepening search.
+const MAX_DEPTH: u32 = 64;
+/// Depth searched when choosing a turn without a clock or other limits.
+const DEFAULT_DEPTH: u32 = 8;
+/// Default size of the transposition table in megabytes.
+const DEFAULT_HASH_SIZE: usize = 16;
+/// Largest transposition table size in megabytes, see `Engine::with_hash_size`.
+pub const MAX_HASH_SIZE: usize = 65_536;
+
+/// Limits on how long a search runs for. A search without limits runs until it is stopped or the
+/// maximum depth is reached.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct SearchLimits {
+    /// Maximum depth in turns, not counting forced jumps at the end of a line.
+    pub depth: Option<u32>,
+    /// Maximum time to search for.
+    pub time: Option<Duration>,
+    /// Maximum number of positions to search.
+    pub nodes: Option<u64>,
+}
+
+/// Result of a search.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SearchResult {
+    /// The best turn found, or None if the player has no legal turns.
+    pub turn: Option<Turn>,
+    /// Score for the player to move, positive when the player is ahead. A man is worth 100.
+    pub score: i32,
+    /// Depth of the deepest completed iteration.
+    pub depth: u32,
+    /// Number of positions searched.
+    pub nodes: u64,
+    /// Principal variation: the best line of play found, starting with `turn`.
+    pub pv: Vec<Turn>,
+}
+
+/// Handle that can be used to stop a running search from another thread.
+#[derive(Clone, Debug)]
+pub struct StopHandle(Arc<AtomicBool>);
+
+/// Type representing a checkers engine, which chooses turns by searching ahead.
+///
+/// Results of searches are kept in a transposition table, which is reused by later searches and
+/// shared between the threads of a parallel search.
+pub struct Engine {
+    stop: Arc<AtomicBool>,
+    book: Option<Arc<OpeningBook>>,
+    rng: Rng,
+    table: TranspositionTable,
+    threads: usize,
+}
+
+impl SearchLimits {
+    /// Returns limits for a search to a fixed depth.
+    pub fn depth(depth: u32) -> SearchLimits {
+        SearchLimits {
+            depth: Some(depth),
+            ..SearchLimits::default()
+        }
+    }
+
+    /// Returns limits for a search with a fixed amount of time.
+    pub fn time(time: Duration) -> SearchLimits {
+        SearchLimits {
+            time: Some(time),
+            ..SearchLimits::default()
+        }
+    }
+}
+
+impl StopHandle {
+    /// Stops the search. The search returns the result from its last completed iteration.
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Engine {
+    /// Returns an engine without an opening book.
+    pub fn new() -> Engine {
+        Engine {
+            stop: Arc::new(AtomicBool::new(false)),
+            book: None,
+            rng: Rng::from_entropy(),
+            table: TranspositionTable::new(DEFAULT_HASH_SIZE),
+            threads: 1,
+        }
+    }
+
+    /// Returns the engine searching with the given number of threads.
+    ///
+    /// Extra threads search the same position in parallel (Lazy SMP), sharing results through the
+    /// transposition table. A single thread, the default, gives deterministic results.
+    pub fn with_threads(mut self, threads: usize) -> Engine {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns the engine with a transposition table of approximately the given size in megabytes.
+    ///
+    /// Sizes above `MAX_HASH_SIZE` are reduced to it.
+    pub fn with_hash_size(mut self, megabytes: usize) -> Engine {
+        self.table = TranspositionTable::new(megabytes.min(MAX_HASH_SIZE));
+        self
+    }
+
+    /// Removes all results of previous searches from the transposition table.
+    pub fn clear_hash(&mut self) {
+        self.table.clear();
+    }
+
+    /// Returns the engine using an opening book when choosing turns.
+    pub fn with_book(mut self, book: OpeningBook) -> Engine {
+        self.book = Some(Arc::new(book));
+        self
+    }
+
+    /// Returns a handle for stopping searches made by this engine.
+    ///
+    /// A search stopped before it starts returns straight away, as do later searches, until a new
+    /// handle is taken. Handles taken before are no longer used.
+    pub fn stop_handle(&mut self) -> StopHandle {
+        self.stop = Arc::new(AtomicBool::new(false));
+        StopHandle(self.stop.clone())
+    }
+
+    /// Searches for the best turn for the given player.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1};
+    /// use bit_checkers::engine::{Engine, SearchLimits};
+    /// let result = Engine::new().search(&Board::new(), Player1, SearchLimits::depth(4));
+    ///
+    /// assert!(result.turn.is_some());
+    /// assert_eq!(result.depth, 4);
+    /// ```
+    pub fn search(&mut self, board: &Board, player: Player, limits: SearchLimits) -> SearchResult {
+        let (mut result, helper_nodes) = self.run(board, player, limits, |searcher| {
+            searcher.search(board, player)
+        });
+        result.nodes += helper_nodes;
+        result
+    }
+
+    /// Searches for the best few turns for the given player, returning a line of play for each
+    /// one ordered from best to worst.
+    ///
+    /// Progress is called with the lines found so far each time an iteration of the search
+    /// completes, so that results can be shown while the search runs. Fewer lines are returned if
+    /// the player doesn't have enough legal turns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1};
+    /// use bit_checkers::engine::{Engine, SearchLimits};
+    /// let mut depths = Vec::new();
+    /// let lines = Engine::new().analyse(&Board::new(), Player1, SearchLimits::depth(3), 3, |lines| {
+    ///     depths.push(lines[0].depth)
+    /// });
+    ///
+    /// assert_eq!(lines.len(), 3);
+    /// assert!(lines[0].score >= lines[1].score && lines[1].score >= lines[2].score);
+    /// assert_eq!(depths, vec![1, 2, 3]);
+    /// ```
+    pub fn analyse<F>(
+        &mut self,
+        board: &Board,
+        player: Player,
+        limits: SearchLimits,
+        lines: usize,
+        progress: F,
+    ) -> Vec<SearchResult>
+    where
+        F: FnMut(&[SearchResult]),
+    {
+        let (mut results, helper_nodes) = self.run(board, player, limits, |searcher| {
+            searcher.search_lines(board, player, lines.max(1), progress)
+        });
+        for result in &mut results {
+            result.nodes += helper_nodes;
+        }
+        results
+    }
+
+    /// Chooses a turn for the player to move in a game, or returns None if the game has finished.
+    ///
+    /// A turn from the opening book is used if there is one. Otherwise the time to search is
+    /// decided from the game's clock, or a fixed depth is searched if the game has no clock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut game = bit_checkers::game::Game::new();
+    /// let turn = bit_checkers::engine::Engine::new().choose_turn(&game).unwrap();
+    ///
+    /// assert!(game.play(turn).is_ok());
+    /// ```
+    pub fn choose_turn(&mut self, game: &Game) -> Option<Turn> {
+        if game.outcome().is_some() {
+            return None;
+        }
+        let (board, player) = (game.board(), game.to_move());
+        let rng = &mut self.rng;
+        if let Some(turn) = self
+            .book
+            .as_ref()
+            .and_then(|book| book.choose(board, player, rng))
+        {
+            return Some(turn);
+        }
+        let mut turns = game.legal_turns();
+        let only_turn = turns.next();
+        if turns.next().is_none() {
+            return only_turn;
+        }
+        let limits = match game.clock() {
+            Some(clock) => SearchLimits::time(allocate_time(clock, player)),
+            None => SearchLimits::depth(DEFAULT_DEPTH),
+        };
+        self.search(board, player, limits).turn
+    }
+}
+
+impl Engine {
+    /// Runs a search on the current thread, with helper threads searching the same position if
+    /// the engine uses more than one thread.
+    ///
+    /// Returns the result of the search and the number of nodes searched by the helpers.
+    fn run<F, R>(
+        &mut self,
+        board: &Board,
+        player: Player,
+        limits: SearchLimits,
+        search: F,
+    ) -> (R, u64)
+    where
+        F: FnOnce(&mut Searcher) -> R,
+    {
+        let (stop, table) = (&*self.stop, &self.table);
+        if self.threads == 1 {
+            return (search(&mut Searcher::new(stop, table, limits)), 0);
+        }
+
+        let finished = AtomicBool::new(false);
+        thread::scope(|scope| {
+            let helpers: Vec<_> = (1..self.threads)
+                .map(|id| {
+                    let finished = &finished;
+                    scope.spawn(move || {
+                        Searcher::new(stop, table, limits)
+                            .helper(id, finished)
+                            .search(board, player)
+                            .nodes
+                    })
+                })
+                .collect();
+            let result = search(&mut Searcher::new(stop, table, limits));
+            finished.store(true, Ordering::Relaxed);
+            let helper_nodes = helpers
+                .into_iter()
+                .map(|helper| helper.join().expect("search thread should not panic"))
+                .sum();
+            (result, helper_nodes)
+        })
+    }
+}
+
+impl fmt::Debug for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Engine")
+            .field("book", &self.book.is_some())
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Engine {
+        Engine::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::board::{Piece, Player1, Player2, Position};
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    fn should_find_winning_jump() {
+        // Player1 can win by jumping both of Player2's pieces
+        let board = Board::new_with_pieces(vec![
+            Piece {
+                player: Player1,
+                king: false,
+                position: Position(1, 2),
+            },
+            Piece {
+                player: Player1,
+                king: false,
+                position: Position(7, 0),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(2, 3),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(4, 5),
+            },
+        ]);
+        let result = Engine::new().search(&board, Player1, SearchLimits::depth(3));
+
+        assert_eq!(result.turn.unwrap().to_string(), "9x18x27");
+        assert_eq!(result.score, WIN_SCORE - 1);
+    }
+
+    #[test]
+    fn should_respect_time_limit() {
+        let started = Instant::now();
+        let result = Engine::new().search(
+            &Board::new(),
+            Player1,
+            SearchLimits::time(Duration::from_millis(100)),
+        );
+
+        assert!(result.turn.is_some());
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn should_stop_when_requested() {
+        let mut engine = Engine::new();
+        let handle = engine.stop_handle();
+        let started = Instant::now();
+        let search =
+            thread::spawn(move || engine.search(&Board::new(), Player1, SearchLimits::default()));
+        thread::sleep(Duration::from_millis(50));
+        handle.stop();
+        let result = search.join().unwrap();
+
+        assert!(result.turn.is_some());
+        assert_eq!(result.turn, result.pv.first().copied());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn should_stop_before_search_starts() {
+        let mut engine = Engine::new();
+        engine.stop_handle().stop();
+        // Without the stop this search would never finish
+        let stopped = engine.search(&Board::new(), Player1, SearchLimits::default());
+        engine.stop_handle();
+        let searched = engine.search(&Board::new(), Player1, SearchLimits::depth(4));
+
+        assert!(stopped.turn.is_some());
+        assert_eq!(searched.depth, 4);
+    }
+
+    #[test]
+    fn should_search_deterministically_with_one_thread() {
+        let board = Board::new().apply_turn(Player1, "11-15".parse().unwrap());
+        let search = || Engine::new().search(&board, Player2, SearchLimits::depth(6));
+
+        assert_eq!(search(), search());
+    }
+
+    #[test]
+    fn should_analyse_multiple_lines() {
+        let mut iterations = 0;
+        let lines = Engine::new().with_threads(2).analyse(
+            &Board::new(),
+            Player1,
+            SearchLimits::depth(5),
+            10,
+            |_| iterations += 1,
+        );
+        let best = Engine::new().search(&Board::new(), Player1, SearchLimits::depth(5));
+
+        assert_eq!(iterations, 5);
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0].score, best.score);
+        for line in &lines {
+            assert_eq!(line.depth, 5);
+            assert_eq!(line.turn, line.pv.first().copied());
+        }
+        for (i, line) in lines.iter().enumerate() {
+            assert!(lines[..i].iter().all(|other| other.turn != line.turn));
+        }
+    }
+
+    #[test]
+    fn should_search_with_multiple_threads() {
+        let board = Board::new().apply_turn(Player1, "11-15".parse().unwrap());
+        let parallel =
+            Engine::new()
+                .with_threads(4)
+                .search(&board, Player2, SearchLimits::depth(6));
+
+        assert_eq!(parallel.depth, 6);
+        assert!(board.turns(Player2).any(|turn| Some(turn) == parallel.turn));
+        assert!(parallel.nodes > 0);
+    }
+}
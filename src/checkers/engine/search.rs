@@ -0,0 +1,295 @@
+use super::eval::evaluate;
+use super::tt::{turn_key, Bound, Entry, TranspositionTable};
+use super::{SearchLimits, SearchResult, MAX_DEPTH, WIN_SCORE};
+use crate::checkers::board::{Board, Player};
+use crate::checkers::turn::Turn;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Number of nodes searched between checks of the time and stop flag.
+const CHECK_INTERVAL: u64 = 1024;
+/// Maximum distance from the root that will be searched, including capture extensions.
+const MAX_PLY: u32 = 128;
+/// Scores further from zero than this are wins found by the search.
+const WIN_THRESHOLD: i32 = WIN_SCORE - MAX_PLY as i32;
+
+/// State for a single thread searching from a root position.
+pub(super) struct Searcher<'a> {
+    stop: &'a AtomicBool,
+    finished: Option<&'a AtomicBool>,
+    table: &'a TranspositionTable,
+    /// Time the search started, only read when there is a time limit since some platforms
+    /// (e.g. `wasm32-unknown-unknown`) don't have a clock.
+    started: Option<Instant>,
+    limits: SearchLimits,
+    start_depth: u32,
+    nodes: u64,
+    aborted: bool,
+    /// Turns at the root that are skipped, because they were already found as better lines.
+    excluded: Vec<Turn>,
+}
+
+impl<'a> Searcher<'a> {
+    pub(super) fn new(
+        stop: &'a AtomicBool,
+        table: &'a TranspositionTable,
+        limits: SearchLimits,
+    ) -> Searcher<'a> {
+        Searcher {
+            stop,
+            finished: None,
+            table,
+            started: limits.time.map(|_| Instant::now()),
+            limits,
+            start_depth: 1,
+            nodes: 0,
+            aborted: false,
+            excluded: Vec::new(),
+        }
+    }
+
+    /// Returns the searcher set up as a helper thread for a parallel search.
+    ///
+    /// Helpers start at different depths so that they fill the transposition table with results
+    /// the main thread will need later, and stop when the finished flag is set.
+    pub(super) fn helper(mut self, id: usize, finished: &'a AtomicBool) -> Searcher<'a> {
+        self.start_depth = 1 + (id % 2) as u32;
+        self.finished = Some(finished);
+        self.limits.depth = None;
+        self
+    }
+
+    /// Searches with iterative deepening until a limit is reached or the search is stopped.
+    ///
+    /// The result is from the deepest iteration that was completed.
+    pub(super) fn search(&mut self, board: &Board, player: Player) -> SearchResult {
+        let fallback = SearchResult {
+            turn: board.turns(player).next(),
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            pv: Vec::new(),
+        };
+        let mut result = self
+            .search_lines(board, player, 1, |_| {})
+            .pop()
+            .unwrap_or(fallback);
+        result.nodes = self.nodes;
+        result
+    }
+
+    /// Searches for the best number of lines with iterative deepening, until a limit is reached or
+    /// the search is stopped.
+    ///
+    /// The lines are ordered from best to worst and are from the deepest iteration that was
+    /// completed. Progress is called with the lines after each iteration.
+    pub(super) fn search_lines<F>(
+        &mut self,
+        board: &Board,
+        player: Player,
+        lines: usize,
+        mut progress: F,
+    ) -> Vec<SearchResult>
+    where
+        F: FnMut(&[SearchResult]),
+    {
+        let mut results: Vec<SearchResult> = Vec::new();
+        let max_depth = self.limits.depth.unwrap_or(MAX_DEPTH).min(MAX_DEPTH);
+
+        for depth in self.start_depth..=max_depth {
+            let mut iteration = Vec::with_capacity(lines);
+            self.excluded.clear();
+            for line in 0..lines {
+                let mut pv = Vec::new();
+                let hint = results.get(line).map_or(&[][..], |result| &result.pv[..]);
+                let score = self.negamax(
+                    board, player, depth, 0, -WIN_SCORE, WIN_SCORE, &mut pv, hint,
+                );
+                if self.aborted || pv.is_empty() {
+                    break;
+                }
+                self.excluded.push(pv[0]);
+                iteration.push(SearchResult {
+                    turn: pv.first().copied(),
+                    score,
+                    depth,
+                    nodes: self.nodes,
+                    pv,
+                });
+            }
+            self.excluded.clear();
+            if self.aborted || iteration.is_empty() {
+                break;
+            }
+            iteration.sort_by_key(|result| std::cmp::Reverse(result.score));
+            results = iteration;
+            progress(&results);
+
+            // Stop early if the result is decided or there wouldn't be time for another iteration
+            let decided = results[0].score.abs() > WIN_THRESHOLD;
+            let half_time_used = self
+                .started
+                .zip(self.limits.time)
+                .is_some_and(|(started, time)| started.elapsed() * 2 > time);
+            if (decided && self.finished.is_none()) || half_time_used {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Returns the score for the player to move, updating the principal variation.
+    ///
+    /// The hint is the principal variation from the previous iteration, its first turn is
+    /// searched first.
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &mut self,
+        board: &Board,
+        player: Player,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        pv: &mut Vec<Turn>,
+        hint: &[Turn],
+    ) -> i32 {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) && self.should_abort() {
+            self.aborted = true;
+        }
+        if self.aborted {
+            return 0;
+        }
+
+        let mut turns: Vec<Turn> = board.turns(player).collect();
+        if turns.is_empty() {
+            return -(WIN_SCORE - ply as i32);
+        }
+        // Jumps are forced, so keep searching until the position is quiet
+        if (depth == 0 && !turns[0].is_jump()) || ply >= MAX_PLY {
+            return evaluate(board, player);
+        }
+
+        let hash = board.position_hash(player);
+        let entry = self.table.probe(hash);
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth >= depth) {
+            let score = score_from_table(entry.score, ply);
+            let cutoff = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => score >= beta,
+                Bound::Upper => score <= alpha,
+            };
+            if cutoff {
+                return score;
+            }
+        }
+
+        let first = match hint.first() {
+            Some(hint) => turns.iter().position(|turn| turn == hint),
+            None => entry
+                .and_then(|entry| entry.best)
+                .and_then(|best| turns.iter().position(|turn| turn_key(turn) == best)),
+        };
+        if let Some(first) = first {
+            turns.swap(0, first);
+        }
+
+        let original_alpha = alpha;
+        let mut best = -WIN_SCORE;
+        let mut best_turn = None;
+        let mut child_pv = Vec::new();
+        for turn in turns {
+            if ply == 0 && self.excluded.contains(&turn) {
+                continue;
+            }
+            let child = board.clone().apply_turn(player, turn);
+            let child_hint = match hint.split_first() {
+                Some((&first, rest)) if first == turn => rest,
+                _ => &[],
+            };
+            child_pv.clear();
+            let score = -self.negamax(
+                &child,
+                player.opponent(),
+                depth.saturating_sub(1),
+                ply + 1,
+                -beta,
+                -alpha,
+                &mut child_pv,
+                child_hint,
+            );
+            if self.aborted {
+                return 0;
+            }
+            if score > best {
+                best = score;
+                best_turn = Some(turn);
+                if score > alpha {
+                    alpha = score;
+                    pv.clear();
+                    pv.push(turn);
+                    pv.extend_from_slice(&child_pv);
+                }
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+
+        if best_turn.is_none() {
+            return best;
+        }
+        let bound = if best <= original_alpha {
+            Bound::Upper
+        } else if best >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.store(
+            hash,
+            Entry {
+                score: score_to_table(best, ply),
+                depth,
+                bound,
+                best: best_turn.as_ref().map(turn_key),
+            },
+        );
+        best
+    }
+
+    fn should_abort(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+            || self
+                .finished
+                .is_some_and(|finished| finished.load(Ordering::Relaxed))
+            || self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes)
+            || self
+                .started
+                .zip(self.limits.time)
+                .is_some_and(|(started, time)| started.elapsed() >= time)
+    }
+}
+
+/// Converts a score relative to the root into one relative to the current position, so that
+/// stored wins are correct when the position is reached at a different distance from the root.
+fn score_to_table(score: i32, ply: u32) -> i32 {
+    if score > WIN_THRESHOLD {
+        score + ply as i32
+    } else if score < -WIN_THRESHOLD {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+fn score_from_table(score: i32, ply: u32) -> i32 {
+    if score > WIN_THRESHOLD {
+        score - ply as i32
+    } else if score < -WIN_THRESHOLD {
+        score + ply as i32
+    } else {
+        score
+    }
+}
@@ -0,0 +1,36 @@
+use crate::checkers::board::Player;
+use crate::checkers::clock::Clock;
+use std::time::Duration;
+
+/// Number of turns assumed to be left in the game when the time control has no periods.
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+/// Time kept in reserve so that the clock doesn't run out while a turn is being made.
+const SAFETY_MARGIN: Duration = Duration::from_millis(50);
+
+/// Returns how long the engine should spend choosing a turn for the player based on their clock.
+///
+/// The remaining time is split evenly over the turns left in the period (or an estimate of the
+/// turns left in the game), and most of the increment is used as well.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::board::Player1;
+/// use bit_checkers::clock::{Clock, TimeControl};
+/// use std::time::Duration;
+/// let clock = Clock::new(TimeControl::MovesPerPeriod {
+///     time: Duration::from_secs(100),
+///     moves: 20,
+/// });
+///
+/// assert_eq!(
+///     bit_checkers::engine::allocate_time(&clock, Player1),
+///     Duration::from_secs(5)
+/// );
+/// ```
+pub fn allocate_time(clock: &Clock, player: Player) -> Duration {
+    let remaining = clock.remaining(player);
+    let moves_to_go = clock.moves_to_go(player).unwrap_or(DEFAULT_MOVES_TO_GO);
+    let budget = remaining / moves_to_go + clock.increment() * 3 / 4;
+    budget.min(remaining.saturating_sub(SAFETY_MARGIN))
+}
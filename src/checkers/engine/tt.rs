@@ -0,0 +1,153 @@
+use crate::checkers::board::Position;
+use crate::checkers::turn::Turn;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Size in bytes of each entry in the table.
+const ENTRY_SIZE: usize = 16;
+
+/// Type of bound a stored score represents.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum Bound {
+    /// The score is exact.
+    Exact,
+    /// The real score is at least the stored score.
+    Lower,
+    /// The real score is at most the stored score.
+    Upper,
+}
+
+/// Result of a previous search of a position.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) struct Entry {
+    pub score: i32,
+    pub depth: u32,
+    pub bound: Bound,
+    /// Start and end position of the best turn found, if any.
+    pub best: Option<(Position, Position)>,
+}
+
+/// Transposition table shared between search threads without locking.
+///
+/// Each entry is stored as two words, the position hash XORed with the data and the data itself.
+/// An entry torn by concurrent writes fails the hash check when read, so it is ignored rather than
+/// producing a wrong result.
+pub(super) struct TranspositionTable {
+    entries: Vec<[AtomicU64; 2]>,
+}
+
+impl TranspositionTable {
+    /// Returns an empty table using approximately the given number of megabytes.
+    pub(super) fn new(megabytes: usize) -> TranspositionTable {
+        let len = (megabytes.saturating_mul(1024 * 1024) / ENTRY_SIZE).max(1);
+        TranspositionTable {
+            entries: (0..len)
+                .map(|_| [AtomicU64::new(0), AtomicU64::new(0)])
+                .collect(),
+        }
+    }
+
+    /// Removes every entry.
+    pub(super) fn clear(&self) {
+        for [key, data] in &self.entries {
+            key.store(0, Ordering::Relaxed);
+            data.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the entry for the position hash, if there is one.
+    pub(super) fn probe(&self, hash: u64) -> Option<Entry> {
+        let [key, data] = &self.entries[self.index(hash)];
+        let data = data.load(Ordering::Relaxed);
+        if data == 0 || key.load(Ordering::Relaxed) ^ data != hash {
+            return None;
+        }
+        Some(unpack(data))
+    }
+
+    /// Stores an entry for the position hash, replacing whatever was there.
+    pub(super) fn store(&self, hash: u64, entry: Entry) {
+        let [key, data] = &self.entries[self.index(hash)];
+        let packed = pack(entry);
+        key.store(hash ^ packed, Ordering::Relaxed);
+        data.store(packed, Ordering::Relaxed);
+    }
+
+    fn index(&self, hash: u64) -> usize {
+        (hash % self.entries.len() as u64) as usize
+    }
+}
+
+/// Returns the start and end position of a turn in the form stored in an entry.
+pub(super) fn turn_key(turn: &Turn) -> (Position, Position) {
+    (turn.from(), turn.to())
+}
+
+/// Packs an entry into a single non-zero word.
+///
+/// Layout (from least significant bit): score (16), depth (8), bound (2), has best turn (1),
+/// best turn start (6) and end (6), and a set bit so that the word is never zero.
+fn pack(entry: Entry) -> u64 {
+    let bound = match entry.bound {
+        Bound::Exact => 0,
+        Bound::Lower => 1,
+        Bound::Upper => 2,
+    };
+    let best = match entry.best {
+        Some((from, to)) => 1 | position_index(from) << 1 | position_index(to) << 7,
+        None => 0,
+    };
+    (entry.score as i16 as u16 as u64)
+        | (entry.depth.min(255) as u64) << 16
+        | bound << 24
+        | best << 26
+        | 1 << 63
+}
+
+fn unpack(data: u64) -> Entry {
+    let best = (data >> 26) & 0x1FFF;
+    Entry {
+        score: data as u16 as i16 as i32,
+        depth: ((data >> 16) & 0xFF) as u32,
+        bound: match (data >> 24) & 0b11 {
+            0 => Bound::Exact,
+            1 => Bound::Lower,
+            _ => Bound::Upper,
+        },
+        best: if best & 1 == 1 {
+            Some((index_position(best >> 1), index_position(best >> 7)))
+        } else {
+            None
+        },
+    }
+}
+
+fn position_index(position: Position) -> u64 {
+    position.index() as u64
+}
+
+fn index_position(index: u64) -> Position {
+    Position::from_index((index & 0x3F) as u32).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_store_and_probe_entries() {
+        let table = TranspositionTable::new(1);
+        let entry = Entry {
+            score: -9_990,
+            depth: 12,
+            bound: Bound::Lower,
+            best: Some((Position(1, 2), Position(5, 6))),
+        };
+
+        table.store(0x1234_5678, entry);
+
+        assert_eq!(table.probe(0x1234_5678), Some(entry));
+        assert_eq!(table.probe(0x1234_5679), None);
+        table.clear();
+        assert_eq!(table.probe(0x1234_5678), None);
+    }
+}
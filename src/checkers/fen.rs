@@ -0,0 +1,259 @@
+use super::board::{Board, InvalidBoard, Piece, Player, Player1, Player2, Position};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Type representing a position in the FEN format used by PDN, e.g. `B:W21,22,K30:B1,2,3`.
+///
+/// The first field is the player to move, followed by a field listing the squares of each
+/// player's pieces. `B` (Black) is Player1 and `W` (White) is Player2. Kings are prefixed by `K`,
+/// and a range of squares containing men can be given as e.g. `1-12`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Fen {
+    pub board: Board,
+    pub to_move: Player,
+}
+
+/// Error returned when parsing a FEN string fails.
+#[derive(Clone, PartialEq, Debug)]
+pub enum FenError {
+    /// The string does not have a side to move followed by two piece fields.
+    InvalidFormat(String),
+    /// A field does not start with `B` or `W`, or both piece fields are for the same player.
+    InvalidColor(String),
+    /// A piece is not a valid square number or range.
+    InvalidSquare(String),
+    /// A square contains more than one piece.
+    DuplicateSquare(u32),
+    /// The pieces are not a valid position, see `Board::validate`.
+    InvalidBoard(InvalidBoard),
+}
+
+impl Fen {
+    /// Returns the FEN for the initial board with Player1 to move.
+    pub fn new() -> Fen {
+        Fen {
+            board: Board::new(),
+            to_move: Player1,
+        }
+    }
+}
+
+impl Default for Fen {
+    fn default() -> Fen {
+        Fen::new()
+    }
+}
+
+impl FromStr for Fen {
+    type Err = FenError;
+
+    /// Parses a FEN string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Player1, Player2, Position};
+    /// use bit_checkers::fen::Fen;
+    /// let fen: Fen = "W:W21,22,K30:B1,2,3".parse().unwrap();
+    ///
+    /// assert_eq!(fen.to_move, Player2);
+    /// assert_eq!(fen.board.pieces(Player1).count(), 3);
+    /// assert!(fen.board.piece_at(Position(2, 7)).unwrap().king);
+    /// ```
+    fn from_str(s: &str) -> Result<Fen, FenError> {
+        let fields: Vec<&str> = s.trim().trim_end_matches('.').split(':').collect();
+        if fields.len() != 3 {
+            return Err(FenError::InvalidFormat(s.to_string()));
+        }
+        let to_move = parse_color(fields[0].trim())?;
+        let mut pieces = Vec::new();
+        let mut players = Vec::new();
+        for field in &fields[1..] {
+            let field = field.trim();
+            let player = parse_color(field.get(..1).unwrap_or(field))?;
+            players.push(player);
+            for item in field[1..]
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            {
+                parse_item(item, player, &mut pieces)?;
+            }
+        }
+        if players[0] == players[1] {
+            return Err(FenError::InvalidColor(fields[2].trim().to_string()));
+        }
+
+        let mut squares: Vec<u32> = pieces
+            .iter()
+            .filter_map(|piece: &Piece| piece.position.square())
+            .collect();
+        squares.sort_unstable();
+        if let Some(pair) = squares.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(FenError::DuplicateSquare(pair[0]));
+        }
+        Ok(Fen {
+            board: Board::try_new_with_pieces(pieces).map_err(FenError::InvalidBoard)?,
+            to_move,
+        })
+    }
+}
+
+impl fmt::Display for Fen {
+    /// Formats the position as a FEN string, with the pieces of each player in square order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let fen = bit_checkers::fen::Fen::new();
+    ///
+    /// assert_eq!(
+    ///     fen.to_string(),
+    ///     "B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", color(self.to_move))?;
+        for &player in &[Player2, Player1] {
+            write!(f, ":{}", color(player))?;
+            let mut squares: Vec<(u32, bool)> = self
+                .board
+                .clone()
+                .pieces_iter()
+                .filter(|piece| piece.player == player)
+                .filter_map(|piece| piece.position.square().map(|square| (square, piece.king)))
+                .collect();
+            squares.sort_unstable();
+            for (i, (square, king)) in squares.into_iter().enumerate() {
+                let separator = if i > 0 { "," } else { "" };
+                let king = if king { "K" } else { "" };
+                write!(f, "{}{}{}", separator, king, square)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn color(player: Player) -> char {
+    match player {
+        Player1 => 'B',
+        Player2 => 'W',
+    }
+}
+
+fn parse_color(field: &str) -> Result<Player, FenError> {
+    match field {
+        "B" => Ok(Player1),
+        "W" => Ok(Player2),
+        _ => Err(FenError::InvalidColor(field.to_string())),
+    }
+}
+
+/// Parses a square, king or range of squares, adding the pieces to the list.
+fn parse_item(item: &str, player: Player, pieces: &mut Vec<Piece>) -> Result<(), FenError> {
+    let error = || FenError::InvalidSquare(item.to_string());
+    let parse_square = |square: &str| {
+        square
+            .parse()
+            .ok()
+            .and_then(Position::from_square)
+            .ok_or_else(error)
+    };
+    if let Some(square) = item.strip_prefix('K') {
+        pieces.push(Piece {
+            position: parse_square(square)?,
+            player,
+            king: true,
+        });
+    } else if let Some((first, last)) = item.split_once('-') {
+        let first = parse_square(first)?.square().unwrap();
+        let last = parse_square(last)?.square().unwrap();
+        if first > last {
+            return Err(error());
+        }
+        pieces.extend((first..=last).map(|square| Piece {
+            position: Position::from_square(square).unwrap(),
+            player,
+            king: false,
+        }));
+    } else {
+        pieces.push(Piece {
+            position: parse_square(item)?,
+            player,
+            king: false,
+        });
+    }
+    Ok(())
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::InvalidFormat(fen) => write!(f, "invalid FEN \"{}\"", fen),
+            FenError::InvalidColor(field) => write!(f, "invalid color \"{}\"", field),
+            FenError::InvalidSquare(item) => write!(f, "invalid square \"{}\"", item),
+            FenError::DuplicateSquare(square) => {
+                write!(f, "square {} contains more than one piece", square)
+            }
+            FenError::InvalidBoard(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for FenError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::board::Violation;
+
+    #[test]
+    fn should_round_trip_positions() {
+        for fen in &["W:W18,24,27,28,K10,K15:B12,16,20,K22,K25,K29", "B:W:BK1"] {
+            let parsed: Fen = fen.parse().unwrap();
+            let formatted = parsed.to_string();
+
+            assert_eq!(formatted.parse::<Fen>().unwrap(), parsed);
+        }
+        assert_eq!(
+            "W:B1-3,K5:W32.".parse::<Fen>().unwrap().to_string(),
+            "W:W32:B1,2,3,K5"
+        );
+    }
+
+    #[test]
+    fn should_report_errors() {
+        assert_eq!(
+            "B:W21".parse::<Fen>(),
+            Err(FenError::InvalidFormat("B:W21".to_string()))
+        );
+        assert_eq!(
+            "X:W21:B1".parse::<Fen>(),
+            Err(FenError::InvalidColor("X".to_string()))
+        );
+        assert_eq!(
+            "B:W21:W1".parse::<Fen>(),
+            Err(FenError::InvalidColor("W1".to_string()))
+        );
+        assert_eq!(
+            "B:W21,33:B1".parse::<Fen>(),
+            Err(FenError::InvalidSquare("33".to_string()))
+        );
+        assert_eq!(
+            "B:W3-1:B5".parse::<Fen>(),
+            Err(FenError::InvalidSquare("3-1".to_string()))
+        );
+        assert_eq!(
+            "B:W21,K1:B1".parse::<Fen>(),
+            Err(FenError::DuplicateSquare(1))
+        );
+        assert_eq!(
+            "B:W2:B30".parse::<Fen>(),
+            Err(FenError::InvalidBoard(InvalidBoard(vec![
+                Violation::UncrownedMan(Player2, Position::from_square(2).unwrap()),
+                Violation::UncrownedMan(Player1, Position::from_square(30).unwrap()),
+            ])))
+        );
+    }
+}
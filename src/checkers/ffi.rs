@@ -0,0 +1,362 @@
+use super::board::{Move, Player, Player1, Player2, Position};
+use super::fen::Fen;
+use super::game::{Game, Outcome};
+use super::turn::Turn;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr;
+
+/// Maximum number of squares in the path of a move.
+pub const BC_MAX_PATH: usize = 13;
+
+/// Value for no player, returned by `bc_board_winner` while the game is in progress.
+pub const BC_NONE: i32 = 0;
+/// Value for Player1 (Black), who moves first.
+pub const BC_PLAYER1: i32 = 1;
+/// Value for Player2 (White).
+pub const BC_PLAYER2: i32 = 2;
+
+/// Status returned when a call succeeds.
+pub const BC_OK: i32 = 0;
+/// Status returned when a required pointer argument is null.
+pub const BC_ERROR_NULL: i32 = -1;
+/// Status returned when a move is not legal for the player to move.
+pub const BC_ERROR_ILLEGAL_MOVE: i32 = -2;
+
+/// Opaque handle to a board and the player to move.
+pub struct BcBoard {
+    game: Game,
+}
+
+/// A move as the path of squares visited by the moving piece, in the standard 1 to 32
+/// numbering. A jump over several pieces visits more than two squares.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct BcMove {
+    /// Number of squares in the path, at least 2.
+    pub len: u8,
+    pub squares: [u8; BC_MAX_PATH],
+}
+
+/// Returns a new board with pieces in the initial positions and Player1 to move.
+///
+/// The board must be freed with `bc_board_free`.
+#[no_mangle]
+pub extern "C" fn bc_board_new() -> *mut BcBoard {
+    Box::into_raw(Box::new(BcBoard { game: Game::new() }))
+}
+
+/// Returns a new board from a FEN string such as `B:W21-32:B1-12`, or null if the string is
+/// null or not a valid position.
+///
+/// The board must be freed with `bc_board_free`.
+///
+/// # Safety
+///
+/// `fen` must be null or point to a null terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_from_fen(fen: *const c_char) -> *mut BcBoard {
+    if fen.is_null() {
+        return ptr::null_mut();
+    }
+    let parsed = CStr::from_ptr(fen)
+        .to_str()
+        .ok()
+        .and_then(|fen| fen.parse::<Fen>().ok());
+    match parsed {
+        Some(Fen { board, to_move }) => Box::into_raw(Box::new(BcBoard {
+            game: Game::from_position(board, to_move),
+        })),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Returns a copy of a board, or null if the board is null.
+///
+/// The copy must be freed with `bc_board_free`.
+///
+/// # Safety
+///
+/// `board` must be null or a board returned by this library that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_clone(board: *const BcBoard) -> *mut BcBoard {
+    match board.as_ref() {
+        Some(board) => Box::into_raw(Box::new(BcBoard {
+            game: board.game.clone(),
+        })),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees a board. Does nothing if the board is null.
+///
+/// # Safety
+///
+/// `board` must be null or a board returned by this library that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_free(board: *mut BcBoard) {
+    if !board.is_null() {
+        drop(Box::from_raw(board));
+    }
+}
+
+/// Returns `BC_PLAYER1` or `BC_PLAYER2` for the player to move, or `BC_ERROR_NULL` if the board
+/// is null.
+///
+/// # Safety
+///
+/// `board` must be null or a board returned by this library that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_to_move(board: *const BcBoard) -> i32 {
+    match board.as_ref() {
+        Some(board) => player_value(board.game.to_move()),
+        None => BC_ERROR_NULL,
+    }
+}
+
+/// Writes the legal moves for the player to move into a buffer, returning the number of legal
+/// moves.
+///
+/// At most `capacity` moves are written, so if the result is larger than `capacity` the call can
+/// be repeated with a larger buffer. `moves` may be null if `capacity` is 0. Returns 0 if the
+/// board is null.
+///
+/// # Safety
+///
+/// `board` must be null or a board returned by this library that hasn't been freed, and `moves`
+/// must point to a buffer of at least `capacity` moves.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_legal_moves(
+    board: *const BcBoard,
+    moves: *mut BcMove,
+    capacity: usize,
+) -> usize {
+    let board = match board.as_ref() {
+        Some(board) => board,
+        None => return 0,
+    };
+    let mut count = 0;
+    for turn in board.game.legal_turns() {
+        if count < capacity && !moves.is_null() {
+            *moves.add(count) = from_turn(&turn);
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Plays a move for the player to move, returning `BC_OK`, `BC_ERROR_ILLEGAL_MOVE` if the move
+/// is not legal (including after the game has been won) or `BC_ERROR_NULL`.
+///
+/// # Safety
+///
+/// `board` must be null or a board returned by this library that hasn't been freed, and
+/// `bc_move` must be null or point to a move.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_apply_move(board: *mut BcBoard, bc_move: *const BcMove) -> i32 {
+    let (board, bc_move) = match (board.as_mut(), bc_move.as_ref()) {
+        (Some(board), Some(bc_move)) => (board, bc_move),
+        _ => return BC_ERROR_NULL,
+    };
+    match to_turn(bc_move).map(|turn| board.game.play(turn)) {
+        Some(Ok(())) => BC_OK,
+        _ => BC_ERROR_ILLEGAL_MOVE,
+    }
+}
+
+/// Returns the player who has won because their opponent can't move, `BC_NONE` if the game is
+/// in progress, or `BC_ERROR_NULL` if the board is null.
+///
+/// # Safety
+///
+/// `board` must be null or a board returned by this library that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_winner(board: *const BcBoard) -> i32 {
+    match board.as_ref().map(|board| board.game.outcome()) {
+        Some(Some(Outcome::Win(player))) => player_value(player),
+        Some(_) => BC_NONE,
+        None => BC_ERROR_NULL,
+    }
+}
+
+/// Writes the board as a null terminated FEN string, returning the length of the string without
+/// the terminator.
+///
+/// Like `snprintf`, the string is truncated if the buffer is too small, so the result can be
+/// used to size the buffer. `buffer` may be null if `capacity` is 0. Returns 0 if the board is
+/// null.
+///
+/// # Safety
+///
+/// `board` must be null or a board returned by this library that hasn't been freed, and
+/// `buffer` must point to at least `capacity` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bc_board_fen(
+    board: *const BcBoard,
+    buffer: *mut c_char,
+    capacity: usize,
+) -> usize {
+    match board.as_ref() {
+        Some(board) => {
+            let fen = Fen {
+                board: board.game.board().clone(),
+                to_move: board.game.to_move(),
+            };
+            write_string(&fen.to_string(), buffer, capacity)
+        }
+        None => 0,
+    }
+}
+
+/// Writes a move in standard notation such as `11-15` or `9x18x27` as a null terminated string,
+/// returning the length of the string without the terminator.
+///
+/// The string is truncated in the same way as `bc_board_fen`. Returns 0 if the move is null or
+/// invalid.
+///
+/// # Safety
+///
+/// `bc_move` must be null or point to a move, and `buffer` must point to at least `capacity`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bc_move_notation(
+    bc_move: *const BcMove,
+    buffer: *mut c_char,
+    capacity: usize,
+) -> usize {
+    match bc_move.as_ref().and_then(to_turn) {
+        Some(turn) => write_string(&turn.to_string(), buffer, capacity),
+        None => 0,
+    }
+}
+
+/// Returns the C API value of a player.
+fn player_value(player: Player) -> i32 {
+    match player {
+        Player1 => BC_PLAYER1,
+        Player2 => BC_PLAYER2,
+    }
+}
+
+/// Returns the C API representation of a turn.
+fn from_turn(turn: &Turn) -> BcMove {
+    let mut bc_move = BcMove::default();
+    for position in turn.positions() {
+        let square = position
+            .square()
+            .expect("turns should be on playable squares");
+        bc_move.squares[bc_move.len as usize] = square as u8;
+        bc_move.len += 1;
+    }
+    bc_move
+}
+
+/// Returns the turn for a C API move, or None if it isn't a valid path of squares.
+fn to_turn(bc_move: &BcMove) -> Option<Turn> {
+    let len = bc_move.len as usize;
+    if !(2..=BC_MAX_PATH).contains(&len) {
+        return None;
+    }
+    let mut positions = bc_move.squares[..len]
+        .iter()
+        .map(|&square| Position::from_square(square as u32));
+    let (from, to) = (positions.next()??, positions.next()??);
+    positions.try_fold(Turn::from_move(Move { from, to }), |turn, position| {
+        Some(turn.then(position?))
+    })
+}
+
+/// Writes a string to a buffer with a null terminator, truncating it if necessary, and returns
+/// the length of the whole string.
+unsafe fn write_string(text: &str, buffer: *mut c_char, capacity: usize) -> usize {
+    if !buffer.is_null() && capacity > 0 {
+        let len = text.len().min(capacity - 1);
+        ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, buffer, len);
+        *buffer.add(len) = 0;
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::CString;
+
+    fn notation(bc_move: &BcMove) -> String {
+        let mut buffer = [0; 40];
+        let len = unsafe { bc_move_notation(bc_move, buffer.as_mut_ptr(), buffer.len()) };
+        let text = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        assert_eq!(len, text.to_bytes().len());
+        text.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn should_list_and_apply_moves() {
+        unsafe {
+            let board = bc_board_new();
+            let mut moves = [BcMove::default(); 4];
+
+            assert_eq!(bc_board_legal_moves(board, ptr::null_mut(), 0), 7);
+            assert_eq!(bc_board_legal_moves(board, moves.as_mut_ptr(), 4), 7);
+            assert_eq!(notation(&moves[0]), "9-13");
+            assert_eq!(bc_board_apply_move(board, &moves[0]), BC_OK);
+            assert_eq!(bc_board_to_move(board), BC_PLAYER2);
+            assert_eq!(bc_board_apply_move(board, &moves[0]), BC_ERROR_ILLEGAL_MOVE);
+            assert_eq!(bc_board_winner(board), BC_NONE);
+
+            let mut fen = [0; 8];
+            let len = bc_board_fen(board, fen.as_mut_ptr(), fen.len());
+            assert_eq!(
+                len,
+                "W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,10,11,12,13".len()
+            );
+            assert_eq!(CStr::from_ptr(fen.as_ptr()).to_str(), Ok("W:W21,2"));
+            bc_board_free(board);
+        }
+    }
+
+    #[test]
+    fn should_play_jumps_from_fen() {
+        unsafe {
+            let fen = CString::new("B:W18,27:B14").unwrap();
+            let board = bc_board_from_fen(fen.as_ptr());
+            let mut moves = [BcMove::default(); 2];
+
+            assert_eq!(bc_board_legal_moves(board, moves.as_mut_ptr(), 2), 1);
+            assert_eq!(notation(&moves[0]), "14x23x32");
+            let copy = bc_board_clone(board);
+            assert_eq!(bc_board_apply_move(copy, &moves[0]), BC_OK);
+            assert_eq!(bc_board_winner(copy), BC_PLAYER1);
+            assert_eq!(bc_board_winner(board), BC_NONE);
+            bc_board_free(copy);
+            bc_board_free(board);
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_arguments() {
+        unsafe {
+            let invalid = CString::new("B:W1:B2,2").unwrap();
+
+            assert!(bc_board_from_fen(invalid.as_ptr()).is_null());
+            assert!(bc_board_from_fen(ptr::null()).is_null());
+            assert_eq!(bc_board_to_move(ptr::null()), BC_ERROR_NULL);
+            assert_eq!(
+                bc_board_apply_move(ptr::null_mut(), ptr::null()),
+                BC_ERROR_NULL
+            );
+            assert_eq!(
+                bc_move_notation(
+                    &BcMove {
+                        len: 1,
+                        squares: [0; BC_MAX_PATH]
+                    },
+                    ptr::null_mut(),
+                    0
+                ),
+                0
+            );
+            bc_board_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use super::board::{Board, Player, Player1};
+use super::clock::Clock;
+use super::turn::Turn;
+use std::error::Error;
+use std::fmt;
+
+/// Number of turns after which games played by the command line, the arena and DXP are drawn.
+pub const DRAW_TURNS: usize = 300;
+
+/// Type representing how a game ended.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Outcome {
+    /// The player won, either because their opponent can't make a turn or ran out of time.
+    Win(Player),
+    /// The game reached its turn limit, see `Game::with_turn_limit`.
+    Draw,
+}
+
+/// Type representing a game in progress: the board, whose turn it is, the turns played so far,
+/// an optional clock and an optional turn limit.
+#[derive(Clone, Debug)]
+pub struct Game {
+    board: Board,
+    to_move: Player,
+    turns: Vec<Turn>,
+    clock: Option<Clock>,
+    turn_limit: Option<usize>,
+    outcome: Option<Outcome>,
+}
+
+/// Error returned when a turn can't be played.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameError {
+    /// The game has already finished.
+    Finished(Outcome),
+    /// The turn is not legal for the player to move.
+    IllegalTurn(Turn),
+    /// The player to move ran out of time before making the turn.
+    OutOfTime(Player),
+}
+
+impl Game {
+    /// Returns a new game with pieces in the initial positions and Player1 to move.
+    pub fn new() -> Game {
+        Game::from_position(Board::new(), Player1)
+    }
+
+    /// Returns a new game starting from the given board and player to move.
+    pub fn from_position(board: Board, to_move: Player) -> Game {
+        let mut game = Game {
+            board,
+            to_move,
+            turns: Vec::new(),
+            clock: None,
+            turn_limit: None,
+            outcome: None,
+        };
+        game.update_outcome();
+        game
+    }
+
+    /// Returns the game with a clock, which is started for the player to move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Player1;
+    /// use bit_checkers::clock::{Clock, TimeControl};
+    /// use bit_checkers::game::Game;
+    /// use std::time::Duration;
+    /// let game = Game::new().with_clock(Clock::new(TimeControl::SuddenDeath {
+    ///     time: Duration::from_secs(300),
+    /// }));
+    ///
+    /// assert_eq!(game.clock().unwrap().running(), Some(Player1));
+    /// ```
+    pub fn with_clock(mut self, mut clock: Clock) -> Game {
+        if self.outcome.is_none() {
+            clock.start(self.to_move);
+        }
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Returns the game drawn once the given number of turns have been played, including turns
+    /// already played.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::game::{Game, Outcome};
+    /// let mut game = Game::new().with_turn_limit(2);
+    /// game.play("11-15".parse().unwrap()).unwrap();
+    /// game.play("23-19".parse().unwrap()).unwrap();
+    ///
+    /// assert_eq!(game.outcome(), Some(Outcome::Draw));
+    /// ```
+    pub fn with_turn_limit(mut self, turns: usize) -> Game {
+        self.turn_limit = Some(turns);
+        if self.outcome.is_none() {
+            self.update_outcome();
+        }
+        self
+    }
+
+    /// Returns the number of turns after which the game is drawn, if it has a limit.
+    pub fn turn_limit(&self) -> Option<usize> {
+        self.turn_limit
+    }
+
+    /// Returns the current board.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the player whose turn it is.
+    pub fn to_move(&self) -> Player {
+        self.to_move
+    }
+
+    /// Returns the turns played so far.
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    /// Returns the game clock, if the game has one.
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    /// Returns how the game ended, or None if it is still in progress.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    /// Returns an iterator over the turns the player to move can make.
+    pub fn legal_turns(&self) -> impl Iterator<Item = Turn> {
+        self.board.turns(self.to_move)
+    }
+
+    /// Plays a turn for the player to move.
+    ///
+    /// If the game has a clock, the time since the player's clock was started is recorded. The
+    /// game is lost by the player if they ran out of time, and the turn is not played.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Player2;
+    /// let mut game = bit_checkers::game::Game::new();
+    /// game.play("11-15".parse().unwrap()).unwrap();
+    ///
+    /// assert_eq!(game.to_move(), Player2);
+    /// assert!(game.play("11-15".parse().unwrap()).is_err());
+    /// ```
+    pub fn play(&mut self, turn: Turn) -> Result<(), GameError> {
+        if self.check_time() {
+            return Err(GameError::OutOfTime(self.to_move));
+        }
+        if let Some(outcome) = self.outcome {
+            return Err(GameError::Finished(outcome));
+        }
+        if !self.legal_turns().any(|legal| legal == turn) {
+            return Err(GameError::IllegalTurn(turn));
+        }
+        if let Some(clock) = self.clock.as_mut() {
+            if !clock.stop() {
+                self.outcome = Some(Outcome::Win(self.to_move.opponent()));
+                return Err(GameError::OutOfTime(self.to_move));
+            }
+        }
+
+        self.board = self.board.clone().apply_turn(self.to_move, turn);
+        self.to_move = self.to_move.opponent();
+        self.turns.push(turn);
+        self.update_outcome();
+        if let (Some(clock), None) = (self.clock.as_mut(), self.outcome) {
+            clock.start(self.to_move);
+        }
+        Ok(())
+    }
+
+    /// Ends the game if the player to move has run out of time, returning whether they have.
+    pub fn check_time(&mut self) -> bool {
+        let flagged = self.outcome.is_none()
+            && self
+                .clock
+                .as_ref()
+                .is_some_and(|clock| clock.flagged(self.to_move));
+        if flagged {
+            self.outcome = Some(Outcome::Win(self.to_move.opponent()));
+        }
+        flagged
+    }
+
+    /// Sets the outcome if the player to move has no legal turns or the turn limit is reached.
+    fn update_outcome(&mut self) {
+        if self.legal_turns().next().is_none() {
+            self.outcome = Some(Outcome::Win(self.to_move.opponent()));
+        } else if self
+            .turn_limit
+            .is_some_and(|limit| self.turns.len() >= limit)
+        {
+            self.outcome = Some(Outcome::Draw);
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Game {
+        Game::new()
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::Finished(_) => write!(f, "the game has finished"),
+            GameError::IllegalTurn(turn) => write!(f, "{} is not a legal turn", turn),
+            GameError::OutOfTime(player) => write!(f, "{:?} ran out of time", player),
+        }
+    }
+}
+
+impl Error for GameError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::board::{Piece, Player2, Position};
+    use crate::checkers::clock::TimeControl;
+    use std::time::Duration;
+
+    #[test]
+    fn should_end_when_player_cannot_move() {
+        let mut game = Game::from_position(
+            Board::new_with_pieces(vec![
+                Piece {
+                    player: Player1,
+                    king: false,
+                    position: Position(1, 2),
+                },
+                Piece {
+                    player: Player2,
+                    king: false,
+                    position: Position(2, 3),
+                },
+            ]),
+            Player1,
+        );
+
+        game.play("9x18".parse().unwrap()).unwrap();
+
+        assert_eq!(game.outcome(), Some(Outcome::Win(Player1)));
+        assert_eq!(
+            game.play("18-22".parse().unwrap()),
+            Err(GameError::Finished(Outcome::Win(Player1)))
+        );
+    }
+
+    #[test]
+    fn should_draw_at_turn_limit() {
+        let mut game = Game::new().with_turn_limit(3);
+        for turn in &["11-15", "23-19", "8-11"] {
+            assert_eq!(game.outcome(), None);
+            game.play(turn.parse().unwrap()).unwrap();
+        }
+
+        assert_eq!(game.outcome(), Some(Outcome::Draw));
+        assert_eq!(
+            game.play("22-17".parse().unwrap()),
+            Err(GameError::Finished(Outcome::Draw))
+        );
+        assert_eq!(
+            Game::new().with_turn_limit(0).outcome(),
+            Some(Outcome::Draw)
+        );
+    }
+
+    #[test]
+    fn should_lose_on_time() {
+        let mut game = Game::new().with_clock(Clock::new(TimeControl::SuddenDeath {
+            time: Duration::from_millis(1),
+        }));
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert_eq!(
+            game.play("11-15".parse().unwrap()),
+            Err(GameError::OutOfTime(Player1))
+        );
+        assert_eq!(game.outcome(), Some(Outcome::Win(Player2)));
+        assert!(game.turns().is_empty());
+    }
+}
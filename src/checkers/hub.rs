@@ -0,0 +1,668 @@
+use super::board::{Board, Player, Player1};
+use super::clock::{Clock, TimeControl};
+use super::engine::{allocate_time, Engine, SearchLimits, SearchResult, StopHandle, MAX_HASH_SIZE};
+use super::fen::Fen;
+use super::turn::Turn;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, Scope, ScopedJoinHandle};
+use std::time::{Duration, Instant};
+
+/// Largest number of search threads that can be set with the `threads` parameter.
+const MAX_THREADS: usize = 256;
+/// Largest number of best turns that can be reported with the `multipv` parameter.
+const MAX_MULTI_PV: usize = 32;
+
+/// Type representing a line of the engine text protocol, sent either to or from the engine.
+///
+/// A message is a command followed by arguments, which are either a `name=value` pair or a flag
+/// with just a name. Values containing whitespace are surrounded by double quotes.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::hub::Message;
+/// let message: Message = r#"pos fen=W:W21:B1 moves="21-17 1-6""#.parse().unwrap();
+///
+/// assert_eq!(message.command, "pos");
+/// assert_eq!(message.get("moves"), Some("21-17 1-6"));
+/// assert_eq!(
+///     message.to_string(),
+///     r#"pos fen=W:W21:B1 moves="21-17 1-6""#
+/// );
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Message {
+    pub command: String,
+    /// Arguments as names and values. The value of a flag is empty.
+    pub args: Vec<(String, String)>,
+}
+
+/// Error returned when parsing a message fails.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseMessageError(String);
+
+/// Limits for searches started by `go`, set by the `level` command.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+struct Level {
+    depth: Option<u32>,
+    nodes: Option<u64>,
+    move_time: Option<Duration>,
+    time: Option<Duration>,
+    increment: Duration,
+    moves: Option<u32>,
+    infinite: bool,
+}
+
+/// Type representing a search running on another thread, which gives the engine back when it
+/// finishes.
+struct Search<'scope> {
+    thread: ScopedJoinHandle<'scope, Engine>,
+    stop: StopHandle,
+    /// Whether the search has a depth, node or time limit, so that it finishes without `stop`.
+    limited: bool,
+    /// Receiver that is disconnected when the search finishes, kept while pondering so that a
+    /// timer can be started on `ponder-hit`.
+    pondering: Option<Receiver<()>>,
+}
+
+/// State of a connection with a GUI.
+struct Session<'scope, W> {
+    output: Arc<Mutex<W>>,
+    threads: usize,
+    hash_size: usize,
+    multi_pv: usize,
+    engine: Option<Engine>,
+    search: Option<Search<'scope>>,
+    board: Board,
+    to_move: Player,
+    level: Level,
+}
+
+/// Runs the engine, reading commands from input and writing messages to output until the input
+/// ends or `quit` is received. When the input ends, a search with limits is finished first, so
+/// that piped commands get their reply.
+///
+/// The protocol is modelled on the Hub protocol used by draughts GUIs. Commands are:
+///
+/// - `hub`: replies with `id`, a `param` line for each parameter and `wait`.
+/// - `set-param name=<name> value=<value>`: sets the `threads` or `hash` (in megabytes)
+///   parameter, which take effect on `init`, or the `multipv` parameter, the number of best turns
+///   reported by later searches.
+/// - `init`: creates the engine with the current parameters and replies with `ready`.
+/// - `new-game`: clears the transposition table and sets up the initial position.
+/// - `pos [fen=<fen>] [moves="<turns>"]`: sets up the position from a FEN string (or the initial
+///   position) followed by the given turns.
+/// - `level [depth=<n>] [nodes=<n>] [move-time=<s>] [time=<s>] [inc=<s>] [moves=<n>] [infinite]`:
+///   sets the limits for searches, with times in seconds. `time`, `inc` and `moves` describe the
+///   engine's clock, from which the time for each turn is decided.
+/// - `go think`: searches the position and replies with `done move=<turn> ponder=<turn>`.
+/// - `go ponder` or `go analyze`: searches the position without a time limit until `stop`, or
+///   for pondering until the time for the turn has passed after `ponder-hit`.
+/// - `ponder-hit`: the predicted turn was played, so the search continues as if it was started by
+///   `go think`.
+/// - `stop`: stops the search, which replies with `done` straight away.
+/// - `ping`: replies with `pong`.
+/// - `quit`: stops the search and exits.
+///
+/// A search still running when `init`, `new-game`, `pos` or `go` is received is stopped first.
+///
+/// While searching, an `info` line is sent after each iteration with the depth, score (in men),
+/// nodes, time (in seconds), nodes per second and principal variation. When `multipv` is more
+/// than 1, an `info` line is sent for each of the best turns, numbered by `line`. An `error` line
+/// is sent for a command that can't be handled.
+///
+/// # Examples
+///
+/// ```
+/// let input = "pos fen=B:W18:B14\nlevel depth=3\ngo think\n";
+/// let mut output = Vec::new();
+/// bit_checkers::hub::run(input.as_bytes(), &mut output).unwrap();
+/// ```
+pub fn run<R, W>(input: R, output: W) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write + Send,
+{
+    // Searches run on a scoped thread so that output doesn't need to be 'static
+    thread::scope(|scope| {
+        let mut session = Session::new(output);
+        let mut quit = false;
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let keep_running = match line.parse::<Message>() {
+                Ok(message) => session.handle(scope, &message)?,
+                Err(error) => {
+                    session.error(&error.to_string())?;
+                    true
+                }
+            };
+            if !keep_running {
+                quit = true;
+                break;
+            }
+        }
+        match &session.search {
+            Some(search) if search.limited && !quit => session.wait(),
+            _ => session.stop(),
+        }
+        Ok(())
+    })
+}
+
+impl Message {
+    /// Returns a message with no arguments.
+    pub fn new(command: &str) -> Message {
+        Message {
+            command: command.to_string(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Returns the message with an argument added.
+    pub fn arg<T: fmt::Display>(mut self, name: &str, value: T) -> Message {
+        self.args.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Returns the message with a flag added.
+    pub fn flag(mut self, name: &str) -> Message {
+        self.args.push((name.to_string(), String::new()));
+        self
+    }
+
+    /// Returns the value of the first argument with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .find(|(arg, _)| arg == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the value of an argument parsed as a number, or None if it is missing or invalid.
+    fn number<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name).and_then(|value| value.parse().ok())
+    }
+
+    /// Returns the value of an argument parsed as a number of seconds.
+    fn seconds(&self, name: &str) -> Option<Duration> {
+        self.number::<f64>(name)
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+    }
+}
+
+impl FromStr for Message {
+    type Err = ParseMessageError;
+
+    /// Parses a message from a line of text.
+    fn from_str(s: &str) -> Result<Message, ParseMessageError> {
+        let error = || ParseMessageError(s.to_string());
+        let mut rest = s.trim();
+        let mut words = Vec::new();
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '"')
+                .unwrap_or(rest.len());
+            let (mut word, value) = (rest[..end].to_string(), &rest[end..]);
+            rest = if let Some(quoted) = value.strip_prefix('"') {
+                // A quoted value must follow `name=`
+                let close = quoted.find('"').ok_or_else(error)?;
+                if !word.ends_with('=') {
+                    return Err(error());
+                }
+                word.push_str(&quoted[..close]);
+                &quoted[close + 1..]
+            } else {
+                value
+            };
+            words.push(word);
+            rest = rest.trim_start();
+        }
+
+        let mut words = words.into_iter();
+        let command = words
+            .next()
+            .filter(|word| !word.contains('='))
+            .ok_or_else(error)?;
+        let args = words
+            .map(|word| match word.find('=') {
+                Some(0) => Err(error()),
+                Some(i) => Ok((word[..i].to_string(), word[i + 1..].to_string())),
+                None => Ok((word, String::new())),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Message { command, args })
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.command)?;
+        for (name, value) in &self.args {
+            if value.is_empty() {
+                write!(f, " {}", name)?;
+            } else if value.contains(char::is_whitespace) {
+                write!(f, " {}=\"{}\"", name, value)?;
+            } else {
+                write!(f, " {}={}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ParseMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid message \"{}\"", self.0)
+    }
+}
+
+impl Error for ParseMessageError {}
+
+impl Level {
+    /// Returns the level set by a `level` message.
+    fn from_message(message: &Message) -> Level {
+        Level {
+            depth: message.number("depth"),
+            nodes: message.number("nodes"),
+            move_time: message.seconds("move-time"),
+            time: message.seconds("time"),
+            increment: message.seconds("inc").unwrap_or_default(),
+            moves: message.number("moves").filter(|&moves| moves > 0),
+            infinite: message.get("infinite").is_some(),
+        }
+    }
+
+    /// Returns the time to spend on a turn, or None if there is no time limit.
+    fn turn_time(&self, player: Player) -> Option<Duration> {
+        if self.infinite {
+            return None;
+        }
+        self.move_time.or_else(|| {
+            self.time.map(|time| {
+                let control = match self.moves {
+                    Some(moves) => TimeControl::MovesPerPeriod { time, moves },
+                    None => TimeControl::Fischer {
+                        time,
+                        increment: self.increment,
+                    },
+                };
+                allocate_time(&Clock::new(control), player)
+            })
+        })
+    }
+}
+
+impl<'scope, W: Write + Send + 'scope> Session<'scope, W> {
+    fn new(output: W) -> Session<'scope, W> {
+        Session {
+            output: Arc::new(Mutex::new(output)),
+            threads: 1,
+            hash_size: 16,
+            multi_pv: 1,
+            engine: Some(Engine::new()),
+            search: None,
+            board: Board::new(),
+            to_move: Player1,
+            level: Level::default(),
+        }
+    }
+
+    /// Handles a message from the GUI, returning false if the engine should exit.
+    fn handle(&mut self, scope: &'scope Scope<'scope, '_>, message: &Message) -> io::Result<bool> {
+        match message.command.as_str() {
+            "hub" => {
+                let version = env!("CARGO_PKG_VERSION");
+                self.send(
+                    &Message::new("id")
+                        .arg("name", "bit_checkers")
+                        .arg("version", version),
+                )?;
+                self.send(&parameter("threads", self.threads, MAX_THREADS))?;
+                self.send(&parameter("hash", self.hash_size, MAX_HASH_SIZE))?;
+                self.send(&parameter("multipv", self.multi_pv, MAX_MULTI_PV))?;
+                self.send(&Message::new("wait"))?;
+            }
+            "set-param" => {
+                let value = message.number::<usize>("value").filter(|&value| value > 0);
+                match (message.get("name"), value) {
+                    (Some("threads"), Some(threads)) => self.threads = threads.min(MAX_THREADS),
+                    (Some("hash"), Some(size)) => self.hash_size = size.min(MAX_HASH_SIZE),
+                    (Some("multipv"), Some(lines)) => self.multi_pv = lines.min(MAX_MULTI_PV),
+                    _ => self.error(&format!("invalid parameter {}", message))?,
+                }
+            }
+            "init" => {
+                self.stop();
+                self.engine = Some(
+                    Engine::new()
+                        .with_threads(self.threads)
+                        .with_hash_size(self.hash_size),
+                );
+                self.send(&Message::new("ready"))?;
+            }
+            "new-game" => {
+                self.stop();
+                if let Some(engine) = &mut self.engine {
+                    engine.clear_hash();
+                }
+                self.board = Board::new();
+                self.to_move = Player1;
+            }
+            "pos" => {
+                self.stop();
+                match position(message) {
+                    Ok((board, to_move)) => {
+                        self.board = board;
+                        self.to_move = to_move;
+                    }
+                    Err(error) => self.error(&error)?,
+                }
+            }
+            "level" => self.level = Level::from_message(message),
+            "go" => {
+                self.stop();
+                if message.get("think").is_some() {
+                    self.start(scope, self.level.turn_time(self.to_move), false);
+                } else if message.get("ponder").is_some() || message.get("analyze").is_some() {
+                    self.start(scope, None, message.get("ponder").is_some());
+                } else {
+                    self.error(&format!("invalid mode {}", message))?;
+                }
+            }
+            "ponder-hit" => {
+                let time = self.level.turn_time(self.to_move);
+                let pondering = self.search.as_mut().and_then(|search| {
+                    let finished = search.pondering.take()?;
+                    // The search now has a time limit, so it is finished when the input ends
+                    search.limited |= time.is_some();
+                    Some((search.stop.clone(), finished))
+                });
+                if let (Some((stop, finished)), Some(time)) = (pondering, time) {
+                    // Stop the search when the time for the turn has passed, unless it finishes
+                    // first
+                    thread::spawn(move || {
+                        if let Err(RecvTimeoutError::Timeout) = finished.recv_timeout(time) {
+                            stop.stop();
+                        }
+                    });
+                }
+            }
+            "stop" => self.stop(),
+            "ping" => self.send(&Message::new("pong"))?,
+            "quit" => return Ok(false),
+            _ => self.error(&format!("unknown command {}", message.command))?,
+        }
+        Ok(true)
+    }
+
+    /// Starts searching the current position on another thread, with the level's limits and the
+    /// given time limit.
+    fn start(&mut self, scope: &'scope Scope<'scope, '_>, time: Option<Duration>, ponder: bool) {
+        let mut engine = self.engine.take().expect("engine should not be searching");
+        let stop = engine.stop_handle();
+        let (finished, pondering) = mpsc::channel::<()>();
+        let limits = SearchLimits {
+            depth: self.level.depth,
+            nodes: self.level.nodes,
+            time,
+        };
+        let limited = limits.depth.is_some() || limits.nodes.is_some() || limits.time.is_some();
+        let (board, to_move, output) = (self.board.clone(), self.to_move, self.output.clone());
+        let multi_pv = self.multi_pv;
+        let thread = scope.spawn(move || {
+            let _finished: Sender<()> = finished;
+            let started = Instant::now();
+            let lines = engine.analyse(&board, to_move, limits, multi_pv, |lines| {
+                for (index, line) in lines.iter().enumerate() {
+                    let mut message = info(line, started.elapsed());
+                    if multi_pv > 1 {
+                        message = message.arg("line", index + 1);
+                    }
+                    let _ = send(&output, &message);
+                }
+            });
+            let mut done = Message::new("done");
+            if let Some(result) = lines.first() {
+                if let Some(turn) = result.turn {
+                    done = done.arg("move", turn);
+                }
+                if let Some(turn) = result.pv.get(1) {
+                    done = done.arg("ponder", turn);
+                }
+            }
+            let _ = send(&output, &done);
+            engine
+        });
+        self.search = Some(Search {
+            thread,
+            stop,
+            limited,
+            pondering: if ponder { Some(pondering) } else { None },
+        });
+    }
+
+    /// Stops the search if one is running, waiting for it to finish.
+    fn stop(&mut self) {
+        if let Some(search) = &self.search {
+            search.stop.stop();
+        }
+        self.wait();
+    }
+
+    /// Waits for the search to finish if one is running.
+    fn wait(&mut self) {
+        if let Some(search) = self.search.take() {
+            let engine = search
+                .thread
+                .join()
+                .expect("search thread should not panic");
+            self.engine = Some(engine);
+        }
+    }
+
+    fn send(&self, message: &Message) -> io::Result<()> {
+        send(&self.output, message)
+    }
+
+    /// Sends an error message, with double quotes in the text replaced so that it can be quoted.
+    fn error(&self, text: &str) -> io::Result<()> {
+        self.send(&Message::new("error").arg("message", text.replace('"', "'")))
+    }
+}
+
+/// Writes a message as a line of output.
+fn send<W: Write>(output: &Mutex<W>, message: &Message) -> io::Result<()> {
+    let mut output = output.lock().expect("output should not be poisoned");
+    writeln!(output, "{}", message)?;
+    output.flush()
+}
+
+/// Returns a `param` message describing an integer parameter.
+fn parameter(name: &str, value: usize, max: usize) -> Message {
+    Message::new("param")
+        .arg("name", name)
+        .arg("value", value)
+        .arg("type", "int")
+        .arg("min", 1)
+        .arg("max", max)
+}
+
+/// Returns an `info` message for the result of a search iteration.
+fn info(result: &SearchResult, elapsed: Duration) -> Message {
+    let seconds = elapsed.as_secs_f64();
+    let pv: Vec<String> = result.pv.iter().map(Turn::to_string).collect();
+    Message::new("info")
+        .arg("depth", result.depth)
+        .arg("score", format!("{:.2}", result.score as f64 / 100.0))
+        .arg("nodes", result.nodes)
+        .arg("time", format!("{:.3}", seconds))
+        .arg("nps", (result.nodes as f64 / seconds.max(0.001)) as u64)
+        .arg("pv", pv.join(" "))
+}
+
+/// Returns the board and player to move set up by a `pos` message.
+fn position(message: &Message) -> Result<(Board, Player), String> {
+    let fen = match message.get("fen") {
+        Some(fen) => fen
+            .parse()
+            .map_err(|error| format!("invalid position: {}", error))?,
+        None => Fen::new(),
+    };
+    let (mut board, mut to_move) = (fen.board, fen.to_move);
+    for notation in message.get("moves").unwrap_or("").split_whitespace() {
+        let turn = notation
+            .parse()
+            .ok()
+            .and_then(|notation| board.find_turn(to_move, &notation))
+            .ok_or_else(|| format!("illegal move {}", notation))?;
+        board = board.apply_turn(to_move, turn);
+        to_move = to_move.opponent();
+    }
+    Ok((board, to_move))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Runs the engine with the given input lines, returning the lines of output.
+    fn run_lines(input: &[&str]) -> Vec<String> {
+        let mut output = Vec::new();
+        run(input.join("\n").as_bytes(), &mut output).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn should_parse_and_format_messages() {
+        let message: Message = r#"  level  depth=6 infinite pv="11-15 23-19" time=1.5 "#
+            .parse()
+            .unwrap();
+
+        assert_eq!(message.command, "level");
+        assert_eq!(message.get("depth"), Some("6"));
+        assert_eq!(message.get("infinite"), Some(""));
+        assert_eq!(message.get("pv"), Some("11-15 23-19"));
+        assert_eq!(message.seconds("time"), Some(Duration::from_millis(1500)));
+        assert_eq!(
+            message.to_string(),
+            r#"level depth=6 infinite pv="11-15 23-19" time=1.5"#
+        );
+        assert!("".parse::<Message>().is_err());
+        assert!("pos moves=\"11-15".parse::<Message>().is_err());
+        assert!("pos =1".parse::<Message>().is_err());
+        assert!("pos moves\"11-15\"".parse::<Message>().is_err());
+        let level: Message = "level time=1e30 inc=-1 move-time=inf".parse().unwrap();
+        assert_eq!(level.seconds("time"), None);
+        assert_eq!(level.seconds("inc"), None);
+        assert_eq!(level.seconds("move-time"), None);
+    }
+
+    #[test]
+    fn should_set_up_positions() {
+        let message: Message = r#"pos fen=W:W21,22:B9 moves="22-18 9-14""#.parse().unwrap();
+        let (board, to_move) = position(&message).unwrap();
+
+        assert_eq!(to_move, Player::Player2);
+        assert_eq!(Fen { board, to_move }.to_string(), "W:W18,21:B14");
+        assert!(position(&"pos moves=11-17".parse().unwrap()).is_err());
+        assert!(position(&"pos fen=X".parse().unwrap()).is_err());
+        assert!(position(&"pos fen=B:W2:B30".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn should_think_and_reply() {
+        let output = run_lines(&[
+            "hub",
+            "set-param name=threads value=2",
+            "init",
+            "pos fen=B:W18,30:B14,K1",
+            "level depth=3",
+            "ping",
+            "foo",
+            "go think",
+        ]);
+
+        assert_eq!(output[0], "id name=bit_checkers version=0.1.0");
+        assert_eq!(
+            output[1],
+            "param name=threads value=1 type=int min=1 max=256"
+        );
+        assert_eq!(output[4], "wait");
+        assert_eq!(output[5], "ready");
+        assert_eq!(output[6], "pong");
+        assert_eq!(output[7], r#"error message="unknown command foo""#);
+        assert!(output[8].starts_with("info depth=1 score="));
+        assert!(output[10].starts_with("info depth=3 "));
+        assert!(output[11].starts_with("done move=14x23 ponder="));
+    }
+
+    #[test]
+    fn should_stop_analysis_for_new_commands() {
+        let output = run_lines(&[
+            "hub",
+            "init",
+            "go analyze",
+            "new-game",
+            "go analyze",
+            "pos",
+            "ping",
+        ]);
+
+        assert_eq!(
+            output
+                .iter()
+                .filter(|line| line.starts_with("done"))
+                .count(),
+            2
+        );
+        assert_eq!(output[output.len() - 1], "pong");
+    }
+
+    #[test]
+    fn should_report_multiple_lines() {
+        let output = run_lines(&[
+            "set-param name=multipv value=3",
+            "level depth=2",
+            "go think",
+        ]);
+
+        assert_eq!(output.len(), 7);
+        assert!(output[3].starts_with("info depth=2 "));
+        assert!(output[3].ends_with(" line=1"));
+        assert!(output[5].ends_with(" line=3"));
+        assert!(output[6].starts_with("done move="));
+    }
+
+    #[test]
+    fn should_stop_pondering_after_ponder_hit() {
+        let output = run_lines(&[
+            "level move-time=0.05",
+            "go ponder",
+            "ponder-hit",
+            "new-game",
+            "ping",
+        ]);
+
+        assert!(output[output.len() - 2].starts_with("done move="));
+        assert_eq!(output[output.len() - 1], "pong");
+    }
+
+    #[test]
+    fn should_finish_ponder_hit_searches_at_end_of_input() {
+        let output = run_lines(&["level move-time=0.05", "go ponder", "ponder-hit"]);
+
+        assert!(output[output.len() - 1].starts_with("done move="));
+    }
+}
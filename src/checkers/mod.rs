@@ -1,2 +1,47 @@
+#[cfg(feature = "std")]
+pub mod arena;
+#[cfg(feature = "std")]
+pub mod args;
+#[cfg(feature = "std")]
+pub mod ballot;
 pub mod board;
+#[cfg(feature = "std")]
+pub mod book;
+#[cfg(feature = "checkerboard")]
+pub mod checkerboard;
+#[cfg(feature = "std")]
+pub mod cli;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(feature = "std")]
+pub mod diagram;
+#[cfg(feature = "std")]
+pub mod dxp;
+#[cfg(feature = "std")]
+pub mod encoding;
+#[cfg(feature = "std")]
+pub mod engine;
+#[cfg(feature = "std")]
+pub mod fen;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod game;
+#[cfg(feature = "std")]
+pub mod hub;
+#[cfg(feature = "std")]
+pub mod pdn;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "serde")]
+pub mod serialization;
+#[cfg(feature = "std")]
+pub mod svg;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod turn;
 pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
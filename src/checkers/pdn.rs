@@ -0,0 +1,799 @@
+use super::board::{Board, Player, Player1, Player2};
+use super::fen::{Fen, FenError};
+use super::game::{Game, Outcome};
+use super::turn::Turn;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Maximum length of a line of move text written by `PdnGame`.
+const MAX_LINE_LENGTH: usize = 79;
+
+/// Move suffixes and the NAGs (numeric annotation glyphs) they are equivalent to.
+const SUFFIXES: [(&str, u8); 6] = [
+    ("!", 1),
+    ("?", 2),
+    ("!!", 3),
+    ("??", 4),
+    ("!?", 5),
+    ("?!", 6),
+];
+
+/// Type representing a single game read from or written as PDN (Portable Draughts Notation).
+#[derive(Clone, PartialEq, Debug)]
+pub struct PdnGame {
+    /// Tag pairs from the header of the game, in the order they appear.
+    pub tags: Vec<(String, String)>,
+    /// Comment before the first move, if any.
+    pub comment: Option<String>,
+    /// Moves of the main line.
+    pub moves: Vec<PdnMove>,
+    /// Result token terminating the move text (e.g. `1-0` or `*`), if there was one.
+    pub result: Option<String>,
+}
+
+/// Type representing a move in the move text of a game, along with its annotations.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PdnMove {
+    /// The move as written in the move text.
+    ///
+    /// Multi-jumps may be written with only their end points, see `Board::find_turn`.
+    pub turn: Turn,
+    /// NAGs following the move, including those written as suffixes such as `!?`.
+    pub nags: Vec<u8>,
+    /// Comment following the move, if any.
+    pub comment: Option<String>,
+    /// Alternatives to the move, each played from the position before it.
+    pub variations: Vec<Variation>,
+}
+
+/// Type representing a variation, a sequence of moves given as an alternative to a move.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Variation {
+    /// Comment before the first move of the variation, if any.
+    pub comment: Option<String>,
+    /// Moves of the variation.
+    pub moves: Vec<PdnMove>,
+}
+
+/// Error returned when reading PDN fails.
+#[derive(Clone, PartialEq, Debug)]
+pub enum PdnError {
+    /// The text could not be parsed. Contains the line number and a description of the problem.
+    Syntax(usize, String),
+    /// A move could not be played. Contains the index of the move within the game and its
+    /// notation.
+    IllegalMove(usize, Turn),
+    /// The `FEN` tag giving the starting position is invalid.
+    InvalidFen(FenError),
+}
+
+/// Lexical element of PDN text.
+enum Token {
+    Tag(String, String),
+    Comment(String),
+    Nag(u8),
+    Move(Turn, Option<u8>),
+    OpenVariation,
+    CloseVariation,
+    Result(String),
+}
+
+/// Iterator over the tokens of PDN text and the line each starts on.
+struct Tokens<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl PdnGame {
+    /// Returns a game with the given turns as its main line and no tags.
+    pub fn from_turns<I: IntoIterator<Item = Turn>>(turns: I) -> PdnGame {
+        PdnGame {
+            tags: Vec::new(),
+            comment: None,
+            moves: turns.into_iter().map(PdnMove::new).collect(),
+            result: None,
+        }
+    }
+
+    /// Returns a record of the turns played in a game, with a `Result` tag and result token.
+    ///
+    /// A win for Player1 is written as `1-0`, a win for Player2 as `0-1` and a draw as
+    /// `1/2-1/2`. A game in progress has the result `*`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut game = bit_checkers::game::Game::new();
+    /// game.play("11-15".parse().unwrap()).unwrap();
+    /// let pdn = bit_checkers::pdn::PdnGame::from_game(&game);
+    ///
+    /// assert_eq!(pdn.to_string(), "[Result \"*\"]\n\n1. 11-15 *\n");
+    /// ```
+    pub fn from_game(game: &Game) -> PdnGame {
+        let result = match game.outcome() {
+            Some(Outcome::Win(Player1)) => "1-0",
+            Some(Outcome::Win(Player2)) => "0-1",
+            Some(Outcome::Draw) => "1/2-1/2",
+            None => "*",
+        };
+        let mut pdn = PdnGame::from_turns(game.turns().iter().copied());
+        pdn.tags.push(("Result".to_string(), result.to_string()));
+        pdn.result = Some(result.to_string());
+        pdn
+    }
+
+    /// Returns the value of the tag with the given name, if present.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the starting position of the game, given by the `FEN` tag if present or otherwise
+    /// the initial board with Player1 to move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::Player2;
+    /// let games = bit_checkers::pdn::parse_games("[FEN \"W:W21:B1\"] 1... 21-17 *").unwrap();
+    /// let fen = games[0].start().unwrap();
+    ///
+    /// assert_eq!(fen.to_move, Player2);
+    /// assert_eq!(games[0].replay().unwrap().len(), 1);
+    /// ```
+    pub fn start(&self) -> Result<Fen, PdnError> {
+        match self.tag("FEN") {
+            Some(fen) => fen.parse().map_err(PdnError::InvalidFen),
+            None => Ok(Fen::new()),
+        }
+    }
+
+    /// Returns the complete turns of the main line, resolved by playing the moves from the
+    /// starting position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let games = bit_checkers::pdn::parse_games("1. 11-15 23-19 2. 8-11 22-17 *").unwrap();
+    /// let turns = games[0].replay().unwrap();
+    ///
+    /// assert_eq!(turns.len(), 4);
+    /// ```
+    pub fn replay(&self) -> Result<Vec<Turn>, PdnError> {
+        let Fen { board, to_move } = self.start()?;
+        replay_moves(board, to_move, &self.moves)
+    }
+
+    /// Writes the game as PDN text, followed by a blank line.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", self)
+    }
+}
+
+impl PdnMove {
+    /// Returns a move without annotations.
+    pub fn new(turn: Turn) -> PdnMove {
+        PdnMove {
+            turn,
+            nags: Vec::new(),
+            comment: None,
+            variations: Vec::new(),
+        }
+    }
+}
+
+impl Variation {
+    /// Returns the complete turns of the variation, resolved by playing the moves from the given
+    /// board, which is the position before the move the variation is an alternative to.
+    pub fn replay(&self, board: Board, player: Player) -> Result<Vec<Turn>, PdnError> {
+        replay_moves(board, player, &self.moves)
+    }
+}
+
+/// Parses every game in a PDN collection.
+///
+/// # Examples
+///
+/// ```
+/// let text = r#"
+/// [Event "Example"]
+/// [Result "1-0"]
+/// 1. 11-15 24-20 2. 8-11 {Ayrshire Lassie} 28-24 1-0
+///
+/// [Event "Another"]
+/// 1. 9-14 22-17 (1... 23-18 14x23) 2. 11-15! *
+/// "#;
+/// let games = bit_checkers::pdn::parse_games(text).unwrap();
+///
+/// assert_eq!(games.len(), 2);
+/// assert_eq!(games[0].tag("Event"), Some("Example"));
+/// assert_eq!(games[0].moves[2].comment.as_deref(), Some("Ayrshire Lassie"));
+/// assert_eq!(games[1].moves[1].variations[0].moves.len(), 2);
+/// assert_eq!(games[1].moves[2].nags, vec![1]);
+/// ```
+pub fn parse_games(text: &str) -> Result<Vec<PdnGame>, PdnError> {
+    let mut tokens = Tokens {
+        chars: text.chars().peekable(),
+        line: 1,
+    }
+    .peekable();
+    let mut games = Vec::new();
+    while let Some(game) = parse_game(&mut tokens)? {
+        games.push(game);
+    }
+    Ok(games)
+}
+
+/// Writes every game in a collection as PDN text, separated by blank lines.
+pub fn write_games<W: Write>(mut writer: W, games: &[PdnGame]) -> io::Result<()> {
+    for game in games {
+        game.write(&mut writer)?;
+    }
+    Ok(())
+}
+
+/// Parses the next game, or returns None if there is no more text.
+fn parse_game(tokens: &mut Peekable<Tokens>) -> Result<Option<PdnGame>, PdnError> {
+    let mut game = PdnGame::from_turns(None);
+    let mut comments = Vec::new();
+    while let Some(Ok((_, Token::Tag(..)))) | Some(Ok((_, Token::Comment(_)))) = tokens.peek() {
+        match tokens.next() {
+            Some(Ok((_, Token::Tag(name, value)))) => game.tags.push((name, value)),
+            Some(Ok((_, Token::Comment(comment)))) => comments.push(comment),
+            _ => unreachable!(),
+        }
+    }
+
+    let line = parse_line(tokens, None)?;
+    comments.extend(line.comment);
+    game.comment = join_comments(comments);
+    game.moves = line.moves;
+    if let Some(Ok((_, Token::Result(_)))) = tokens.peek() {
+        if let Some(Ok((_, Token::Result(result)))) = tokens.next() {
+            game.result = Some(result);
+        }
+        // Comments after the result belong to the game that has just finished
+        while let Some(Ok((_, Token::Comment(_)))) = tokens.peek() {
+            if let Some(Ok((_, Token::Comment(comment)))) = tokens.next() {
+                let target = match game.moves.last_mut() {
+                    Some(last) => &mut last.comment,
+                    None => &mut game.comment,
+                };
+                *target = join_comments(target.take().into_iter().chain(Some(comment)));
+            }
+        }
+    }
+
+    if game.tags.is_empty() && game.moves.is_empty() && game.result.is_none() {
+        Ok(Some(game).filter(|game| game.comment.is_some()))
+    } else {
+        Ok(Some(game))
+    }
+}
+
+/// Parses moves and their annotations up to the end of a variation opened on the given line, or
+/// for the main line up to the result or start of the next game.
+fn parse_line(tokens: &mut Peekable<Tokens>, opened: Option<usize>) -> Result<Variation, PdnError> {
+    let unclosed = |line| PdnError::Syntax(line, "unclosed variation".to_string());
+    let mut variation = Variation {
+        comment: None,
+        moves: Vec::new(),
+    };
+    loop {
+        match tokens.peek() {
+            None => match opened {
+                Some(line) => return Err(unclosed(line)),
+                None => break,
+            },
+            Some(Ok((line, Token::Tag(..)))) | Some(Ok((line, Token::Result(_))))
+                if opened.is_some() =>
+            {
+                return Err(unclosed(*line))
+            }
+            Some(Ok((_, Token::Tag(..)))) | Some(Ok((_, Token::Result(_)))) => break,
+            Some(Ok((line, Token::CloseVariation))) if opened.is_none() => {
+                return Err(PdnError::Syntax(*line, "unexpected \")\"".to_string()))
+            }
+            _ => {}
+        }
+        let (line, token) = tokens.next().unwrap()?;
+        let last = variation.moves.last_mut();
+        match token {
+            Token::Move(turn, nag) => {
+                let mut pdn_move = PdnMove::new(turn);
+                pdn_move.nags.extend(nag);
+                variation.moves.push(pdn_move);
+            }
+            Token::Comment(comment) => {
+                let target = match last {
+                    Some(last) => &mut last.comment,
+                    None => &mut variation.comment,
+                };
+                *target = join_comments(target.take().into_iter().chain(Some(comment)));
+            }
+            Token::Nag(nag) => last
+                .ok_or_else(|| PdnError::Syntax(line, "annotation before first move".to_string()))?
+                .nags
+                .push(nag),
+            Token::OpenVariation => {
+                let last = last.ok_or_else(|| {
+                    PdnError::Syntax(line, "variation before first move".to_string())
+                })?;
+                last.variations.push(parse_line(tokens, Some(line))?);
+            }
+            Token::CloseVariation => return Ok(variation),
+            Token::Tag(..) | Token::Result(_) => unreachable!(),
+        }
+    }
+    Ok(variation)
+}
+
+/// Returns the comments joined by spaces, or None if there are none.
+fn join_comments<I: IntoIterator<Item = String>>(comments: I) -> Option<String> {
+    comments
+        .into_iter()
+        .fold(None, |joined, comment| match joined {
+            Some(joined) => Some(format!("{} {}", joined, comment)),
+            None => Some(comment),
+        })
+}
+
+/// Returns the complete turns for a sequence of moves played from the given board.
+fn replay_moves(
+    mut board: Board,
+    mut player: Player,
+    moves: &[PdnMove],
+) -> Result<Vec<Turn>, PdnError> {
+    let mut turns = Vec::with_capacity(moves.len());
+    for (i, pdn_move) in moves.iter().enumerate() {
+        let turn = board
+            .find_turn(player, &pdn_move.turn)
+            .ok_or(PdnError::IllegalMove(i, pdn_move.turn))?;
+        board = board.apply_turn(player, turn);
+        player = player.opponent();
+        turns.push(turn);
+    }
+    Ok(turns)
+}
+
+impl<'a> Tokens<'a> {
+    /// Returns the characters up to the terminator, consuming the terminator.
+    fn take_until(&mut self, terminator: char) -> Option<String> {
+        let mut text = String::new();
+        loop {
+            match self.chars.next()? {
+                c if c == terminator => return Some(text),
+                c => {
+                    if c == '\n' {
+                        self.line += 1;
+                    }
+                    text.push(c);
+                }
+            }
+        }
+    }
+
+    /// Returns the tag pair starting after an opening bracket.
+    fn tag(&mut self, line: usize) -> Result<Token, PdnError> {
+        let mut text = String::new();
+        let mut value = None;
+        let mut escaped = false;
+        loop {
+            let c = self
+                .chars
+                .next()
+                .ok_or_else(|| PdnError::Syntax(line, format!("unclosed tag pair \"{}\"", text)))?;
+            match (c, value.as_mut()) {
+                ('\n', _) => {
+                    return Err(PdnError::Syntax(
+                        line,
+                        format!("unclosed tag pair \"{}\"", text),
+                    ))
+                }
+                ('"', None) => value = Some(String::new()),
+                (']', None) => break,
+                (c, None) => text.push(c),
+                ('\\', Some(_)) if !escaped => escaped = true,
+                ('"', Some(_)) if !escaped => {
+                    let name = text.trim();
+                    while self.chars.next_if(|&c| c == ' ' || c == '\t').is_some() {}
+                    if self.chars.next() == Some(']') && !name.is_empty() && text != name {
+                        return Ok(Token::Tag(name.to_string(), value.unwrap()));
+                    }
+                    break;
+                }
+                (c, Some(value)) => {
+                    escaped = false;
+                    value.push(c);
+                }
+            }
+        }
+        Err(PdnError::Syntax(
+            line,
+            format!("invalid tag pair \"{}\"", text.trim()),
+        ))
+    }
+
+    /// Returns the token formed by a word of move text starting with the given character, or None
+    /// if it should be ignored.
+    fn word(&mut self, first: char, line: usize) -> Result<Option<Token>, PdnError> {
+        let mut word = first.to_string();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() || "[]{}();".contains(c) {
+                break;
+            }
+            word.push(c);
+            self.chars.next();
+        }
+        let error = || PdnError::Syntax(line, format!("unexpected token \"{}\"", word));
+        if is_result(&word) {
+            return Ok(Some(Token::Result(word)));
+        }
+        if is_move_number(&word) {
+            return Ok(None);
+        }
+        if let Some(nag) = word.strip_prefix('$') {
+            return nag
+                .parse()
+                .map(|nag| Some(Token::Nag(nag)))
+                .map_err(|_| error());
+        }
+        let notation = match word.rfind('.') {
+            Some(i) if is_move_number(&word[..=i]) => &word[i + 1..],
+            _ => &word,
+        };
+        let split = notation.find(&['!', '?'][..]).unwrap_or(notation.len());
+        let (notation, suffix) = notation.split_at(split);
+        if !notation.starts_with(|c: char| c.is_ascii_digit())
+            || !notation.contains(&['-', 'x', ':'][..])
+        {
+            return Err(error());
+        }
+        let turn = notation
+            .parse()
+            .map_err(|_| PdnError::Syntax(line, format!("invalid move \"{}\"", word)))?;
+        let nag = match suffix {
+            "" => None,
+            suffix => Some(suffix_nag(suffix).ok_or_else(error)?),
+        };
+        Ok(Some(Token::Move(turn, nag)))
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<(usize, Token), PdnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.line;
+            let token = match self.chars.next()? {
+                '\n' => {
+                    self.line += 1;
+                    continue;
+                }
+                c if c.is_whitespace() => continue,
+                '[' => self.tag(line),
+                '{' => match self.take_until('}') {
+                    Some(comment) => Ok(Token::Comment(comment.trim().to_string())),
+                    None => Err(PdnError::Syntax(line, "unclosed comment".to_string())),
+                },
+                ';' => {
+                    let comment = self.take_until('\n').unwrap_or_default();
+                    self.line += 1;
+                    Ok(Token::Comment(comment.trim().to_string()))
+                }
+                '(' => Ok(Token::OpenVariation),
+                ')' => Ok(Token::CloseVariation),
+                c => match self.word(c, line) {
+                    Ok(Some(token)) => Ok(token),
+                    Ok(None) => continue,
+                    Err(error) => Err(error),
+                },
+            };
+            return Some(token.map(|token| (line, token)));
+        }
+    }
+}
+
+/// Appends the move text tokens for a sequence of moves starting at the given ply.
+///
+/// Move numbers are written before each of Player1's moves, and before Player2's move if it
+/// starts the sequence or follows a comment or variation.
+fn move_text(comment: &Option<String>, moves: &[PdnMove], ply: usize, text: &mut Vec<String>) {
+    text.extend(comment.iter().map(|comment| format!("{{{}}}", comment)));
+    let mut numbered = false;
+    for (i, pdn_move) in moves.iter().enumerate() {
+        let ply = ply + i;
+        if ply.is_multiple_of(2) {
+            text.push(format!("{}.", ply / 2 + 1));
+        } else if !numbered {
+            text.push(format!("{}...", ply / 2 + 1));
+        }
+        let mut nags = pdn_move.nags.iter();
+        let suffix = SUFFIXES
+            .iter()
+            .find(|(_, nag)| pdn_move.nags.first() == Some(nag))
+            .map(|(suffix, _)| {
+                nags.next();
+                *suffix
+            });
+        text.push(format!("{}{}", pdn_move.turn, suffix.unwrap_or_default()));
+        text.extend(nags.map(|nag| format!("${}", nag)));
+        text.extend(
+            pdn_move
+                .comment
+                .iter()
+                .map(|comment| format!("{{{}}}", comment)),
+        );
+        for variation in &pdn_move.variations {
+            let start = text.len();
+            move_text(&variation.comment, &variation.moves, ply, text);
+            if text.len() == start {
+                text.push("()".to_string());
+            } else {
+                text[start].insert(0, '(');
+                text.last_mut().unwrap().push(')');
+            }
+        }
+        numbered = pdn_move.comment.is_none() && pdn_move.variations.is_empty();
+    }
+}
+
+impl fmt::Display for PdnGame {
+    /// Formats the game as PDN text, with the move text wrapped to fit within 80 columns.
+    ///
+    /// A game without a result is terminated with `*`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.tags {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{} \"{}\"]", name, value)?;
+        }
+        if !self.tags.is_empty() {
+            writeln!(f)?;
+        }
+        let mut text = Vec::new();
+        let ply = match self.start() {
+            Ok(Fen {
+                to_move: Player2, ..
+            }) => 1,
+            _ => 0,
+        };
+        move_text(&self.comment, &self.moves, ply, &mut text);
+        text.push(self.result.clone().unwrap_or_else(|| "*".to_string()));
+        let mut length = 0;
+        for token in text {
+            if length > 0 && length + 1 + token.len() > MAX_LINE_LENGTH {
+                writeln!(f)?;
+                length = 0;
+            }
+            if length > 0 {
+                write!(f, " ")?;
+                length += 1;
+            }
+            write!(f, "{}", token)?;
+            length += token.len();
+        }
+        writeln!(f)
+    }
+}
+
+impl fmt::Display for PdnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdnError::Syntax(line, message) => write!(f, "line {}: {}", line, message),
+            PdnError::IllegalMove(index, turn) => {
+                write!(f, "move {} ({}) is not legal", index + 1, turn)
+            }
+            PdnError::InvalidFen(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for PdnError {}
+
+fn is_result(token: &str) -> bool {
+    ["1-0", "0-1", "2-0", "0-2", "1-1", "1/2-1/2", "0-0", "*"].contains(&token)
+}
+
+fn is_move_number(token: &str) -> bool {
+    let digits = token.trim_end_matches('.');
+    digits.len() < token.len() && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn suffix_nag(suffix: &str) -> Option<u8> {
+    SUFFIXES
+        .iter()
+        .find(|(text, _)| *text == suffix)
+        .map(|&(_, nag)| nag)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COLLECTION: &str = r#"[Event "Club Championship"]
+[Date "2020.03.14"]
+[Black "A. Player"]
+[White "B. Player \"Junior\""]
+[Result "*"]
+{Single corner opening}
+1. 11-15 23-18 2. 9-13 18x11 3. 7x16 24-19 4. 16x23 27x18 5. 10-15 18x11
+6. 8x15 22-18 7. 15x22 25x18 8. 6-9 18-15 9. 12-16 26-23
+(9... 21-17 13x22 26x17 {keeps the material even}) 10. 13-17 21x14
+11. 9x27 $14 32x23 12. 16-20 23-18 13. 2-6?! (13. 5-9 28-24 20x27) 13... 28-24
+14. 20x27 31x24 15. 6-10 15x6 16. 1x10 24-19 (16... 24-20 10-14 18x9)
+17. 5-9 18-15 ; White is better
+18. 9-14 15x6 19. 14-18 19-15 20. 3-7 (20. 18-23 6-1 23-27) 20... 30-25 *
+
+[Event "Club Championship"]
+[Black "C. Player"]
+[White "D. Player"]
+[Result "1/2-1/2"]
+1. 9-13 24-19 2. 10-14 19-15 (2... 23-18 14x23 27x18) 3. 11x18 22x15
+4. 7-10 27-24 5. 10x19 23x16 6. 12x19 24x15 7. 5-9 25-22 8. 14-18 21-17
+9. 18x25 29x22 (9... 30x21 13x22 26x17) 10. 6-10! 15x6 11. 1x10 26-23
+12. 9-14 22-18 13. 13x22 (13. 14x21 {forced} 23-19 8-11) 13... 18x9 14. 8-12 9-5
+15. 10-14 23-19 16. 14-17 28-24 (16... 19-15 12-16 5-1) 17. 17-21 5-1
+18. 3-7 19-15 19. 7-10 15x6 20. 2x9 1-6 {Drawn by agreement} 1/2-1/2
+"#;
+
+    /// Replays the moves from the board, along with every variation.
+    fn replay_all(mut board: Board, mut player: Player, moves: &[PdnMove]) {
+        let turns = replay_moves(board.clone(), player, moves).unwrap();
+        for (pdn_move, turn) in moves.iter().zip(turns) {
+            for variation in &pdn_move.variations {
+                replay_all(board.clone(), player, &variation.moves);
+            }
+            board = board.apply_turn(player, turn);
+            player = player.opponent();
+        }
+    }
+
+    #[test]
+    fn should_parse_move_text() {
+        let games = parse_games("1.11-15 23-19 2.9-14?! 27-23! $1 3...14x23 *").unwrap();
+
+        assert_eq!(games.len(), 1);
+        let notation: Vec<String> = games[0].moves.iter().map(|m| m.turn.to_string()).collect();
+        assert_eq!(notation, vec!["11-15", "23-19", "9-14", "27-23", "14x23"]);
+        assert_eq!(games[0].moves[2].nags, vec![6]);
+        assert_eq!(games[0].moves[3].nags, vec![1, 1]);
+        assert_eq!(games[0].result, Some("*".to_string()));
+    }
+
+    #[test]
+    fn should_parse_collection() {
+        let games = parse_games(COLLECTION).unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].tag("White"), Some("B. Player \"Junior\""));
+        assert_eq!(games[0].comment.as_deref(), Some("Single corner opening"));
+        assert_eq!(games[0].moves.len(), 40);
+        assert_eq!(games[0].moves[20].nags, vec![14]);
+        assert_eq!(
+            games[0].moves[33].comment.as_deref(),
+            Some("White is better")
+        );
+        assert_eq!(
+            games[0].moves[17].variations[0].moves[2].comment.as_deref(),
+            Some("keeps the material even")
+        );
+        assert_eq!(games[1].result, Some("1/2-1/2".to_string()));
+        for game in &games {
+            replay_all(Board::new(), Player1, &game.moves);
+        }
+        assert_eq!(games[0].replay().unwrap()[20].to_string(), "9x18x27");
+    }
+
+    #[test]
+    fn should_allow_spaces_in_tag_pairs() {
+        let games = parse_games("[ Event \"x\" ]\n[Site\t\"y\"\t]\n1. 11-15 *").unwrap();
+
+        assert_eq!(games[0].tag("Event"), Some("x"));
+        assert_eq!(games[0].tag("Site"), Some("y"));
+        assert!(parse_games("[Event \"x\" y]").is_err());
+    }
+
+    #[test]
+    fn should_round_trip_collection() {
+        let games = parse_games(COLLECTION).unwrap();
+        let mut text = Vec::new();
+        write_games(&mut text, &games).unwrap();
+        let text = String::from_utf8(text).unwrap();
+
+        assert!(text.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert_eq!(parse_games(&text).unwrap(), games);
+        assert!(text.ends_with("{Drawn by agreement} 1/2-1/2\n\n"));
+    }
+
+    #[test]
+    fn should_write_move_numbers() {
+        let games = parse_games("1. 11-15 (9-13 22-18) 23-19 {main line} 8-11?? $10 *").unwrap();
+
+        assert_eq!(
+            games[0].to_string(),
+            "1. 11-15 (1. 9-13 22-18) 1... 23-19 {main line} 2. 8-11?? $10 *\n"
+        );
+    }
+
+    #[test]
+    fn should_start_from_fen() {
+        let text = "[FEN \"W:WK10,19:B1,5\"]\n\n1... 10-6 2. 1x10 19-15 *\n";
+        let games = parse_games(text).unwrap();
+
+        assert_eq!(games[0].replay().unwrap().len(), 3);
+        assert_eq!(games[0].to_string(), text);
+        assert_eq!(
+            parse_games("[FEN \"W:W:W\"] *").unwrap()[0].replay(),
+            Err(PdnError::InvalidFen(FenError::InvalidColor(
+                "W".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn should_split_games_without_results() {
+        let games = parse_games("[Event \"1\"]\n1. 11-15\n[Event \"2\"]\n1. 9-13").unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[1].tag("Event"), Some("2"));
+        assert_eq!(games[1].result, None);
+    }
+
+    #[test]
+    fn should_keep_comments_after_results() {
+        let games =
+            parse_games("[Event \"x\"] 1. 11-15 23-19 1-0 {final comment}\n[Event \"y\"] *")
+                .unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].moves[1].comment.as_deref(), Some("final comment"));
+        assert_eq!(games[1].tag("Event"), Some("y"));
+        assert_eq!(
+            parse_games("1-0 {no moves}").unwrap()[0].comment.as_deref(),
+            Some("no moves")
+        );
+    }
+
+    #[test]
+    fn should_report_errors() {
+        assert_eq!(
+            parse_games("1. 11-15\n2. 8-11 foo"),
+            Err(PdnError::Syntax(2, "unexpected token \"foo\"".to_string()))
+        );
+        assert_eq!(
+            parse_games("[Event]"),
+            Err(PdnError::Syntax(
+                1,
+                "invalid tag pair \"Event\"".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_games("1. 11-15\n(1. 9-13 *"),
+            Err(PdnError::Syntax(2, "unclosed variation".to_string()))
+        );
+        assert_eq!(
+            parse_games("1. 11-15 (1. 9-13\n"),
+            Err(PdnError::Syntax(1, "unclosed variation".to_string()))
+        );
+        let games = parse_games("1. 11-15 22-18 2. 8-11").unwrap();
+        assert_eq!(
+            games[0].replay(),
+            Err(PdnError::IllegalMove(2, "8-11".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn should_replay_multi_jumps() {
+        let games = parse_games("1. 11-16 22-17 2. 8-11 25-22 3. 16-19 24x8 *").unwrap();
+        let turns = games[0].replay().unwrap();
+
+        assert_eq!(turns.len(), 6);
+        assert_eq!(turns[5].to_string(), "24x15x8");
+    }
+}
@@ -0,0 +1,358 @@
+use super::board::{Board, Player, Player1, Player2};
+use super::engine::{Engine, SearchLimits, SearchResult, MAX_HASH_SIZE};
+use super::fen::Fen;
+use super::pdn::{self, PdnGame};
+use super::turn::Turn;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::time::Duration;
+
+/// A move as a complete turn, written in standard notation such as `11-15` or `9x18x27`.
+#[pyclass(name = "Move", module = "bit_checkers", frozen, eq, hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PyMove(Turn);
+
+/// A board together with the player to move. Players are `1` for Player1 (Black), who moves
+/// first, and `2` for Player2 (White).
+#[pyclass(name = "Board", module = "bit_checkers", frozen, eq)]
+#[derive(Clone, PartialEq)]
+pub struct PyBoard {
+    board: Board,
+    to_move: Player,
+}
+
+/// An engine, which keeps its transposition table between searches.
+#[pyclass(name = "Engine", module = "bit_checkers")]
+pub struct PyEngine(Engine);
+
+/// The result of a search, with the score for the player to move where a man is worth 100.
+#[pyclass(name = "SearchResult", module = "bit_checkers", frozen, get_all)]
+pub struct PySearchResult {
+    #[pyo3(name = "move")]
+    turn: Option<PyMove>,
+    score: i32,
+    depth: u32,
+    nodes: u64,
+    pv: Vec<PyMove>,
+}
+
+/// A game read from or written as PDN.
+#[pyclass(name = "PdnGame", module = "bit_checkers", frozen)]
+pub struct PyPdnGame(PdnGame);
+
+#[pymethods]
+impl PyMove {
+    /// Returns a move parsed from standard notation. Jumps may be written with only their end
+    /// points, which `Board.apply_move` resolves if they are not ambiguous.
+    #[new]
+    fn new(notation: &str) -> PyResult<PyMove> {
+        notation.parse().map(PyMove).map_err(value_error)
+    }
+
+    /// Returns the squares visited by the moving piece, numbered 1 to 32.
+    #[getter]
+    fn squares(&self) -> Vec<u32> {
+        self.0
+            .positions()
+            .filter_map(|position| position.square())
+            .collect()
+    }
+
+    #[getter]
+    fn is_jump(&self) -> bool {
+        self.0.is_jump()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Move('{}')", self.0)
+    }
+}
+
+#[pymethods]
+impl PyBoard {
+    /// Returns the initial board with Player1 to move.
+    #[new]
+    fn new() -> PyBoard {
+        PyBoard::from(Fen::new())
+    }
+
+    /// Returns a board parsed from a FEN string such as `B:W21-32:B1-12`.
+    #[staticmethod]
+    fn from_fen(fen: &str) -> PyResult<PyBoard> {
+        let fen: Fen = fen.parse().map_err(value_error)?;
+        Ok(PyBoard::from(fen))
+    }
+
+    /// Returns the board as a FEN string.
+    fn fen(&self) -> String {
+        Fen {
+            board: self.board.clone(),
+            to_move: self.to_move,
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn to_move(&self) -> u8 {
+        player_value(self.to_move)
+    }
+
+    /// Returns the legal moves for the player to move.
+    fn legal_moves(&self) -> Vec<PyMove> {
+        self.board.turns(self.to_move).map(PyMove).collect()
+    }
+
+    /// Returns the board after the player to move makes a move, raising `ValueError` if it is not
+    /// legal.
+    fn apply_move(&self, py_move: &PyMove) -> PyResult<PyBoard> {
+        let turn = self
+            .board
+            .find_turn(self.to_move, &py_move.0)
+            .ok_or_else(|| PyValueError::new_err(format!("illegal move {}", py_move.0)))?;
+        Ok(PyBoard {
+            board: self.board.clone().apply_turn(self.to_move, turn),
+            to_move: self.to_move.opponent(),
+        })
+    }
+
+    /// Returns the winner if the player to move can't move, or None if the game is in progress.
+    fn winner(&self) -> Option<u8> {
+        match self.board.turns(self.to_move).next() {
+            Some(_) => None,
+            None => Some(player_value(self.to_move.opponent())),
+        }
+    }
+
+    /// Returns the bitboards `(player1, player1_kings, player2, player2_kings)`, where bit
+    /// `x + 8 * y` is set for a piece at column x and row y. The player bitboards include kings.
+    fn bitboards(&self) -> (u64, u64, u64, u64) {
+        (
+            self.board.pieces(Player1).mask(),
+            self.board.kings(Player1).mask(),
+            self.board.pieces(Player2).mask(),
+            self.board.kings(Player2).mask(),
+        )
+    }
+
+    /// Returns the planes `player1 men, player1 kings, player2 men, player2 kings` as 4 * 64
+    /// bytes of 0 or 1, ordered by row then column.
+    ///
+    /// `numpy.frombuffer(board.planes(), dtype=numpy.uint8).reshape(4, 8, 8)` indexes them as
+    /// `[plane, y, x]`.
+    fn planes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let (player1, player1_kings, player2, player2_kings) = self.bitboards();
+        let planes = [
+            player1 & !player1_kings,
+            player1_kings,
+            player2 & !player2_kings,
+            player2_kings,
+        ];
+        let bytes: Vec<u8> = planes
+            .iter()
+            .flat_map(|&plane| (0..64).map(move |index| (plane >> index & 1) as u8))
+            .collect();
+        PyBytes::new(py, &bytes)
+    }
+
+    fn __str__(&self) -> String {
+        self.board.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Board.from_fen('{}')", self.fen())
+    }
+}
+
+#[pymethods]
+impl PyEngine {
+    #[new]
+    #[pyo3(signature = (threads=1, hash_size=16))]
+    fn new(threads: usize, hash_size: usize) -> PyResult<PyEngine> {
+        if hash_size > MAX_HASH_SIZE {
+            return Err(PyValueError::new_err(format!(
+                "hash_size must be at most {} megabytes",
+                MAX_HASH_SIZE
+            )));
+        }
+        Ok(PyEngine(
+            Engine::new()
+                .with_threads(threads)
+                .with_hash_size(hash_size),
+        ))
+    }
+
+    /// Searches for the best move for the player to move, stopping at the first limit reached.
+    /// `time` is in seconds. At least one limit must be given.
+    ///
+    /// Other Python threads can run during the search.
+    #[pyo3(signature = (board, depth=None, time=None, nodes=None))]
+    fn search(
+        &mut self,
+        py: Python<'_>,
+        board: &PyBoard,
+        depth: Option<u32>,
+        time: Option<f64>,
+        nodes: Option<u64>,
+    ) -> PyResult<PySearchResult> {
+        if depth.is_none() && time.is_none() && nodes.is_none() {
+            return Err(PyValueError::new_err(
+                "a search needs a depth, time or nodes limit",
+            ));
+        }
+        let time = time
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(value_error)?;
+        let limits = SearchLimits { depth, time, nodes };
+        let engine = &mut self.0;
+        let result = py.detach(|| engine.search(&board.board, board.to_move, limits));
+        Ok(PySearchResult::from(result))
+    }
+
+    /// Clears the transposition table, so that later searches don't depend on earlier ones.
+    fn clear_hash(&mut self) {
+        self.0.clear_hash();
+    }
+}
+
+#[pymethods]
+impl PyPdnGame {
+    /// Returns a game with the given moves as its main line, followed by the given tags and
+    /// result token.
+    #[new]
+    #[pyo3(signature = (moves, tags=Vec::new(), result=None))]
+    fn new(moves: Vec<PyMove>, tags: Vec<(String, String)>, result: Option<String>) -> PyPdnGame {
+        let mut game = PdnGame::from_turns(moves.into_iter().map(|py_move| py_move.0));
+        game.tags = tags;
+        game.result = result;
+        PyPdnGame(game)
+    }
+
+    /// Returns the tags from the header of the game as `(name, value)` pairs.
+    #[getter]
+    fn tags(&self) -> Vec<(String, String)> {
+        self.0.tags.clone()
+    }
+
+    #[getter]
+    fn result(&self) -> Option<String> {
+        self.0.result.clone()
+    }
+
+    /// Returns the starting position, given by the `FEN` tag if present.
+    fn start(&self) -> PyResult<PyBoard> {
+        self.0.start().map(PyBoard::from).map_err(value_error)
+    }
+
+    /// Returns the complete moves of the main line, resolved by playing them from the starting
+    /// position.
+    fn moves(&self) -> PyResult<Vec<PyMove>> {
+        let turns = self.0.replay().map_err(value_error)?;
+        Ok(turns.into_iter().map(PyMove).collect())
+    }
+
+    /// Returns the game as PDN text.
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Returns every game in a PDN collection, raising `ValueError` if the text can't be parsed.
+#[pyfunction]
+fn parse_pdn(text: &str) -> PyResult<Vec<PyPdnGame>> {
+    let games = pdn::parse_games(text).map_err(value_error)?;
+    Ok(games.into_iter().map(PyPdnGame).collect())
+}
+
+/// Checkers boards, move generation and search.
+#[pymodule]
+fn bit_checkers(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyMove>()?;
+    module.add_class::<PyBoard>()?;
+    module.add_class::<PyEngine>()?;
+    module.add_class::<PySearchResult>()?;
+    module.add_class::<PyPdnGame>()?;
+    module.add_function(wrap_pyfunction!(parse_pdn, module)?)?;
+    Ok(())
+}
+
+impl From<Fen> for PyBoard {
+    fn from(Fen { board, to_move }: Fen) -> PyBoard {
+        PyBoard { board, to_move }
+    }
+}
+
+impl From<SearchResult> for PySearchResult {
+    fn from(result: SearchResult) -> PySearchResult {
+        PySearchResult {
+            turn: result.turn.map(PyMove),
+            score: result.score,
+            depth: result.depth,
+            nodes: result.nodes,
+            pv: result.pv.into_iter().map(PyMove).collect(),
+        }
+    }
+}
+
+/// Returns the Python value of a player.
+fn player_value(player: Player) -> u8 {
+    match player {
+        Player1 => 1,
+        Player2 => 2,
+    }
+}
+
+/// Returns a `ValueError` with the message of an error.
+fn value_error<E: ToString>(error: E) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_apply_moves() {
+        let board = PyBoard::new();
+        let moves = board.legal_moves();
+        let board = board.apply_move(&PyMove::new("11-15").unwrap()).unwrap();
+
+        assert_eq!(moves.len(), 7);
+        assert_eq!(board.to_move(), 2);
+        assert_eq!(
+            board.fen(),
+            "W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,12,15"
+        );
+        assert!(board.apply_move(&PyMove::new("11-15").unwrap()).is_err());
+    }
+
+    #[test]
+    fn should_resolve_jumps() {
+        let board = PyBoard::from_fen("B:W18,27:B14").unwrap();
+        let board = board.apply_move(&PyMove::new("14x32").unwrap()).unwrap();
+
+        assert_eq!(board.winner(), Some(1));
+        assert_eq!(board.bitboards(), (1 << 62, 1 << 62, 0, 0));
+        assert!(PyBoard::from_fen("B:W1:B1").is_err());
+    }
+
+    #[test]
+    fn should_read_pdn() {
+        let games = pdn::parse_games("[FEN \"W:W21:B1\"] 1... 21-17 *").unwrap();
+        let game = PyPdnGame(games[0].clone());
+
+        assert_eq!(game.start().unwrap().to_move(), 2);
+        assert_eq!(game.moves().unwrap()[0].squares(), vec![21, 17]);
+        assert_eq!(
+            PyPdnGame::new(vec![PyMove::new("11-15").unwrap()], Vec::new(), None)
+                .0
+                .to_string(),
+            "1. 11-15 *\n"
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use super::board::{Board, Player, Player1, Player2, Position};
+use super::turn::Turn;
+use std::fmt;
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_LIGHT_SQUARE: &str = "\x1b[47m";
+const ANSI_DARK_SQUARE: &str = "\x1b[42m";
+const ANSI_HIGHLIGHT: &str = "\x1b[43m";
+const ANSI_PLAYER1: &str = "\x1b[1;30m";
+const ANSI_PLAYER2: &str = "\x1b[1;97m";
+
+/// Type representing how the squares of a rendered board are labelled.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Labels {
+    /// No labels.
+    None,
+    /// Files `a` to `h` below the board and ranks `1` to `8` to its left.
+    Coordinates,
+    /// Standard 1 to 32 square numbers shown in empty playable squares.
+    Squares,
+}
+
+/// Type for rendering a board as text, with options for labels, glyphs, colours, orientation and
+/// highlighting.
+///
+/// Each square is three characters wide. Player1 (Black) pieces are `b` and kings `B`, Player2
+/// (White) pieces are `w` and kings `W`, and empty playable squares are `.`. Highlighted squares
+/// are surrounded by brackets, or given a background colour when colours are enabled. Square
+/// numbers are not shown in highlighted squares.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::board::{Board, Player1};
+/// use bit_checkers::render::{Labels, Renderer};
+/// let text = Renderer::new()
+///     .labels(Labels::Squares)
+///     .perspective(Player1)
+///     .render(&Board::new());
+///
+/// assert_eq!(text.lines().nth(3).unwrap(), "20    19    18    17    ");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    labels: Labels,
+    unicode: bool,
+    colors: bool,
+    perspective: Player,
+    highlighted: Vec<Position>,
+}
+
+impl Renderer {
+    /// Returns a renderer using coordinate labels and ASCII glyphs without colours, viewing the
+    /// board from Player2's side as in a standard diagram, with Player1 at the top.
+    pub fn new() -> Renderer {
+        Renderer {
+            labels: Labels::Coordinates,
+            unicode: false,
+            colors: false,
+            perspective: Player2,
+            highlighted: Vec::new(),
+        }
+    }
+
+    /// Sets how squares are labelled.
+    pub fn labels(mut self, labels: Labels) -> Renderer {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets whether pieces are drawn with the Unicode draughts glyphs (⛂ ⛃ ⛀ ⛁) instead of
+    /// letters.
+    pub fn unicode(mut self, unicode: bool) -> Renderer {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Sets whether ANSI escape codes are used to colour the squares and pieces.
+    pub fn colors(mut self, colors: bool) -> Renderer {
+        self.colors = colors;
+        self
+    }
+
+    /// Sets the player whose side of the board is shown at the bottom.
+    pub fn perspective(mut self, player: Player) -> Renderer {
+        self.perspective = player;
+        self
+    }
+
+    /// Sets a turn, usually the last turn played, whose squares are highlighted.
+    pub fn highlight(mut self, turn: Turn) -> Renderer {
+        self.highlighted = turn.positions().collect();
+        self
+    }
+
+    /// Returns the board rendered as text, with a newline after each row.
+    pub fn render(&self, board: &Board) -> String {
+        let mut text = String::new();
+        self.write(&mut text, board)
+            .expect("writing to a string should not fail");
+        text
+    }
+
+    /// Writes the board rendered as text, with a newline after each row.
+    pub fn write<W: fmt::Write>(&self, writer: &mut W, board: &Board) -> fmt::Result {
+        for row in 0..8 {
+            if self.labels == Labels::Coordinates {
+                write!(writer, "{} ", self.rank(row))?;
+            }
+            for column in 0..8 {
+                self.write_square(writer, board, self.position(column, row))?;
+            }
+            writeln!(writer)?;
+        }
+        if self.labels == Labels::Coordinates {
+            write!(writer, " ")?;
+            for column in 0..8 {
+                let Position(x, _) = self.position(column, 0);
+                write!(writer, "  {}", (b'a' + x as u8) as char)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    fn write_square<W: fmt::Write>(
+        &self,
+        writer: &mut W,
+        board: &Board,
+        position: Position,
+    ) -> fmt::Result {
+        let highlighted = self.highlighted.contains(&position);
+        let piece = board.piece_at(position);
+        let content = match (&piece, position.square()) {
+            (Some(piece), _) => self.glyph(piece.player, piece.king).to_string(),
+            (None, Some(square)) if self.labels == Labels::Squares && !highlighted => {
+                square.to_string()
+            }
+            (None, Some(_)) => ".".to_string(),
+            (None, None) => " ".to_string(),
+        };
+
+        if self.colors {
+            let background = match (highlighted, position.square()) {
+                (true, _) => ANSI_HIGHLIGHT,
+                (false, Some(_)) => ANSI_DARK_SQUARE,
+                (false, None) => ANSI_LIGHT_SQUARE,
+            };
+            let foreground = match piece.map(|piece| piece.player) {
+                Some(Player1) => ANSI_PLAYER1,
+                Some(Player2) => ANSI_PLAYER2,
+                None => "",
+            };
+            write!(
+                writer,
+                "{}{}{:^3}{}",
+                background, foreground, content, ANSI_RESET
+            )
+        } else if highlighted {
+            write!(writer, "[{}]", content)
+        } else {
+            write!(writer, "{:^3}", content)
+        }
+    }
+
+    fn glyph(&self, player: Player, king: bool) -> char {
+        match (self.unicode, player, king) {
+            (false, Player1, false) => 'b',
+            (false, Player1, true) => 'B',
+            (false, Player2, false) => 'w',
+            (false, Player2, true) => 'W',
+            (true, Player1, false) => '⛂',
+            (true, Player1, true) => '⛃',
+            (true, Player2, false) => '⛀',
+            (true, Player2, true) => '⛁',
+        }
+    }
+
+    /// Returns the position shown at the given column and row of the rendered board.
+    fn position(&self, column: u32, row: u32) -> Position {
+        match self.perspective {
+            Player1 => Position(7 - column, 7 - row),
+            Player2 => Position(column, row),
+        }
+    }
+
+    /// Returns the rank label for a row of the rendered board.
+    fn rank(&self, row: u32) -> u32 {
+        let Position(_, y) = self.position(0, row);
+        8 - y
+    }
+}
+
+impl Default for Renderer {
+    fn default() -> Renderer {
+        Renderer::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_render_coordinates() {
+        let text = Renderer::new().render(&Board::new());
+
+        assert_eq!(
+            text,
+            [
+                "8     b     b     b     b ",
+                "7  b     b     b     b    ",
+                "6     b     b     b     b ",
+                "5  .     .     .     .    ",
+                "4     .     .     .     . ",
+                "3  w     w     w     w    ",
+                "2     w     w     w     w ",
+                "1  w     w     w     w    ",
+                "   a  b  c  d  e  f  g  h",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn should_render_from_player1_perspective() {
+        let text = Renderer::new().perspective(Player1).render(&Board::new());
+
+        assert_eq!(text.lines().next(), Some("1     w     w     w     w "));
+        assert_eq!(text.lines().last(), Some("   h  g  f  e  d  c  b  a"));
+    }
+
+    #[test]
+    fn should_highlight_turn() {
+        let board = Board::new();
+        let turn: Turn = "11-15".parse().unwrap();
+        let text = Renderer::new()
+            .labels(Labels::None)
+            .unicode(true)
+            .highlight(turn)
+            .render(&board.apply_turn(Player1, turn));
+
+        assert_eq!(text.lines().nth(2), Some("    ⛂     ⛂    [.]    ⛂ "));
+        assert_eq!(text.lines().nth(3), Some(" .     .    [⛂]    .    "));
+    }
+
+    #[test]
+    fn should_render_colors() {
+        let text = Renderer::new()
+            .labels(Labels::None)
+            .colors(true)
+            .render(&Board::new());
+
+        assert!(text.starts_with("\x1b[47m   \x1b[0m\x1b[42m\x1b[1;30m b \x1b[0m"));
+    }
+}
@@ -0,0 +1,164 @@
+use super::board::{Board, Piece, Player1, Player2};
+use super::fen::Fen;
+use super::util::BitGrid;
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// Compact representation of a board, as the bit mask of each player's pieces and kings.
+#[derive(Serialize, Deserialize)]
+struct BoardMasks {
+    player1: BitGrid,
+    player1_kings: BitGrid,
+    player2: BitGrid,
+    player2_kings: BitGrid,
+}
+
+impl Serialize for Board {
+    /// Serializes the board in its compact form, the bit masks of each player's pieces and kings.
+    ///
+    /// Use `serialization::pieces` for a verbose form listing every piece.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BoardMasks {
+            player1: self.pieces(Player1),
+            player1_kings: self.kings(Player1),
+            player2: self.pieces(Player2),
+            player2_kings: self.kings(Player2),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    /// Deserializes a board from the bit masks of each player's pieces and kings.
+    ///
+    /// Fails if the masks are not a valid position, see `Board::validate`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        let masks = BoardMasks::deserialize(deserializer)?;
+        let board = Board::from_grids(
+            masks.player1,
+            masks.player1_kings,
+            masks.player2,
+            masks.player2_kings,
+        );
+        board.validate().map_err(de::Error::custom)?;
+        Ok(board)
+    }
+}
+
+impl Serialize for Fen {
+    /// Serializes the position as a FEN string.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fen {
+    /// Deserializes a position from a FEN string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Fen, D::Error> {
+        let fen = String::deserialize(deserializer)?;
+        fen.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Verbose serialization of a board as a list of pieces, for use with `#[serde(with)]`.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::board::Board;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Record {
+///     #[serde(with = "bit_checkers::checkers::serialization::pieces")]
+///     board: Board,
+/// }
+/// ```
+pub mod pieces {
+    use super::*;
+
+    /// Serializes the board as a list of pieces.
+    pub fn serialize<S: Serializer>(board: &Board, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(board.clone().pieces_iter())
+    }
+
+    /// Deserializes a board from a list of pieces.
+    ///
+    /// Fails if the pieces are not a valid position, see `Board::try_new_with_pieces`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        let pieces = Vec::<Piece>::deserialize(deserializer)?;
+        Board::try_new_with_pieces(pieces).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::board::{Move, Position};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        #[serde(with = "pieces")]
+        board: Board,
+    }
+
+    #[test]
+    fn should_round_trip_compact_board() {
+        let board = Board::new().apply_turn(Player1, "11-15".parse().unwrap());
+        let json = serde_json::to_string(&board).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"player1":277501354,"player1_kings":0,"player2":6172839697753047040,"player2_kings":0}"#
+        );
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+    }
+
+    #[test]
+    fn should_round_trip_verbose_board() {
+        let record = Record {
+            board: Board::new_with_pieces(vec![Piece {
+                position: Position(1, 0),
+                player: Player2,
+                king: true,
+            }]),
+        };
+        let json = serde_json::to_string(&record).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"board":[{"position":[1,0],"player":"Player2","king":true}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+    }
+
+    #[test]
+    fn should_round_trip_other_types() {
+        let a_move: Move = "11-15".parse().unwrap();
+        let json = serde_json::to_string(&a_move).unwrap();
+        assert_eq!(json, r#"{"from":[5,2],"to":[4,3]}"#);
+        assert_eq!(serde_json::from_str::<Move>(&json).unwrap(), a_move);
+
+        let fen: Fen = "W:W21:BK1".parse().unwrap();
+        let json = serde_json::to_string(&fen).unwrap();
+        assert_eq!(json, r#""W:W21:BK1""#);
+        assert_eq!(serde_json::from_str::<Fen>(&json).unwrap(), fen);
+    }
+
+    #[test]
+    fn should_reject_invalid_boards() {
+        let invalid = [
+            r#"{"player1":1,"player1_kings":0,"player2":0,"player2_kings":0}"#,
+            r#"{"player1":2,"player1_kings":0,"player2":2,"player2_kings":0}"#,
+            r#"{"player1":2,"player1_kings":8,"player2":0,"player2_kings":0}"#,
+            r#"{"player1":0,"player1_kings":0,"player2":2,"player2_kings":0}"#,
+        ];
+        for json in &invalid {
+            assert!(serde_json::from_str::<Board>(json).is_err());
+        }
+        assert!(serde_json::from_str::<Record>(
+            r#"{"board":[{"position":[1,0],"player":"Player2","king":true},{"position":[1,0],"player":"Player1","king":false}]}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Fen>(r#""W:W21""#).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+use super::board::{Board, Player, Player1, Player2, Position};
+use super::turn::Turn;
+use std::fmt::Write;
+
+/// Type for rendering a board as an SVG diagram.
+///
+/// The diagram is drawn with square numbers on the playable squares, and can include highlighted
+/// squares and arrows along the path of turns. It has no external dependencies, the result is a
+/// standalone SVG document. Diagrams inlined in the same HTML page should each be given their own
+/// id with `id`, so that they don't use each other's arrowheads.
+///
+/// # Examples
+///
+/// ```
+/// use bit_checkers::board::Board;
+/// use bit_checkers::svg::SvgRenderer;
+/// let svg = SvgRenderer::new()
+///     .arrow("11-15".parse().unwrap())
+///     .render(&Board::new());
+///
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains("<polyline"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SvgRenderer {
+    square_size: u32,
+    light_color: String,
+    dark_color: String,
+    player1_color: String,
+    player2_color: String,
+    highlight_color: String,
+    arrow_color: String,
+    labels: bool,
+    perspective: Player,
+    highlighted: Vec<Position>,
+    arrows: Vec<Turn>,
+    id: String,
+}
+
+impl SvgRenderer {
+    /// Returns a renderer with 50 pixel squares, square numbers and a traditional colour scheme,
+    /// viewing the board from Player2's side as in a standard diagram.
+    pub fn new() -> SvgRenderer {
+        SvgRenderer {
+            square_size: 50,
+            light_color: "#f0d9b5".to_string(),
+            dark_color: "#4a7c3f".to_string(),
+            player1_color: "#c0392b".to_string(),
+            player2_color: "#f5f5f5".to_string(),
+            highlight_color: "#f7dc6f".to_string(),
+            arrow_color: "#1f4e99".to_string(),
+            labels: true,
+            perspective: Player2,
+            highlighted: Vec::new(),
+            arrows: Vec::new(),
+            id: "board".to_string(),
+        }
+    }
+
+    /// Sets the width and height of each square in pixels.
+    pub fn square_size(mut self, size: u32) -> SvgRenderer {
+        self.square_size = size;
+        self
+    }
+
+    /// Sets the colours of the unplayable and playable squares, as SVG colour values.
+    pub fn square_colors(mut self, light: &str, dark: &str) -> SvgRenderer {
+        self.light_color = light.to_string();
+        self.dark_color = dark.to_string();
+        self
+    }
+
+    /// Sets the colours of Player1's and Player2's pieces, as SVG colour values.
+    pub fn piece_colors(mut self, player1: &str, player2: &str) -> SvgRenderer {
+        self.player1_color = player1.to_string();
+        self.player2_color = player2.to_string();
+        self
+    }
+
+    /// Sets the colours of highlighted squares and arrows, as SVG colour values.
+    pub fn annotation_colors(mut self, highlight: &str, arrow: &str) -> SvgRenderer {
+        self.highlight_color = highlight.to_string();
+        self.arrow_color = arrow.to_string();
+        self
+    }
+
+    /// Sets whether square numbers are drawn on the playable squares.
+    pub fn labels(mut self, labels: bool) -> SvgRenderer {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets the player whose side of the board is shown at the bottom.
+    pub fn perspective(mut self, player: Player) -> SvgRenderer {
+        self.perspective = player;
+        self
+    }
+
+    /// Sets the prefix of the ids of elements in the diagram, `board` by default.
+    pub fn id(mut self, id: &str) -> SvgRenderer {
+        self.id = id.to_string();
+        self
+    }
+
+    /// Adds a highlighted square.
+    pub fn highlight(mut self, position: Position) -> SvgRenderer {
+        self.highlighted.push(position);
+        self
+    }
+
+    /// Adds an arrow following the path of a turn, through every square visited by a multi-jump.
+    pub fn arrow(mut self, turn: Turn) -> SvgRenderer {
+        self.arrows.push(turn);
+        self
+    }
+
+    /// Returns the board rendered as an SVG document.
+    pub fn render(&self, board: &Board) -> String {
+        let size = self.square_size * 8;
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+            size
+        );
+        self.write_squares(&mut svg);
+        self.write_pieces(&mut svg, board);
+        self.write_arrows(&mut svg);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Returns diagrams of a sequence of positions: the starting board, then the board after each
+    /// turn with an arrow showing the turn, or None if a turn is not legal.
+    ///
+    /// Each diagram's id is the renderer's id followed by its index in the sequence (e.g.
+    /// `board-2`), so that the diagrams can be inlined in the same page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Board, Player1};
+    /// use bit_checkers::svg::SvgRenderer;
+    /// let turns = ["11-15".parse().unwrap(), "23-19".parse().unwrap()];
+    /// let diagrams = SvgRenderer::new().render_sequence(&Board::new(), Player1, &turns);
+    ///
+    /// assert_eq!(diagrams.unwrap().len(), 3);
+    /// assert!(SvgRenderer::new()
+    ///     .render_sequence(&Board::new(), Player1, &turns[1..])
+    ///     .is_none());
+    /// ```
+    pub fn render_sequence(
+        &self,
+        board: &Board,
+        player: Player,
+        turns: &[Turn],
+    ) -> Option<Vec<String>> {
+        let id = |index: usize| format!("{}-{}", self.id, index);
+        let mut diagrams = vec![self.clone().id(&id(0)).render(board)];
+        let mut board = board.clone();
+        let mut player = player;
+        for notation in turns {
+            let turn = board.find_turn(player, notation)?;
+            board = board.apply_turn(player, turn);
+            player = player.opponent();
+            let renderer = self.clone().id(&id(diagrams.len())).arrow(turn);
+            diagrams.push(renderer.render(&board));
+        }
+        Some(diagrams)
+    }
+
+    fn write_squares(&self, svg: &mut String) {
+        let size = self.square_size;
+        for y in 0..8 {
+            for x in 0..8 {
+                let position = Position(x, y);
+                let (left, top) = self.corner(position);
+                let color = match position.square() {
+                    _ if self.highlighted.contains(&position) => &self.highlight_color,
+                    Some(_) => &self.dark_color,
+                    None => &self.light_color,
+                };
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{2}" height="{2}" fill="{3}"/>"#,
+                    left,
+                    top,
+                    size,
+                    escape(color)
+                );
+                if let (Some(square), true) = (position.square(), self.labels) {
+                    let _ = writeln!(
+                        svg,
+                        r#"<text x="{}" y="{}" font-family="sans-serif" font-size="{}" fill="{}">{}</text>"#,
+                        left + size / 20 + 1,
+                        top + size / 4,
+                        size / 5,
+                        escape(&self.light_color),
+                        square
+                    );
+                }
+            }
+        }
+    }
+
+    fn write_pieces(&self, svg: &mut String, board: &Board) {
+        let radius = self.square_size as f32 * 0.38;
+        for piece in board.clone().pieces_iter() {
+            let (x, y) = self.center(piece.position);
+            let (fill, stroke) = match piece.player {
+                Player1 => (&self.player1_color, &self.player2_color),
+                Player2 => (&self.player2_color, &self.player1_color),
+            };
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="black" stroke-width="{}"/>"#,
+                x,
+                y,
+                radius,
+                escape(fill),
+                radius / 12.0
+            );
+            if piece.king {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                    x,
+                    y,
+                    radius * 0.55,
+                    escape(stroke),
+                    radius / 6.0
+                );
+            }
+        }
+    }
+
+    fn write_arrows(&self, svg: &mut String) {
+        if self.arrows.is_empty() {
+            return;
+        }
+        let width = self.square_size as f32 / 10.0;
+        let marker = escape(&format!("{}-arrowhead", self.id));
+        let _ = writeln!(
+            svg,
+            r#"<defs><marker id="{}" markerWidth="3" markerHeight="3" refX="1.5" refY="1.5" orient="auto"><path d="M0,0 L3,1.5 L0,3 z" fill="{}"/></marker></defs>"#,
+            marker,
+            escape(&self.arrow_color)
+        );
+        for turn in &self.arrows {
+            let points: Vec<String> = turn
+                .positions()
+                .map(|position| {
+                    let (x, y) = self.center(position);
+                    format!("{},{}", x, y)
+                })
+                .collect();
+            let _ = writeln!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round" opacity="0.8" marker-end="url(#{})"/>"#,
+                points.join(" "),
+                escape(&self.arrow_color),
+                width,
+                marker
+            );
+        }
+    }
+
+    /// Returns the coordinates of the top left corner of a square in the diagram.
+    fn corner(&self, Position(x, y): Position) -> (u32, u32) {
+        let (column, row) = match self.perspective {
+            Player1 => (7 - x, 7 - y),
+            Player2 => (x, y),
+        };
+        (column * self.square_size, row * self.square_size)
+    }
+
+    /// Returns the coordinates of the centre of a square in the diagram.
+    fn center(&self, position: Position) -> (f32, f32) {
+        let (left, top) = self.corner(position);
+        let half = self.square_size as f32 / 2.0;
+        (left as f32 + half, top as f32 + half)
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> SvgRenderer {
+        SvgRenderer::new()
+    }
+}
+
+/// Returns the text with characters that are special in XML attributes escaped.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checkers::board::Piece;
+
+    #[test]
+    fn should_render_board() {
+        let board = Board::new_with_pieces(vec![
+            Piece {
+                player: Player1,
+                king: true,
+                position: Position(1, 0),
+            },
+            Piece {
+                player: Player2,
+                king: false,
+                position: Position(6, 7),
+            },
+        ]);
+        let svg = SvgRenderer::new()
+            .square_size(10)
+            .piece_colors("black", "white")
+            .highlight(Position(0, 7))
+            .render(&board);
+
+        assert_eq!(svg.matches("<rect").count(), 64);
+        assert_eq!(svg.matches("<text").count(), 32);
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains(r#"<circle cx="15" cy="5" r="3.8" fill="black""#));
+        assert!(svg.contains(r##"<rect x="0" y="70" width="10" height="10" fill="#f7dc6f"/>"##));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn should_draw_arrow_along_jump_path() {
+        let svg = SvgRenderer::new()
+            .square_size(10)
+            .labels(false)
+            .perspective(Player1)
+            .annotation_colors("yellow", "a\"b")
+            .arrow("9x18x27".parse().unwrap())
+            .render(&Board::new_with_pieces(None));
+
+        assert_eq!(svg.matches("<text").count(), 0);
+        assert!(svg.contains(r#"<marker id="board-arrowhead""#));
+        assert!(svg.contains(r#"marker-end="url(#board-arrowhead)""#));
+        assert!(
+            svg.contains(r#"<polyline points="65,55 45,35 25,15" fill="none" stroke="a&quot;b""#)
+        );
+    }
+
+    #[test]
+    fn should_give_each_diagram_in_a_sequence_its_own_id() {
+        let turns = ["11-15".parse().unwrap(), "23-19".parse().unwrap()];
+        let diagrams = SvgRenderer::new()
+            .id("game")
+            .render_sequence(&Board::new(), Player1, &turns)
+            .unwrap();
+
+        assert!(!diagrams[0].contains("<marker"));
+        assert!(diagrams[1].contains(r#"<marker id="game-1-arrowhead""#));
+        assert!(diagrams[2].contains(r#"marker-end="url(#game-2-arrowhead)""#));
+    }
+}
@@ -0,0 +1,710 @@
+use super::board::{Board, Player, Player1, Player2, Position};
+use super::cli::{self, Controller, Options};
+use super::engine::{Engine, SearchLimits};
+use super::fen::Fen;
+use super::game::{Game, Outcome, DRAW_TURNS};
+use super::turn::Turn;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEventKind,
+};
+use crossterm::style::{
+    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Width of a square in columns.
+const SQUARE_WIDTH: u16 = 5;
+/// Height of a square in rows.
+const SQUARE_HEIGHT: u16 = 2;
+/// Column of the left edge of the board, leaving room for the evaluation bar.
+const BOARD_LEFT: u16 = 4;
+/// Row of the top edge of the board, leaving room for the title.
+const BOARD_TOP: u16 = 2;
+/// Column of the left edge of the move list.
+const MOVES_LEFT: u16 = BOARD_LEFT + 8 * SQUARE_WIDTH + 3;
+/// Row of the status line, below the board.
+const STATUS_TOP: u16 = BOARD_TOP + 8 * SQUARE_HEIGHT + 1;
+/// Score at which the evaluation bar is full, 5 men ahead.
+const EVAL_RANGE: i32 = 500;
+/// Depth searched to evaluate positions reached by a human's turn.
+const EVAL_DEPTH: u32 = 6;
+/// Time each step of a multi-jump is shown for.
+const ANIMATION_STEP: Duration = Duration::from_millis(250);
+
+/// Result of handling an input event.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Action {
+    Continue,
+    Play(Turn),
+    Quit,
+}
+
+/// State of a game being played in the terminal UI.
+struct Tui {
+    options: Options,
+    engine: Engine,
+    start: Fen,
+    game: Game,
+    /// Player whose side of the board is shown at the bottom.
+    perspective: Player,
+    /// Square under the keyboard cursor.
+    cursor: Position,
+    /// Squares chosen so far for the human's turn, starting with the selected piece.
+    path: Vec<Position>,
+    /// Score of the last search for Player1, where a man is worth 100.
+    score: Option<i32>,
+    message: String,
+}
+
+/// Puts the terminal into full-screen mode, and restores it when dropped.
+struct Screen;
+
+/// Plays a game in a full-screen terminal UI, until the player quits.
+///
+/// A piece is selected by moving the cursor to it with the arrow keys and pressing enter or
+/// space, or by clicking it. Its legal destinations are then highlighted and chosen in the same
+/// way, one jump at a time for a multi-jump.
+pub fn run(options: Options) -> io::Result<()> {
+    let mut tui = Tui::new(options);
+    let _screen = Screen::enter()?;
+    let mut output = io::stdout();
+    loop {
+        if tui.engine_to_move() {
+            tui.message = format!("{} is thinking...", cli::name(tui.game.to_move()));
+            tui.draw(&mut output)?;
+            let turn = tui.engine_turn();
+            tui.play(&mut output, turn)?;
+            // Engine games can be left between turns
+            while event::poll(Duration::ZERO)? {
+                if let Action::Quit = tui.handle(event::read()?) {
+                    return Ok(());
+                }
+            }
+            continue;
+        }
+
+        tui.draw(&mut output)?;
+        match tui.handle(event::read()?) {
+            Action::Continue => (),
+            Action::Play(turn) => tui.play(&mut output, turn)?,
+            Action::Quit => return Ok(()),
+        }
+    }
+}
+
+impl Tui {
+    fn new(options: Options) -> Tui {
+        let perspective = match (options.player1, options.player2) {
+            (Controller::Human, Controller::Engine) => Player1,
+            _ => Player2,
+        };
+        let start = options.start.clone();
+        let mut tui = Tui {
+            engine: Engine::new(),
+            game: cli::replay(&start, &[]),
+            start,
+            options,
+            perspective,
+            cursor: Position(0, 0),
+            path: Vec::new(),
+            score: None,
+            message: String::new(),
+        };
+        if let Some(path) = tui.options.load.clone() {
+            tui.message = match cli::load_game(&path) {
+                Ok((start, turns)) => {
+                    tui.game = cli::replay(&start, &turns);
+                    tui.start = start;
+                    format!("Loaded {} moves from {}.", turns.len(), path.display())
+                }
+                Err(error) => format!("Could not load {}: {}", path.display(), error),
+            };
+        }
+        tui.reset_cursor();
+        tui
+    }
+
+    /// Returns whether the game is in progress with the engine to move.
+    fn engine_to_move(&self) -> bool {
+        self.in_progress() && self.options.controller(self.game.to_move()) == Controller::Engine
+    }
+
+    fn in_progress(&self) -> bool {
+        self.game.outcome().is_none()
+    }
+
+    /// Returns the engine's choice of turn, updating the score.
+    fn engine_turn(&mut self) -> Turn {
+        let (board, player) = (self.game.board(), self.game.to_move());
+        let result = self.engine.search(board, player, self.options.limits);
+        self.score = Some(score_for_player1(result.score, player));
+        result
+            .turn
+            .expect("player to move should have a legal turn")
+    }
+
+    fn handle(&mut self, event: Event) -> Action {
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) => self.key(code, modifiers),
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                match self.position_at(mouse.column, mouse.row) {
+                    Some(position) => {
+                        self.cursor = position;
+                        self.choose(position)
+                    }
+                    None => Action::Continue,
+                }
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    fn key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Action {
+        // Arrow keys move across the screen, so they are reversed when Player1 is at the bottom
+        let direction = match self.perspective {
+            Player1 => -1,
+            Player2 => 1,
+        };
+        match code {
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Action::Quit,
+            KeyCode::Left => self.move_cursor(-direction, 0),
+            KeyCode::Right => self.move_cursor(direction, 0),
+            KeyCode::Up => self.move_cursor(0, -direction),
+            KeyCode::Down => self.move_cursor(0, direction),
+            KeyCode::Enter | KeyCode::Char(' ') => return self.choose(self.cursor),
+            KeyCode::Esc => self.path.clear(),
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('n') => {
+                self.game = cli::replay(&self.start, &[]);
+                self.after_reset("New game.");
+            }
+            KeyCode::Char('f') => self.perspective = self.perspective.opponent(),
+            _ => (),
+        }
+        Action::Continue
+    }
+
+    fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let Position(x, y) = self.cursor;
+        let x = (x as i32 + dx).clamp(0, 7) as u32;
+        let y = (y as i32 + dy).clamp(0, 7) as u32;
+        self.cursor = Position(x, y);
+    }
+
+    /// Selects a piece or chooses the next square of its turn, returning the turn to play once
+    /// it is complete.
+    fn choose(&mut self, position: Position) -> Action {
+        if !self.in_progress() || self.engine_to_move() {
+            return Action::Continue;
+        }
+        if self.path == [position] {
+            self.path.clear();
+            return Action::Continue;
+        }
+        let mut path = self.path.clone();
+        path.push(position);
+        if !self.matching_turns(&path).is_empty() {
+            self.path = path;
+        } else if !self.matching_turns(&[position]).is_empty() {
+            self.path = vec![position];
+        } else {
+            self.message = match self.path.is_empty() {
+                true => "That piece has no legal moves.".to_string(),
+                false => "That is not a legal destination.".to_string(),
+            };
+            return Action::Continue;
+        }
+        self.message.clear();
+        let complete = self
+            .matching_turns(&self.path)
+            .into_iter()
+            .find(|turn| turn.positions().count() == self.path.len());
+        match complete {
+            Some(turn) => {
+                self.path.clear();
+                Action::Play(turn)
+            }
+            None => Action::Continue,
+        }
+    }
+
+    /// Returns the legal turns whose path starts with the given positions.
+    fn matching_turns(&self, path: &[Position]) -> Vec<Turn> {
+        self.game
+            .legal_turns()
+            .filter(|turn| {
+                let positions: Vec<Position> = turn.positions().take(path.len()).collect();
+                positions == path
+            })
+            .collect()
+    }
+
+    /// Returns the squares that can be chosen next: the pieces that can move if none is
+    /// selected, or otherwise the next squares of the selected piece's turns.
+    fn destinations(&self) -> Vec<Position> {
+        let mut destinations = Vec::new();
+        for turn in self.matching_turns(&self.path) {
+            match turn.positions().nth(self.path.len()) {
+                Some(position) if !destinations.contains(&position) => destinations.push(position),
+                _ => (),
+            }
+        }
+        destinations
+    }
+
+    /// Returns the boards shown while a multi-jump is animated, after each jump except the
+    /// last.
+    fn frames(&self, turn: Turn) -> Vec<Board> {
+        let player = self.game.to_move();
+        let moves: Vec<_> = turn.moves().collect();
+        let mut board = self.game.board().clone();
+        let mut frames = Vec::new();
+        for &step in &moves[..moves.len() - 1] {
+            board = board.move_piece(player, step);
+            frames.push(board.clone());
+        }
+        frames
+    }
+
+    /// Plays a turn, animating the steps of a multi-jump.
+    fn play<W: Write>(&mut self, output: &mut W, turn: Turn) -> io::Result<()> {
+        let player = self.game.to_move();
+        let positions: Vec<Position> = turn.positions().collect();
+        for (i, board) in self.frames(turn).iter().enumerate() {
+            self.draw_board(output, board, &positions[..i + 2])?;
+            output.flush()?;
+            thread::sleep(ANIMATION_STEP);
+        }
+        self.game
+            .play(turn)
+            .expect("turn should be legal in a game in progress");
+        self.message = format!("{} played {}.", cli::name(player), turn);
+        let opponent = self.options.controller(self.game.to_move());
+        if self.options.controller(player) == Controller::Human && opponent == Controller::Human {
+            self.evaluate();
+        }
+        if !self.in_progress() {
+            self.message = self.ending();
+        }
+        self.reset_cursor();
+        Ok(())
+    }
+
+    /// Updates the score with a short search, so that the bar is shown in human games.
+    fn evaluate(&mut self) {
+        if self.in_progress() {
+            let (board, player) = (self.game.board(), self.game.to_move());
+            let result = self
+                .engine
+                .search(board, player, SearchLimits::depth(EVAL_DEPTH));
+            self.score = Some(score_for_player1(result.score, player));
+        }
+    }
+
+    /// Takes back turns until it is a human's turn again, at least one turn earlier.
+    fn undo(&mut self) {
+        let mut turns = self.game.turns().to_vec();
+        if turns.pop().is_none() {
+            return;
+        }
+        let mut to_move = self.game.to_move().opponent();
+        while !turns.is_empty() && self.options.controller(to_move) == Controller::Engine {
+            turns.pop();
+            to_move = to_move.opponent();
+        }
+        self.game = cli::replay(&self.start, &turns);
+        self.after_reset("Took back a move.");
+    }
+
+    fn after_reset(&mut self, message: &str) {
+        self.path.clear();
+        self.score = None;
+        self.message = message.to_string();
+        self.reset_cursor();
+    }
+
+    /// Moves the cursor to the first piece that can move, so that enter selects it.
+    fn reset_cursor(&mut self) {
+        if let Some(turn) = self.game.legal_turns().next() {
+            self.cursor = turn.from();
+        }
+    }
+
+    fn ending(&self) -> String {
+        match self.game.outcome() {
+            Some(Outcome::Win(player)) => format!(
+                "{} wins, {} can't move.",
+                cli::name(player),
+                cli::name(player.opponent())
+            ),
+            Some(Outcome::Draw) => format!("The game is drawn after {} moves.", DRAW_TURNS),
+            None => String::new(),
+        }
+    }
+
+    /// Returns the screen column and row of the top left of the square at a position.
+    fn square_origin(&self, Position(x, y): Position) -> (u16, u16) {
+        let (column, row) = match self.perspective {
+            Player1 => (7 - x, 7 - y),
+            Player2 => (x, y),
+        };
+        (
+            BOARD_LEFT + column as u16 * SQUARE_WIDTH,
+            BOARD_TOP + row as u16 * SQUARE_HEIGHT,
+        )
+    }
+
+    /// Returns the position of the square at a screen column and row, if it is on the board.
+    fn position_at(&self, column: u16, row: u16) -> Option<Position> {
+        let column = column.checked_sub(BOARD_LEFT)? / SQUARE_WIDTH;
+        let row = row.checked_sub(BOARD_TOP)? / SQUARE_HEIGHT;
+        if column >= 8 || row >= 8 {
+            return None;
+        }
+        let (column, row) = (u32::from(column), u32::from(row));
+        Some(match self.perspective {
+            Player1 => Position(7 - column, 7 - row),
+            Player2 => Position(column, row),
+        })
+    }
+
+    fn draw<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        queue!(output, Clear(ClearType::All), MoveTo(BOARD_LEFT, 0))?;
+        queue!(
+            output,
+            SetAttribute(Attribute::Bold),
+            Print(format!(
+                "Black ({}) vs White ({})",
+                controller_name(self.options.player1),
+                controller_name(self.options.player2)
+            )),
+            SetAttribute(Attribute::Reset)
+        )?;
+        let highlighted = match self.game.turns().last() {
+            Some(turn) if self.path.is_empty() => turn.positions().collect(),
+            _ => self.path.clone(),
+        };
+        self.draw_board(output, self.game.board(), &highlighted)?;
+        self.draw_moves(output)?;
+
+        let status = match self.in_progress() {
+            true => format!(
+                "{} to move. Arrows or mouse to select, enter to choose, esc to cancel.",
+                cli::name(self.game.to_move())
+            ),
+            false => "Game over.".to_string(),
+        };
+        queue!(
+            output,
+            MoveTo(BOARD_LEFT, STATUS_TOP),
+            Print(status),
+            MoveTo(BOARD_LEFT, STATUS_TOP + 1),
+            Print("u undo  n new game  f flip board  q quit"),
+            MoveTo(BOARD_LEFT, STATUS_TOP + 2),
+            Print(&self.message)
+        )?;
+        output.flush()
+    }
+
+    /// Draws a board with the evaluation bar, highlighting the given squares along with the
+    /// cursor and the squares that can be chosen.
+    fn draw_board<W: Write>(
+        &self,
+        output: &mut W,
+        board: &Board,
+        highlighted: &[Position],
+    ) -> io::Result<()> {
+        let destinations = match self.in_progress() && !self.engine_to_move() {
+            true => self.destinations(),
+            false => Vec::new(),
+        };
+        for y in 0..8 {
+            for x in 0..8 {
+                let position = Position(x, y);
+                let background = if position == self.cursor && !self.engine_to_move() {
+                    Color::Blue
+                } else if destinations.contains(&position) {
+                    Color::Green
+                } else if highlighted.contains(&position) {
+                    Color::DarkYellow
+                } else if position.square().is_some() {
+                    Color::DarkGreen
+                } else {
+                    Color::Grey
+                };
+                let (foreground, text) = match (board.piece_at(position), position.square()) {
+                    (Some(piece), _) => (
+                        match piece.player {
+                            Player1 => Color::Black,
+                            Player2 => Color::White,
+                        },
+                        self.glyph(piece.player, piece.king).to_string(),
+                    ),
+                    (None, Some(square)) => (Color::DarkGrey, square.to_string()),
+                    (None, None) => (Color::Reset, String::new()),
+                };
+                let (column, row) = self.square_origin(position);
+                queue!(
+                    output,
+                    SetBackgroundColor(background),
+                    SetForegroundColor(foreground),
+                    SetAttribute(Attribute::Bold),
+                    MoveTo(column, row),
+                    Print(format!("{:^width$}", text, width = SQUARE_WIDTH as usize)),
+                    MoveTo(column, row + 1),
+                    Print(" ".repeat(SQUARE_WIDTH as usize)),
+                    SetAttribute(Attribute::Reset),
+                    ResetColor
+                )?;
+            }
+        }
+        self.draw_eval_bar(output)
+    }
+
+    /// Draws the score as a bar beside the board, filled from the bottom with the colour of
+    /// the player at the bottom in proportion to how far they are ahead.
+    fn draw_eval_bar<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let height = 8 * SQUARE_HEIGHT;
+        let score = self.score.unwrap_or(0).clamp(-EVAL_RANGE, EVAL_RANGE);
+        let score = match self.perspective {
+            Player1 => score,
+            Player2 => -score,
+        };
+        let filled = (i32::from(height) * (score + EVAL_RANGE) / (2 * EVAL_RANGE)) as u16;
+        let (bottom, top) = match self.perspective {
+            Player1 => (Color::Black, Color::White),
+            Player2 => (Color::White, Color::Black),
+        };
+        for row in 0..height {
+            let color = if height - row <= filled { bottom } else { top };
+            queue!(
+                output,
+                MoveTo(1, BOARD_TOP + row),
+                SetBackgroundColor(color),
+                Print("  "),
+                ResetColor
+            )?;
+        }
+        let label = match self.score {
+            Some(score) => format!("{:+.1}", f64::from(score) / 100.0),
+            None => String::new(),
+        };
+        queue!(output, MoveTo(0, BOARD_TOP + height), Print(label))
+    }
+
+    /// Draws the turns played so far, numbered in pairs, scrolling to show the latest.
+    fn draw_moves<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        queue!(
+            output,
+            MoveTo(MOVES_LEFT, BOARD_TOP - 1),
+            SetAttribute(Attribute::Bold),
+            Print("Moves"),
+            SetAttribute(Attribute::Reset)
+        )?;
+        let lines = move_lines(self.start.to_move, self.game.turns());
+        let visible = usize::from(8 * SQUARE_HEIGHT);
+        for (row, line) in (0..).zip(lines.iter().skip(lines.len().saturating_sub(visible))) {
+            queue!(output, MoveTo(MOVES_LEFT, BOARD_TOP + row), Print(line))?;
+        }
+        Ok(())
+    }
+
+    fn glyph(&self, player: Player, king: bool) -> char {
+        match (self.options.unicode, player, king) {
+            (false, Player1, false) => 'b',
+            (false, Player1, true) => 'B',
+            (false, Player2, false) => 'w',
+            (false, Player2, true) => 'W',
+            (true, _, false) => '●',
+            (true, _, true) => '♛',
+        }
+    }
+}
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        // Errors can't be reported here, and the terminal is left as well as possible anyway
+        let _ = execute!(
+            io::stdout(),
+            Show,
+            DisableMouseCapture,
+            LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Returns the lines of the move list, with Black's and White's turns of each move on one line.
+fn move_lines(start: Player, turns: &[Turn]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut player = start;
+    for (i, turn) in turns.iter().enumerate() {
+        match player {
+            Player1 => lines.push(format!("{:>3}. {:<9}", lines.len() + 1, turn.to_string())),
+            Player2 if i == 0 => lines.push(format!("{:>3}. {:<9}{}", 1, "...", turn)),
+            Player2 => match lines.last_mut() {
+                Some(line) => line.push_str(&turn.to_string()),
+                None => unreachable!("White's turn should follow Black's"),
+            },
+        }
+        player = player.opponent();
+    }
+    lines
+}
+
+/// Returns a score for the player to move as a score for Player1.
+fn score_for_player1(score: i32, player: Player) -> i32 {
+    match player {
+        Player1 => score,
+        Player2 => -score,
+    }
+}
+
+fn controller_name(controller: Controller) -> &'static str {
+    match controller {
+        Controller::Human => "human",
+        Controller::Engine => "engine",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::process;
+
+    fn press(tui: &mut Tui, code: KeyCode) -> Action {
+        tui.key(code, KeyModifiers::NONE)
+    }
+
+    fn human_game(fen: &str) -> Tui {
+        Tui::new(Options {
+            player2: Controller::Human,
+            start: fen.parse().unwrap(),
+            ..Options::default()
+        })
+    }
+
+    #[test]
+    fn should_select_pieces_and_destinations() {
+        let mut tui = human_game("B:W21-32:B1-12");
+
+        assert_eq!(tui.cursor, Position(1, 2));
+        assert_eq!(press(&mut tui, KeyCode::Enter), Action::Continue);
+        assert_eq!(tui.destinations(), vec![Position(0, 3), Position(2, 3)]);
+        assert_eq!(press(&mut tui, KeyCode::Esc), Action::Continue);
+        assert_eq!(tui.destinations().len(), 4);
+
+        press(&mut tui, KeyCode::Right);
+        press(&mut tui, KeyCode::Right);
+        press(&mut tui, KeyCode::Enter);
+        assert_eq!(tui.destinations(), vec![Position(2, 3), Position(4, 3)]);
+        press(&mut tui, KeyCode::Down);
+        press(&mut tui, KeyCode::Right);
+        assert_eq!(
+            press(&mut tui, KeyCode::Char(' ')),
+            Action::Play("10-15".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn should_choose_multi_jumps_one_step_at_a_time() {
+        let mut tui = human_game("B:W14,15,23:B10");
+        let turn = "10x19x26".parse().unwrap();
+
+        assert_eq!(tui.choose(Position(3, 2)), Action::Continue);
+        assert_eq!(tui.destinations(), vec![Position(1, 4), Position(5, 4)]);
+        assert_eq!(tui.choose(Position(5, 4)), Action::Continue);
+        assert_eq!(tui.choose(Position(3, 6)), Action::Play(turn));
+        assert_eq!(tui.frames(turn).len(), 1);
+        assert!(tui.choose(Position(0, 0)) == Action::Continue && tui.path.is_empty());
+    }
+
+    #[test]
+    fn should_map_clicks_to_squares() {
+        let mut tui = human_game("B:W21-32:B1-12");
+        let (column, row) = tui.square_origin(Position(5, 2));
+
+        assert_eq!(tui.position_at(column + 1, row + 1), Some(Position(5, 2)));
+        assert_eq!(tui.position_at(0, row), None);
+        press(&mut tui, KeyCode::Char('f'));
+        let (column, row) = tui.square_origin(Position(5, 2));
+        assert_eq!(tui.position_at(column, row), Some(Position(5, 2)));
+    }
+
+    #[test]
+    fn should_list_moves_in_pairs() {
+        let turns: Vec<Turn> = ["11-15", "23-19", "8-11"]
+            .iter()
+            .map(|turn| turn.parse().unwrap())
+            .collect();
+
+        assert_eq!(
+            move_lines(Player1, &turns),
+            vec!["  1. 11-15    23-19", "  2. 8-11     "]
+        );
+        assert_eq!(
+            move_lines(Player2, &turns[1..2]),
+            vec!["  1. ...      23-19"]
+        );
+    }
+
+    #[test]
+    fn should_load_games() {
+        let path = std::env::temp_dir().join(format!("bit_checkers_tui_{}.pdn", process::id()));
+        fs::write(&path, "[FEN \"W:W21,22:B1\"]\n1... 21-17 2. 1-5 *\n").unwrap();
+        let tui = Tui::new(Options {
+            player2: Controller::Human,
+            load: Some(path.clone()),
+            ..Options::default()
+        });
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(tui.game.turns().len(), 2);
+        assert_eq!(tui.start.to_move, Player2);
+        assert!(tui.message.starts_with("Loaded 2 moves from "));
+
+        let tui = Tui::new(Options {
+            load: Some(path),
+            ..Options::default()
+        });
+        assert!(tui.game.turns().is_empty());
+        assert!(tui.message.starts_with("Could not load "));
+    }
+
+    #[test]
+    fn should_draw_and_undo() {
+        let mut tui = human_game("B:W21-32:B1-12");
+        let mut output = Vec::new();
+        tui.play(&mut output, "11-15".parse().unwrap()).unwrap();
+        tui.draw(&mut output).unwrap();
+        let text = String::from_utf8_lossy(&output);
+
+        assert!(text.contains("  1. 11-15"));
+        assert!(text.contains("Black played 11-15."));
+        assert!(tui.score.is_some());
+        press(&mut tui, KeyCode::Char('u'));
+        assert!(tui.game.turns().is_empty());
+        assert_eq!(press(&mut tui, KeyCode::Char('q')), Action::Quit);
+    }
+}
@@ -0,0 +1,283 @@
+use super::board::{Move, Position};
+use core::fmt;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Maximum number of positions visited by a piece during a single turn.
+///
+/// A turn can capture at most 12 pieces, so it visits at most 13 positions.
+const MAX_TURN_LENGTH: usize = 13;
+/// Maximum number of legal turns available in any position.
+const MAX_TURNS: usize = 128;
+
+/// Type representing a complete turn for one player.
+///
+/// A turn is either a single normal move, or a sequence of jump moves made by the same piece.
+/// It is stored as the path of positions visited by the moving piece.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Turn {
+    path: [u8; MAX_TURN_LENGTH],
+    len: u8,
+}
+
+/// Fixed capacity list of turns, used to generate turns without allocating.
+pub(crate) struct TurnList {
+    turns: [Turn; MAX_TURNS],
+    len: usize,
+}
+
+/// Error returned when parsing a turn from standard notation fails.
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseTurnError(String);
+
+impl Turn {
+    /// Returns a turn consisting of a single move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Move, Position};
+    /// let turn = bit_checkers::turn::Turn::from_move(Move {
+    ///     from: Position(1, 2),
+    ///     to: Position(0, 3),
+    /// });
+    ///
+    /// assert_eq!(turn.to_string(), "9-13");
+    /// ```
+    pub fn from_move(Move { from, to }: Move) -> Turn {
+        let mut path = [0; MAX_TURN_LENGTH];
+        path[0] = Turn::index_of(from);
+        path[1] = Turn::index_of(to);
+        Turn { path, len: 2 }
+    }
+
+    /// Returns a turn with the piece continuing on to another position.
+    ///
+    /// Panics if the turn already visits the maximum number of positions.
+    pub fn then(mut self, to: Position) -> Turn {
+        assert!(
+            (self.len as usize) < MAX_TURN_LENGTH,
+            "turn should not exceed maximum length"
+        );
+        self.path[self.len as usize] = Turn::index_of(to);
+        self.len += 1;
+        self
+    }
+
+    /// Returns the position the piece starts the turn at.
+    pub fn from(&self) -> Position {
+        Turn::position_of(self.path[0])
+    }
+
+    /// Returns the position the piece finishes the turn at.
+    pub fn to(&self) -> Position {
+        Turn::position_of(self.path[self.len as usize - 1])
+    }
+
+    /// Returns whether the turn is made up of jump moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let normal: bit_checkers::turn::Turn = "11-15".parse().unwrap();
+    /// let jump: bit_checkers::turn::Turn = "15x24x31".parse().unwrap();
+    ///
+    /// assert!(!normal.is_jump());
+    /// assert!(jump.is_jump());
+    /// ```
+    pub fn is_jump(&self) -> bool {
+        let Position(from_x, _) = self.from();
+        let Position(to_x, _) = Turn::position_of(self.path[1]);
+        (from_x as i32 - to_x as i32).abs() != 1
+    }
+
+    /// Returns an iterator over every position visited during the turn, including the start.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.path[..self.len as usize]
+            .iter()
+            .map(|&index| Turn::position_of(index))
+    }
+
+    /// Returns an iterator over the individual moves making up the turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_checkers::board::{Move, Position};
+    /// let turn: bit_checkers::turn::Turn = "15x24x31".parse().unwrap();
+    /// let moves: Vec<Move> = turn.moves().collect();
+    ///
+    /// assert_eq!(moves.len(), 2);
+    /// assert_eq!(moves[1], Move { from: Position(6, 5), to: Position(4, 7) });
+    /// ```
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.path[..self.len as usize].windows(2).map(|pair| Move {
+            from: Turn::position_of(pair[0]),
+            to: Turn::position_of(pair[1]),
+        })
+    }
+
+    /// Returns whether this turn matches the given notation.
+    ///
+    /// The notation matches when it starts and ends at the same positions as the turn, and any
+    /// intermediate positions it lists are visited by the turn in the same order. This allows
+    /// multi-jumps to be written with only their end points (e.g. `1x10`).
+    pub fn matches(&self, notation: &Turn) -> bool {
+        if self.from() != notation.from() || self.to() != notation.to() {
+            return false;
+        }
+        let mut remaining = self.positions();
+        notation
+            .positions()
+            .all(|position| remaining.any(|visited| visited == position))
+    }
+
+    fn index_of(position: Position) -> u8 {
+        position.index() as u8
+    }
+
+    fn position_of(index: u8) -> Position {
+        Position::from_index(index as u32).expect("turn should only contain valid positions")
+    }
+}
+
+impl TurnList {
+    pub(crate) fn new() -> TurnList {
+        TurnList {
+            turns: [Turn {
+                path: [0; MAX_TURN_LENGTH],
+                len: 0,
+            }; MAX_TURNS],
+            len: 0,
+        }
+    }
+
+    /// Adds a turn to the end of the list.
+    ///
+    /// Panics if the list is full.
+    pub(crate) fn push(&mut self, turn: Turn) {
+        self.turns[self.len] = turn;
+        self.len += 1;
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl IntoIterator for TurnList {
+    type Item = Turn;
+    type IntoIter = core::iter::Take<core::array::IntoIter<Turn, MAX_TURNS>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.turns).take(self.len)
+    }
+}
+
+impl fmt::Display for Turn {
+    /// Formats the turn in standard notation using square numbers, e.g. `11-15` or `15x24x31`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.is_jump() { 'x' } else { '-' };
+        for (i, position) in self.positions().enumerate() {
+            if i > 0 {
+                write!(f, "{}", separator)?;
+            }
+            match position.square() {
+                Some(square) => write!(f, "{}", square)?,
+                None => write!(f, "{:?}", position)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Turn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Turn({})", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Turn {
+    type Err = ParseTurnError;
+
+    /// Parses a turn from standard notation using square numbers.
+    ///
+    /// Normal moves are separated by `-` and jumps by `x` or `:`.
+    fn from_str(s: &str) -> Result<Turn, ParseTurnError> {
+        let error = || ParseTurnError(s.to_string());
+        let squares: Vec<&str> = s.split(&['-', 'x', ':'][..]).collect();
+        if squares.len() < 2 || squares.len() > MAX_TURN_LENGTH {
+            return Err(error());
+        }
+        let mut positions = squares.iter().map(|square| {
+            square
+                .parse()
+                .ok()
+                .and_then(Position::from_square)
+                .ok_or_else(error)
+        });
+        let from = positions.next().unwrap()?;
+        let to = positions.next().unwrap()?;
+        let mut turn = Turn::from_move(Move { from, to });
+        for position in positions {
+            turn = turn.then(position?);
+        }
+        let normal = s.contains('-');
+        if normal == (s.contains('x') || s.contains(':'))
+            || normal != (turn.len == 2 && !turn.is_jump())
+        {
+            return Err(error());
+        }
+        Ok(turn)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseTurnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid turn notation \"{}\"", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseTurnError {}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_and_format_turns() {
+        for notation in &["11-15", "22x15", "15x24x31", "32-27"] {
+            let turn: Turn = notation.parse().unwrap();
+            assert_eq!(&turn.to_string(), notation);
+        }
+        let turn: Turn = "9:18:27".parse().unwrap();
+        assert_eq!(turn.to_string(), "9x18x27");
+    }
+
+    #[test]
+    fn should_reject_invalid_notation() {
+        for notation in &[
+            "", "11", "11-", "0-5", "11-33", "a-b", "11-15-19", "11x15", "9x18-27",
+        ] {
+            assert!(notation.parse::<Turn>().is_err(), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn should_match_shorthand_notation() {
+        let turn: Turn = "1x10x19".parse().unwrap();
+        let shorthand: Turn = "1x19".parse().unwrap();
+        let other: Turn = "1x10".parse().unwrap();
+
+        assert!(turn.matches(&shorthand));
+        assert!(turn.matches(&turn));
+        assert!(!turn.matches(&other));
+        assert!(!shorthand.matches(&turn));
+    }
+}
@@ -1,2 +1,4 @@
 mod bit_grid;
+mod rng;
 pub use bit_grid::BitGrid;
+pub use rng::Rng;
@@ -0,0 +1,65 @@
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hasher};
+
+/// Small, fast pseudo-random number generator (xorshift64*).
+///
+/// Not suitable for cryptographic use.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Returns a generator producing a fixed sequence determined by the seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut rng1 = bit_checkers::checkers::util::Rng::new(42);
+    /// let mut rng2 = bit_checkers::checkers::util::Rng::new(42);
+    ///
+    /// assert_eq!(rng1.next_u64(), rng2.next_u64());
+    /// ```
+    pub fn new(seed: u64) -> Rng {
+        // A zero state would only ever produce zeros
+        Rng {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    /// Returns a generator seeded from the process' source of randomness.
+    #[cfg(feature = "std")]
+    pub fn from_entropy() -> Rng {
+        Rng::new(RandomState::new().build_hasher().finish())
+    }
+
+    /// Returns the next random value.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a random value less than the given bound.
+    ///
+    /// Panics if the bound is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut rng = bit_checkers::checkers::util::Rng::new(7);
+    ///
+    /// assert!((0..100).all(|_| rng.below(3) < 3));
+    /// ```
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound should be positive");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
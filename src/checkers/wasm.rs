@@ -0,0 +1,185 @@
+use super::board::{Board, Player, Player1, Player2};
+use super::engine::{Engine, SearchLimits};
+use super::fen::Fen;
+use super::turn::Turn;
+use wasm_bindgen::prelude::*;
+
+/// Largest transposition table size in megabytes, leaving room in the 4 GB memory of WebAssembly.
+const MAX_HASH_SIZE: usize = 2048;
+
+/// A board together with the player to move. Players are `1` for Player1 (Black), who moves
+/// first, and `2` for Player2 (White). Moves are strings in standard notation such as `11-15` or
+/// `9x18x27`.
+#[wasm_bindgen(js_name = Board)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct WasmBoard {
+    board: Board,
+    to_move: Player,
+}
+
+/// An engine, which keeps its transposition table between searches.
+#[wasm_bindgen(js_name = Engine)]
+pub struct WasmEngine(Engine);
+
+/// The result of a search, with the score for the player to move where a man is worth 100.
+#[wasm_bindgen(js_name = SearchResult, getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct WasmSearchResult {
+    /// The best move found, or undefined if the player has no legal moves.
+    #[wasm_bindgen(js_name = move)]
+    pub turn: Option<String>,
+    pub score: i32,
+    pub depth: u32,
+    /// Number of positions searched, as a double to avoid a BigInt.
+    pub nodes: f64,
+    /// The best line of play found, starting with the move.
+    pub pv: Vec<String>,
+}
+
+#[wasm_bindgen(js_class = Board)]
+impl WasmBoard {
+    /// Returns the initial board with Player1 to move.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmBoard {
+        WasmBoard::from(Fen::new())
+    }
+
+    /// Returns a board parsed from a FEN string such as `B:W21-32:B1-12`.
+    #[wasm_bindgen(js_name = fromFen)]
+    pub fn from_fen(fen: &str) -> Result<WasmBoard, JsError> {
+        let fen: Fen = fen.parse()?;
+        Ok(WasmBoard::from(fen))
+    }
+
+    /// Returns the board as a FEN string.
+    pub fn fen(&self) -> String {
+        Fen {
+            board: self.board.clone(),
+            to_move: self.to_move,
+        }
+        .to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = toMove)]
+    pub fn to_move(&self) -> u8 {
+        player_value(self.to_move)
+    }
+
+    /// Returns the legal moves for the player to move.
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String> {
+        self.board
+            .turns(self.to_move)
+            .map(|turn| turn.to_string())
+            .collect()
+    }
+
+    /// Returns the board after the player to move makes a move, throwing if it is not legal.
+    ///
+    /// Jumps may be written with only their end points if they are not ambiguous.
+    #[wasm_bindgen(js_name = applyMove)]
+    pub fn apply_move(&self, notation: &str) -> Result<WasmBoard, JsError> {
+        let turn = notation
+            .parse::<Turn>()
+            .ok()
+            .and_then(|turn| self.board.find_turn(self.to_move, &turn))
+            .ok_or_else(|| JsError::new(&format!("illegal move {}", notation)))?;
+        Ok(WasmBoard {
+            board: self.board.clone().apply_turn(self.to_move, turn),
+            to_move: self.to_move.opponent(),
+        })
+    }
+
+    /// Returns the winner if the player to move can't move, or undefined if the game is in
+    /// progress.
+    pub fn winner(&self) -> Option<u8> {
+        match self.board.turns(self.to_move).next() {
+            Some(_) => None,
+            None => Some(player_value(self.to_move.opponent())),
+        }
+    }
+}
+
+#[wasm_bindgen(js_class = Engine)]
+impl WasmEngine {
+    /// Returns an engine with a transposition table of approximately the given size in
+    /// megabytes, which must fit in the 4 GB memory of WebAssembly.
+    #[wasm_bindgen(constructor)]
+    pub fn new(hash_size: usize) -> Result<WasmEngine, JsError> {
+        if hash_size > MAX_HASH_SIZE {
+            return Err(JsError::new(&format!(
+                "hash size must be at most {} megabytes",
+                MAX_HASH_SIZE
+            )));
+        }
+        Ok(WasmEngine(Engine::new().with_hash_size(hash_size)))
+    }
+
+    /// Searches for the best move for the player to move, stopping after about the given number
+    /// of positions.
+    ///
+    /// The search runs on the calling thread, so in a browser it should be run in a worker.
+    pub fn search(&mut self, board: &WasmBoard, nodes: u32) -> WasmSearchResult {
+        let limits = SearchLimits {
+            nodes: Some(u64::from(nodes)),
+            ..SearchLimits::default()
+        };
+        let result = self.0.search(&board.board, board.to_move, limits);
+        WasmSearchResult {
+            turn: result.turn.map(|turn| turn.to_string()),
+            score: result.score,
+            depth: result.depth,
+            nodes: result.nodes as f64,
+            pv: result.pv.iter().map(|turn| turn.to_string()).collect(),
+        }
+    }
+
+    /// Clears the transposition table, so that later searches don't depend on earlier ones.
+    #[wasm_bindgen(js_name = clearHash)]
+    pub fn clear_hash(&mut self) {
+        self.0.clear_hash();
+    }
+}
+
+impl From<Fen> for WasmBoard {
+    fn from(Fen { board, to_move }: Fen) -> WasmBoard {
+        WasmBoard { board, to_move }
+    }
+}
+
+impl Default for WasmBoard {
+    fn default() -> WasmBoard {
+        WasmBoard::new()
+    }
+}
+
+/// Returns the JavaScript value of a player.
+fn player_value(player: Player) -> u8 {
+    match player {
+        Player1 => 1,
+        Player2 => 2,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_apply_moves() {
+        let board = WasmBoard::new().apply_move("11-15").unwrap();
+
+        assert_eq!(WasmBoard::new().legal_moves().len(), 7);
+        assert_eq!(board.to_move(), 2);
+        assert_eq!(WasmBoard::from_fen(&board.fen()).unwrap(), board);
+    }
+
+    #[test]
+    fn should_search_with_node_limit() {
+        let board = WasmBoard::from_fen("B:W18,27:B14").unwrap();
+        let result = WasmEngine::new(1).unwrap().search(&board, 1000);
+
+        assert_eq!(result.turn.as_deref(), Some("14x23x32"));
+        assert_eq!(board.apply_move("14x32").unwrap().winner(), Some(1));
+    }
+}
@@ -1,2 +1,51 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Tests run with std even when the library is built without it
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
 pub mod checkers;
+#[cfg(feature = "std")]
+pub use checkers::arena;
+#[cfg(feature = "std")]
+pub use checkers::args;
+#[cfg(feature = "std")]
+pub use checkers::ballot;
 pub use checkers::board;
+#[cfg(feature = "std")]
+pub use checkers::book;
+#[cfg(feature = "checkerboard")]
+pub use checkers::checkerboard;
+#[cfg(feature = "std")]
+pub use checkers::cli;
+#[cfg(feature = "std")]
+pub use checkers::clock;
+#[cfg(feature = "std")]
+pub use checkers::diagram;
+#[cfg(feature = "std")]
+pub use checkers::dxp;
+#[cfg(feature = "std")]
+pub use checkers::encoding;
+#[cfg(feature = "std")]
+pub use checkers::engine;
+#[cfg(feature = "std")]
+pub use checkers::fen;
+#[cfg(feature = "ffi")]
+pub use checkers::ffi;
+#[cfg(feature = "std")]
+pub use checkers::game;
+#[cfg(feature = "std")]
+pub use checkers::hub;
+#[cfg(feature = "std")]
+pub use checkers::pdn;
+#[cfg(feature = "python")]
+pub use checkers::python;
+#[cfg(feature = "std")]
+pub use checkers::render;
+#[cfg(feature = "std")]
+pub use checkers::svg;
+#[cfg(feature = "tui")]
+pub use checkers::tui;
+pub use checkers::turn;
+#[cfg(feature = "wasm")]
+pub use checkers::wasm;
@@ -1,30 +1,27 @@
-use bit_checkers::board::{Board, Move, Player1, Player2, Position};
+use bit_checkers::cli::{self, Options};
+use std::env;
+use std::io;
+use std::process;
 
-fn main() {
-    let board = Board::new();
-    let board = board.move_piece(
-        Player1,
-        Move {
-            from: Position(1, 2),
-            to: Position(1, 4),
-        },
-    );
-
-    println!("{:?}", board);
-    println!("Player1 normal moves:");
-    for m in board.normal_moves(Player1) {
-        println!("{:?}", m);
-    }
-    println!("Player2 normal moves:");
-    for m in board.normal_moves(Player2) {
-        println!("{:?}", m);
-    }
-    println!("Player1 jump moves:");
-    for m in board.jump_moves(Player1) {
-        println!("{:?}", m);
+/// Plays checkers in the terminal, against the engine or another person. See `cli::USAGE` for
+/// the options.
+fn main() -> io::Result<()> {
+    if env::args().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", cli::USAGE);
+        return Ok(());
     }
-    println!("Player2 jump moves:");
-    for m in board.jump_moves(Player2) {
-        println!("{:?}", m);
+    let options = match Options::from_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    #[cfg(feature = "tui")]
+    {
+        if options.tui {
+            return bit_checkers::tui::run(options);
+        }
     }
+    cli::run(options, io::stdin().lock(), io::stdout())
 }
@@ -0,0 +1,61 @@
+/*
+ * Plays a game through the C API, always choosing the first legal move.
+ *
+ * Build and run from the repository root with:
+ *
+ *     cargo build --lib --features ffi
+ *     cc -Wall -Iinclude tests/c/ffi_test.c target/debug/libbit_checkers.a -lpthread -ldl -lm
+ *     ./a.out
+ */
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "bit_checkers.h"
+
+#define MAX_MOVES 64
+#define MAX_TURNS 300
+
+int main(void) {
+    BcMove moves[MAX_MOVES];
+    char text[128];
+
+    BcBoard *board = bc_board_new();
+    assert(board != NULL);
+    assert(bc_board_to_move(board) == BC_PLAYER1);
+    assert(bc_board_legal_moves(board, NULL, 0) == 7);
+
+    size_t count = bc_board_legal_moves(board, moves, MAX_MOVES);
+    assert(count == 7);
+    bc_move_notation(&moves[0], text, sizeof text);
+    assert(strcmp(text, "9-13") == 0);
+
+    BcMove illegal = {2, {1, 5}};
+    assert(bc_board_apply_move(board, &illegal) == BC_ERROR_ILLEGAL_MOVE);
+
+    int turns = 0;
+    while (bc_board_winner(board) == BC_NONE && turns < MAX_TURNS) {
+        count = bc_board_legal_moves(board, moves, MAX_MOVES);
+        assert(count > 0 && count <= MAX_MOVES);
+        assert(bc_board_apply_move(board, &moves[0]) == BC_OK);
+        turns++;
+    }
+    size_t len = bc_board_fen(board, text, sizeof text);
+    assert(len == strlen(text));
+    printf("%d turns, winner %d, position %s\n", turns, bc_board_winner(board), text);
+    bc_board_free(board);
+
+    board = bc_board_from_fen("B:W18,27:B14");
+    assert(board != NULL);
+    assert(bc_board_legal_moves(board, moves, MAX_MOVES) == 1);
+    bc_move_notation(&moves[0], text, sizeof text);
+    assert(strcmp(text, "14x23x32") == 0);
+    assert(bc_board_apply_move(board, &moves[0]) == BC_OK);
+    assert(bc_board_winner(board) == BC_PLAYER1);
+    bc_board_free(board);
+
+    assert(bc_board_from_fen("not a position") == NULL);
+
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,46 @@
+use bit_checkers::arena::{self, EngineSpec, MatchOptions};
+use bit_checkers::ballot::Ballot;
+use bit_checkers::engine::SearchLimits;
+
+/// Returns a spec for the hub binary built with the tests.
+fn hub_spec() -> EngineSpec {
+    format!(
+        "hub name=hub command={} depth=2 hash=1",
+        env!("CARGO_BIN_EXE_bit_checkers_hub")
+    )
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn should_play_game_against_hub_engine() {
+    let mut hub = hub_spec().create().unwrap();
+    let mut builtin = EngineSpec::builtin("builtin", SearchLimits::depth(2))
+        .create()
+        .unwrap();
+    let ballot = Ballot::get(10).unwrap();
+    let game = arena::play_game(hub.as_mut(), builtin.as_mut(), &ballot, 30).unwrap();
+
+    assert!(game.outcome().is_some() || game.turns().len() == 30);
+}
+
+#[test]
+fn should_play_match_against_hub_engine() {
+    let options = MatchOptions {
+        engines: [
+            hub_spec(),
+            EngineSpec::builtin("builtin", SearchLimits::depth(2)),
+        ],
+        games: 2,
+        concurrency: 2,
+        sprt: None,
+        max_turns: 20,
+    };
+    let mut output = Vec::new();
+    let result = arena::run(&options, &mut output).unwrap();
+
+    assert_eq!(result.score.games(), 2);
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("Score of hub vs builtin"));
+}
@@ -0,0 +1,83 @@
+use bit_checkers::board::{Board, Player};
+use bit_checkers::pdn::{self, PdnGame, PdnMove};
+use std::fs;
+use std::path::PathBuf;
+
+/// Returns the games in a file in `tests/data`.
+fn read_games(name: &str) -> Vec<PdnGame> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join(name);
+    let text = fs::read_to_string(&path).unwrap();
+    pdn::parse_games(&text).unwrap()
+}
+
+/// Replays the moves from the board, along with every variation.
+fn replay_all(mut board: Board, mut player: Player, moves: &[PdnMove]) {
+    for pdn_move in moves {
+        for variation in &pdn_move.variations {
+            replay_all(board.clone(), player, &variation.moves);
+        }
+        let turn = board.find_turn(player, &pdn_move.turn).unwrap();
+        board = board.apply_turn(player, turn);
+        player = player.opponent();
+    }
+}
+
+/// Checks that every game can be replayed and is read back unchanged after being written.
+fn assert_round_trip(games: &[PdnGame]) {
+    for game in games {
+        let start = game.start().unwrap();
+        replay_all(start.board, start.to_move, &game.moves);
+    }
+    let mut text = Vec::new();
+    pdn::write_games(&mut text, games).unwrap();
+
+    assert_eq!(
+        pdn::parse_games(&String::from_utf8(text).unwrap()).unwrap(),
+        games
+    );
+}
+
+#[test]
+fn should_round_trip_engine_games() {
+    let games = read_games("engine_sparring.pdn");
+
+    assert_eq!(games.len(), 6);
+    assert!(games
+        .iter()
+        .all(|game| game.tag("Event") == Some("Engine sparring")));
+    assert_eq!(games[0].replay().unwrap()[27].to_string(), "31x22x13x6");
+    assert_round_trip(&games);
+}
+
+#[test]
+fn should_round_trip_annotated_games() {
+    let games = read_games("annotated.pdn");
+
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[0].tag("Opening"), Some("Old Fourteenth"));
+    assert_eq!(games[0].moves[11].variations[0].moves.len(), 3);
+    assert_eq!(games[0].moves[12].nags, vec![5]);
+    assert_eq!(games[2].moves[6].nags, vec![4]);
+    assert_eq!(games[1].result.as_deref(), Some("1/2-1/2"));
+    assert_eq!(
+        games[1].moves.last().unwrap().comment.as_deref(),
+        Some("Agreed drawn; the position is level.")
+    );
+    assert_eq!(games[2].start().unwrap().to_move, Player::Player2);
+    assert_round_trip(&games);
+}
+
+#[test]
+fn should_read_games_in_other_export_layouts() {
+    let games = read_games("crlf_export.pdn");
+
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].tag("Date"), Some("2023.11.04"));
+    assert_eq!(games[0].replay().unwrap()[27].to_string(), "31x22x13x6");
+    assert_eq!(games[1].replay().unwrap()[7].to_string(), "23x14x7");
+    assert_eq!(games[1].result.as_deref(), Some("0-1"));
+    assert_round_trip(&games);
+}
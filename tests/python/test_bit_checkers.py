@@ -0,0 +1,93 @@
+"""Tests for the Python extension module.
+
+Build and install the module with `maturin develop`, then run with:
+
+    python -m unittest discover tests/python
+"""
+import threading
+import unittest
+
+import bit_checkers
+from bit_checkers import Board, Engine, Move
+
+
+class BoardTest(unittest.TestCase):
+    def test_legal_moves(self):
+        board = Board()
+        moves = board.legal_moves()
+
+        self.assertEqual(len(moves), 7)
+        self.assertEqual(board.to_move, 1)
+        self.assertIn(Move("11-15"), moves)
+        self.assertEqual(len({str(move) for move in moves}), 7)
+
+    def test_apply_move(self):
+        board = Board().apply_move(Move("11-15"))
+
+        self.assertEqual(board.to_move, 2)
+        self.assertEqual(Board.from_fen(board.fen()), board)
+        with self.assertRaises(ValueError):
+            board.apply_move(Move("11-15"))
+
+    def test_jumps_and_winner(self):
+        board = Board.from_fen("B:W18,27:B14")
+        (jump,) = board.legal_moves()
+
+        self.assertTrue(jump.is_jump)
+        self.assertEqual(jump.squares, [14, 23, 32])
+        self.assertEqual(board.apply_move(Move("14x32")).winner(), 1)
+        self.assertIsNone(board.winner())
+        with self.assertRaises(ValueError):
+            Board.from_fen("B:W1:B1")
+
+    def test_planes(self):
+        board = Board.from_fen("B:WK32:B1")
+        planes = board.planes()
+
+        self.assertEqual(len(planes), 4 * 64)
+        self.assertEqual(board.bitboards(), (1 << 1, 0, 1 << 62, 1 << 62))
+        self.assertEqual(planes[1], 1)
+        self.assertEqual(planes[3 * 64 + 62], 1)
+        self.assertEqual(sum(planes), 2)
+
+
+class EngineTest(unittest.TestCase):
+    def test_search(self):
+        result = Engine().search(Board(), depth=4)
+
+        self.assertEqual(result.depth, 4)
+        self.assertIn(result.move, Board().legal_moves())
+        self.assertEqual(result.pv[0], result.move)
+        with self.assertRaises(ValueError):
+            Engine().search(Board())
+
+    def test_hash_size_limit(self):
+        with self.assertRaises(ValueError):
+            Engine(hash_size=2**40)
+
+    def test_search_in_thread(self):
+        results = []
+        thread = threading.Thread(
+            target=lambda: results.append(Engine().search(Board(), nodes=10000))
+        )
+        thread.start()
+        thread.join()
+
+        self.assertIsNotNone(results[0].move)
+
+
+class PdnTest(unittest.TestCase):
+    def test_read_and_write(self):
+        (game,) = bit_checkers.parse_pdn('[Event "Example"] 1. 11-15 23-19 *')
+        written = bit_checkers.PdnGame(game.moves(), game.tags, game.result)
+
+        self.assertEqual(game.tags, [("Event", "Example")])
+        self.assertEqual(game.start(), Board())
+        self.assertEqual([str(move) for move in game.moves()], ["11-15", "23-19"])
+        self.assertEqual(str(written), str(game))
+        with self.assertRaises(ValueError):
+            bit_checkers.parse_pdn("1. 11-15 [")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,34 @@
+//! Tests for the JavaScript bindings, run in Node.js with:
+//!
+//! ```text
+//! cargo test --target wasm32-unknown-unknown --features wasm --test wasm
+//! ```
+//!
+//! This needs `wasm-bindgen-test-runner` from `wasm-bindgen-cli` as the runner for the target,
+//! see `.cargo/config.toml`.
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use bit_checkers::wasm::{WasmBoard, WasmEngine};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn should_play_moves() {
+    let board = WasmBoard::new();
+    let board = board.apply_move(&board.legal_moves()[0]).unwrap();
+
+    assert_eq!(board.to_move(), 2);
+    assert_eq!(WasmBoard::from_fen(&board.fen()).unwrap(), board);
+    assert!(board.apply_move("9-13").is_err());
+    assert!(WasmBoard::from_fen("B:W1:B1").is_err());
+}
+
+#[wasm_bindgen_test]
+fn should_search_without_clock() {
+    let mut engine = WasmEngine::new(1).unwrap();
+    let result = engine.search(&WasmBoard::new(), 5000);
+
+    assert!(result.turn.is_some());
+    assert!(result.depth > 0);
+    assert!(result.nodes <= 6000.0);
+    assert!(WasmEngine::new(4096).is_err());
+}